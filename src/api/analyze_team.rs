@@ -0,0 +1,105 @@
+use crate::api::Status;
+use crate::domain::analyze_team;
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct Request {
+    members: Vec<Member>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    number: u16,
+    move_types: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    members: Vec<MemberSummary>,
+    offense: Vec<OffenseRow>,
+    defense: Vec<DefenseRow>,
+    uncovered_types: Vec<String>,
+    shared_weaknesses: Vec<String>,
+    unresolved_weaknesses: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct MemberSummary {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct OffenseRow {
+    defending_type: String,
+    multipliers: Vec<f32>,
+}
+
+#[derive(Serialize)]
+struct DefenseRow {
+    attacking_type: String,
+    multipliers: Vec<f32>,
+    weak: usize,
+    resistant: usize,
+    immune: usize,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => analyze_team::Request {
+            members: req
+                .members
+                .into_iter()
+                .map(|m| analyze_team::Member {
+                    number: m.number,
+                    move_types: m.move_types,
+                })
+                .collect(),
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let _span = Span::enter("analyze_team");
+    match analyze_team::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            members: res
+                .members
+                .into_iter()
+                .map(|m| MemberSummary {
+                    number: m.number,
+                    name: m.name,
+                    types: m.types,
+                })
+                .collect(),
+            offense: res
+                .offense
+                .into_iter()
+                .map(|row| OffenseRow {
+                    defending_type: row.defending_type,
+                    multipliers: row.multipliers,
+                })
+                .collect(),
+            defense: res
+                .defense
+                .into_iter()
+                .map(|row| DefenseRow {
+                    attacking_type: row.attacking_type,
+                    multipliers: row.multipliers,
+                    weak: row.weak,
+                    resistant: row.resistant,
+                    immune: row.immune,
+                })
+                .collect(),
+            uncovered_types: res.uncovered_types,
+            shared_weaknesses: res.shared_weaknesses,
+            unresolved_weaknesses: res.unresolved_weaknesses,
+        }),
+        Err(analyze_team::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(analyze_team::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(analyze_team::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
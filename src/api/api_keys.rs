@@ -0,0 +1,79 @@
+use crate::api::Status;
+use crate::domain::{fetch_all_api_keys, issue_api_key, revoke_api_key};
+use crate::logger::Span;
+use crate::repositories::api_key::ApiKeyRepository;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    role: String,
+}
+
+#[derive(Serialize)]
+struct IssuedResponse {
+    id: u32,
+    name: String,
+    role: String,
+    key: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    id: u32,
+    name: String,
+    role: String,
+    created_at: u64,
+}
+
+pub fn serve_all(repo: Arc<dyn ApiKeyRepository>) -> rouille::Response {
+    let _span = Span::enter("fetch_all_api_keys");
+    match fetch_all_api_keys::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|k| Response {
+                    id: k.id,
+                    name: k.name,
+                    role: k.role,
+                    created_at: k.created_at,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_all_api_keys::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
+
+pub fn serve_issue(repo: Arc<dyn ApiKeyRepository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => issue_api_key::Request {
+            name: req.name,
+            role: req.role,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let _span = Span::enter("issue_api_key");
+    match issue_api_key::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&IssuedResponse {
+            id: res.id,
+            name: res.name,
+            role: res.role,
+            key: res.key,
+        }),
+        Err(issue_api_key::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(issue_api_key::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(issue_api_key::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+pub fn serve_revoke(repo: Arc<dyn ApiKeyRepository>, id: u32) -> rouille::Response {
+    let _span = Span::enter("revoke_api_key");
+    match revoke_api_key::execute(repo, revoke_api_key::Request { id }) {
+        Ok(()) => rouille::Response::from(Status::Ok),
+        Err(revoke_api_key::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(revoke_api_key::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,70 @@
+use crate::api::Status;
+use crate::domain::audit::PokemonSnapshot;
+use crate::domain::fetch_audit;
+use crate::logger::Span;
+use crate::repositories::audit::AuditRepository;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct Response {
+    at: u64,
+    actor: String,
+    action: String,
+    number: u16,
+    form: String,
+    before: Option<Snapshot>,
+    after: Option<Snapshot>,
+}
+
+#[derive(Serialize)]
+struct Snapshot {
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Vec<u16>,
+}
+
+impl From<PokemonSnapshot> for Snapshot {
+    fn from(snapshot: PokemonSnapshot) -> Self {
+        Self {
+            name: snapshot.name,
+            names: snapshot.names.into_iter().collect(),
+            types: snapshot.types,
+            stats: snapshot.stats,
+        }
+    }
+}
+
+pub fn serve(audit_repo: Arc<dyn AuditRepository>, req: &rouille::Request) -> rouille::Response {
+    let (number, since) = match (
+        req.get_param("number").map(|n| n.parse::<u16>()),
+        req.get_param("since").map(|s| s.parse::<u64>()),
+    ) {
+        (Some(Err(_)), _) | (_, Some(Err(_))) => {
+            return rouille::Response::from(Status::BadRequest)
+        }
+        (number, since) => (number.and_then(Result::ok), since.and_then(Result::ok)),
+    };
+
+    let req = fetch_audit::Request { number, since };
+    let _span = Span::enter("fetch_audit");
+    match fetch_audit::execute(audit_repo, req) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|e| Response {
+                    at: e.at,
+                    actor: e.actor,
+                    action: e.action,
+                    number: e.number,
+                    form: e.form,
+                    before: e.before.map(Snapshot::from),
+                    after: e.after.map(Snapshot::from),
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_audit::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_audit::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
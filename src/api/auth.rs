@@ -0,0 +1,82 @@
+use crate::api::{problem, Status};
+use crate::domain::api_key::Role;
+use crate::domain::authorize::{self, Credentials};
+use crate::domain::jwt::JwtError;
+use crate::logger::Span;
+use crate::repositories::api_key::ApiKeyRepository;
+use std::sync::Arc;
+
+/// The least role allowed to make a request, `None` when anyone can, from
+/// its method and the pattern of its path.
+pub fn required_role(method: &str, route: &str) -> Option<Role> {
+    match (method, route) {
+        (_, "/health") | (_, "/health/live") | (_, "/health/ready") | (_, "/metrics") => None,
+        (_, route) if route.starts_with("/api-keys") || route.starts_with("/webhooks") => {
+            Some(Role::Admin)
+        }
+        // The audit log tells who changed what, the holders of the keys included.
+        ("DELETE", _) | ("GET", "/audit") => Some(Role::Admin),
+        ("GET", _) | ("HEAD", _) => Some(Role::Reader),
+        // Computed from the Pokedex without changing it.
+        ("POST", "/team-analysis") | ("POST", "/battles") => Some(Role::Reader),
+        _ => Some(Role::Editor),
+    }
+}
+
+/// Why a token was refused, as told to the client.
+fn reason(error: JwtError) -> &'static str {
+    match error {
+        JwtError::Malformed => "The token is malformed",
+        JwtError::UnsupportedAlgorithm => "The token must be signed with HS256",
+        JwtError::BadSignature => "The signature of the token is not valid",
+        JwtError::MissingExpiration => "The token has no expiration time",
+        JwtError::Expired => "The token has expired",
+        JwtError::NotYetValid => "The token is not valid yet",
+        JwtError::WrongAudience => "The token was not issued for this API",
+        JwtError::MissingRole => "The token does not grant a known role",
+    }
+}
+
+/// The key of `Authorization: Bearer <key>`.
+fn bearer(req: &rouille::Request) -> Option<String> {
+    req.header("Authorization")
+        .and_then(|value| value.trim().strip_prefix("Bearer "))
+        .map(|key| key.trim().to_string())
+}
+
+/// Answers the request with a problem when its key does not allow it.
+pub fn check(
+    repo: Arc<dyn ApiKeyRepository>,
+    credentials: &Credentials,
+    req: &rouille::Request,
+    required: Role,
+) -> Result<authorize::Response, rouille::Response> {
+    let auth_req = authorize::Request {
+        key: bearer(req),
+        required,
+    };
+
+    let _span = Span::enter("authorize");
+    match authorize::execute(repo, credentials, auth_req) {
+        Ok(res) => Ok(res),
+        Err(authorize::Error::Unauthenticated) => Err(problem(
+            401,
+            "Unauthorized",
+            "A valid API key must be sent in the Authorization header as a Bearer token",
+        )
+        .with_additional_header("WWW-Authenticate", "Bearer")),
+        Err(authorize::Error::InvalidToken(error)) => {
+            Err(problem(401, "Unauthorized", reason(error))
+                .with_additional_header("WWW-Authenticate", "Bearer error=\"invalid_token\""))
+        }
+        Err(authorize::Error::Forbidden(role)) => Err(problem(
+            403,
+            "Forbidden",
+            &format!(
+                "The role of the API key, {}, does not allow this request, which needs the {} role",
+                role, required
+            ),
+        )),
+        Err(authorize::Error::Unknown) => Err(rouille::Response::from(Status::InternalServerError)),
+    }
+}
@@ -1,44 +1,63 @@
-use crate::api::Status;
+use crate::api::{Stats, Status};
 use crate::domain::create_pokemon;
+use crate::domain::events::EventBus;
+use crate::domain::generation::Generations;
+use crate::logger::Span;
 use crate::repositories::pokemon::Repository;
-use rouille;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 #[derive(Deserialize)]
 struct Request {
     number: u16,
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    #[serde(default)]
+    stats: Stats,
 }
 
 #[derive(Serialize)]
 struct Response {
     number: u16,
+    form: String,
     name: String,
     types: Vec<String>,
+    stats: Stats,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    generations: &Generations,
+    req: &rouille::Request,
+) -> rouille::Response {
     let req = match rouille::input::json_input::<Request>(req) {
         Ok(req) => create_pokemon::Request {
             number: req.number,
+            form: req.form,
             name: req.name,
             types: req.types,
+            stats: req.stats.into(),
         },
         _ => return rouille::Response::from(Status::BadRequest),
     };
 
     // rouille::Response::from(Status::InternalServerError)
-    match create_pokemon::execute(repo, req) {
+    let _span = Span::enter("create_pokemon");
+    match create_pokemon::execute(repo, events, generations, req) {
         Ok(create_pokemon::Response {
             number,
+            form,
             name,
             types,
+            stats,
         }) => rouille::Response::json(&Response {
             number,
+            form,
             name,
             types,
+            stats: stats.into(),
         }),
         Err(create_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(create_pokemon::Error::Conflict) => rouille::Response::from(Status::Conflict),
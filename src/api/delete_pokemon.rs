@@ -1,12 +1,19 @@
 use crate::api::Status;
 use crate::domain::delete_pokemon;
+use crate::domain::events::EventBus;
+use crate::logger::Span;
 use crate::repositories::pokemon::Repository;
-use rouille;
 use std::sync::Arc;
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
-    let req = delete_pokemon::Request { number };
-    match delete_pokemon::execute(repo, req) {
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    number: u16,
+    form: Option<String>,
+) -> rouille::Response {
+    let req = delete_pokemon::Request { number, form };
+    let _span = Span::enter("delete_pokemon");
+    match delete_pokemon::execute(repo, events, req) {
         Ok(()) => rouille::Response::from(Status::Ok),
         Err(delete_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(delete_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
@@ -0,0 +1,242 @@
+use crate::api::{Stats, Status};
+use crate::domain::entities::Pokemon;
+use crate::domain::events::{Event, Subscriber};
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{self, Read};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Number of events kept to be replayed to the clients reconnecting.
+const REPLAY_BUFFER_SIZE: usize = 256;
+
+/// Delay after which an idle stream receives a comment, so that proxies and
+/// clients do not close it.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Serialize)]
+struct PokemonResponse {
+    number: u16,
+    form: String,
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+impl From<&Pokemon> for PokemonResponse {
+    fn from(pokemon: &Pokemon) -> Self {
+        Self {
+            number: pokemon.number.to_u16(),
+            form: pokemon.form.to_string(),
+            name: pokemon.name.to_string(),
+            names: pokemon
+                .translations
+                .iter()
+                .map(|(l, n)| (String::from(l.clone()), n.to_string()))
+                .collect(),
+            types: pokemon.types.to_vec_string(),
+            stats: Stats::from(pokemon.stats.to_vec_u16()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DeletedResponse {
+    number: u16,
+    form: String,
+}
+
+/// An event ready to be sent, numbered in publication order.
+#[derive(Clone)]
+struct Frame {
+    id: u64,
+    name: &'static str,
+    data: String,
+}
+
+impl Frame {
+    fn try_new(id: u64, event: &Event) -> Result<Self, ()> {
+        let (name, data) = match event {
+            Event::PokemonCreated(pokemon) => (
+                "created",
+                serde_json::to_string(&PokemonResponse::from(pokemon)),
+            ),
+            Event::PokemonUpdated(pokemon) => (
+                "updated",
+                serde_json::to_string(&PokemonResponse::from(pokemon)),
+            ),
+            Event::PokemonDeleted { number, form } => (
+                "deleted",
+                serde_json::to_string(&DeletedResponse {
+                    number: number.to_u16(),
+                    form: form.to_string(),
+                }),
+            ),
+        };
+
+        match data {
+            Ok(data) => Ok(Self { id, name, data }),
+            _ => Err(()),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            self.id, self.name, self.data
+        )
+        .into_bytes()
+    }
+}
+
+struct State {
+    last_id: u64,
+    replay: VecDeque<Frame>,
+    clients: Vec<Sender<Frame>>,
+    /// Set on shutdown, the clients subscribing then only being replayed the
+    /// events they missed.
+    closed: bool,
+}
+
+/// Broadcasts the domain events to the clients of `GET /events`, keeping the
+/// most recent ones for the clients resuming with `Last-Event-ID`.
+pub struct EventStream {
+    state: Mutex<State>,
+}
+
+impl EventStream {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                last_id: 0,
+                replay: VecDeque::with_capacity(REPLAY_BUFFER_SIZE),
+                clients: vec![],
+                closed: false,
+            }),
+        }
+    }
+
+    /// The events published after `last_id` that are still buffered, and the
+    /// ones to come. An id the stream never gave, from before a restart,
+    /// replays the whole buffer.
+    fn subscribe(&self, last_id: Option<u64>) -> Result<(Vec<Frame>, Receiver<Frame>), ()> {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            _ => return Err(()),
+        };
+
+        let replayed = match last_id {
+            Some(id) if id <= state.last_id => {
+                state.replay.iter().filter(|f| f.id > id).cloned().collect()
+            }
+            Some(_) => state.replay.iter().cloned().collect(),
+            None => vec![],
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        if !state.closed {
+            state.clients.push(sender);
+        }
+        Ok((replayed, receiver))
+    }
+
+    /// Ends the streams of every client once they are sent the events
+    /// already published.
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+            state.clients.clear();
+        }
+    }
+}
+
+impl Subscriber for EventStream {
+    fn handle(&self, event: &Event) {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            _ => return,
+        };
+
+        let frame = match Frame::try_new(state.last_id + 1, event) {
+            Ok(frame) => frame,
+            _ => return,
+        };
+
+        state.last_id = frame.id;
+        if state.replay.len() == REPLAY_BUFFER_SIZE {
+            state.replay.pop_front();
+        }
+        state.replay.push_back(frame.clone());
+
+        // The clients that went away are forgotten.
+        state
+            .clients
+            .retain(|client| client.send(frame.clone()).is_ok());
+    }
+}
+
+/// Reads the frames as the body of the response, until the stream is closed
+/// or dropped, a heartbeat being read whenever none came for a while.
+struct Body {
+    receiver: Receiver<Frame>,
+    pending: Vec<u8>,
+    position: usize,
+}
+
+impl Body {
+    fn next(&mut self) -> Option<Vec<u8>> {
+        match self.receiver.recv_timeout(HEARTBEAT_INTERVAL) {
+            Ok(frame) => Some(frame.to_bytes()),
+            Err(RecvTimeoutError::Timeout) => Some(b": heartbeat\n\n".to_vec()),
+            Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+}
+
+impl Read for Body {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.pending.len() {
+            self.pending = match self.next() {
+                Some(bytes) => bytes,
+                None => return Ok(0),
+            };
+            self.position = 0;
+        }
+
+        let read = (self.pending.len() - self.position).min(buf.len());
+        buf[..read].copy_from_slice(&self.pending[self.position..self.position + read]);
+        self.position += read;
+        Ok(read)
+    }
+}
+
+pub fn serve(stream: Arc<EventStream>, req: &rouille::Request) -> rouille::Response {
+    let last_id = req
+        .header("Last-Event-ID")
+        .and_then(|id| id.trim().parse::<u64>().ok());
+
+    let (replayed, receiver) = match stream.subscribe(last_id) {
+        Ok(subscription) => subscription,
+        _ => return rouille::Response::from(Status::InternalServerError),
+    };
+
+    let body = Body {
+        receiver,
+        pending: replayed.iter().flat_map(Frame::to_bytes).collect(),
+        position: 0,
+    };
+
+    // The body has no length, so the server sends every event as soon as it
+    // is read.
+    rouille::Response {
+        status_code: 200,
+        headers: vec![
+            ("Content-Type".into(), "text/event-stream".into()),
+            ("Cache-Control".into(), "no-cache".into()),
+        ],
+        data: rouille::ResponseBody::from_reader(body),
+        upgrade: None,
+    }
+}
@@ -1,29 +1,55 @@
+use crate::logger::Span;
 use std::sync::Arc;
 
 use serde::Serialize;
 
-use crate::{domain::fetch_all_pokemons, repositories::pokemon::Repository};
+use crate::{
+    domain::{fetch_all_pokemons, generation::Generations},
+    repositories::pokemon::Repository,
+};
 
-use super::Status;
+use super::{Stats, Status};
 
 #[derive(Serialize)]
 struct Response {
     number: u16,
+    form: String,
     name: String,
     types: Vec<String>,
+    stats: Stats,
 }
 
-pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
-    match fetch_all_pokemons::execute(repo) {
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    generations: &Generations,
+    req: &rouille::Request,
+    languages: Vec<String>,
+) -> rouille::Response {
+    let generation = match req.get_param("generation").map(|g| g.parse::<u8>()) {
+        Some(Ok(generation)) => Some(generation),
+        Some(Err(_)) => return rouille::Response::from(Status::BadRequest),
+        None => None,
+    };
+
+    let req = fetch_all_pokemons::Request {
+        generation,
+        name: req.get_param("name"),
+        languages,
+    };
+    let _span = Span::enter("fetch_all_pokemons");
+    match fetch_all_pokemons::execute(repo, generations, req) {
         Ok(res) => rouille::Response::json(
             &res.into_iter()
                 .map(|p| Response {
                     number: p.number,
+                    form: p.form,
                     name: p.name,
                     types: p.types,
+                    stats: p.stats.into(),
                 })
                 .collect::<Vec<Response>>(),
         ),
+        Err(fetch_all_pokemons::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(fetch_all_pokemons::Error::Unknown) => {
             rouille::Response::from(Status::InternalServerError)
         }
@@ -1,28 +1,48 @@
-use crate::api::Status;
-use crate::domain::fetch_pokemon;
+use crate::api::{Stats, Status};
+use crate::domain::fetch_pokemon::{self, Identifier};
+use crate::logger::Span;
 use crate::repositories::pokemon::Repository;
-use rouille;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 #[derive(Serialize)]
 struct Response {
     number: u16,
+    form: String,
     name: String,
+    names: BTreeMap<String, String>,
     types: Vec<String>,
+    stats: Stats,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
-    let req = fetch_pokemon::Request { number };
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    identifier: Identifier,
+    form: Option<String>,
+    languages: Vec<String>,
+) -> rouille::Response {
+    let req = fetch_pokemon::Request {
+        identifier,
+        form,
+        languages,
+    };
+    let _span = Span::enter("fetch_pokemon");
     match fetch_pokemon::execute(repo, req) {
         Ok(fetch_pokemon::Response {
             number,
+            form,
             name,
+            names,
             types,
+            stats,
         }) => rouille::Response::json(&Response {
             number,
+            form,
             name,
+            names: names.into_iter().collect(),
             types,
+            stats: stats.into(),
         }),
         Err(fetch_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(fetch_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
@@ -0,0 +1,39 @@
+use crate::api::{Stats, Status};
+use crate::domain::fetch_forms;
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    form: String,
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16, languages: Vec<String>) -> rouille::Response {
+    let req = fetch_forms::Request { number, languages };
+    let _span = Span::enter("fetch_forms");
+    match fetch_forms::execute(repo, req) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|p| Response {
+                    number: p.number,
+                    form: p.form,
+                    name: p.name,
+                    names: p.names.into_iter().collect(),
+                    types: p.types,
+                    stats: p.stats.into(),
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_forms::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_forms::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_forms::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
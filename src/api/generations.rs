@@ -0,0 +1,73 @@
+use crate::api::{Stats, Status};
+use crate::domain::generation::Generations;
+use crate::domain::{fetch_generations, fetch_regional_pokemon};
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct GenerationResponse {
+    number: u8,
+    region: String,
+    first: u16,
+    last: u16,
+}
+
+#[derive(Serialize)]
+struct PokemonResponse {
+    number: u16,
+    form: String,
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+pub fn serve_all(generations: &Generations) -> rouille::Response {
+    let _span = Span::enter("fetch_generations");
+    rouille::Response::json(
+        &fetch_generations::execute(generations)
+            .into_iter()
+            .map(|g| GenerationResponse {
+                number: g.number,
+                region: g.region,
+                first: g.first,
+                last: g.last,
+            })
+            .collect::<Vec<GenerationResponse>>(),
+    )
+}
+
+pub fn serve_regional(
+    repo: Arc<dyn Repository>,
+    generations: &Generations,
+    region: String,
+    number: u16,
+    languages: Vec<String>,
+) -> rouille::Response {
+    let req = fetch_regional_pokemon::Request {
+        region,
+        number,
+        languages,
+    };
+    let _span = Span::enter("fetch_regional_pokemon");
+    match fetch_regional_pokemon::execute(repo, generations, req) {
+        Ok(res) => rouille::Response::json(&PokemonResponse {
+            number: res.number,
+            form: res.form,
+            name: res.name,
+            names: res.names.into_iter().collect(),
+            types: res.types,
+            stats: res.stats.into(),
+        }),
+        Err(fetch_regional_pokemon::Error::BadRequest) => {
+            rouille::Response::from(Status::BadRequest)
+        }
+        Err(fetch_regional_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_regional_pokemon::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
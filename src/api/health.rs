@@ -1,13 +1,82 @@
-use rouille;
+use crate::domain::check_readiness;
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
 use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[derive(Serialize)]
 struct Response {
     message: String,
 }
 
+#[derive(Serialize)]
+struct LiveResponse {
+    status: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+struct ComponentResponse {
+    status: &'static str,
+    latency_ms: f64,
+}
+
+#[derive(Serialize)]
+struct ReadyResponse {
+    status: &'static str,
+    version: &'static str,
+    components: BTreeMap<&'static str, ComponentResponse>,
+}
+
+fn status(healthy: bool) -> &'static str {
+    if healthy {
+        "up"
+    } else {
+        "down"
+    }
+}
+
 pub fn serve() -> rouille::Response {
     rouille::Response::json(&Response {
         message: String::from("Gotta catch them all!"),
     })
 }
+
+/// Answers as long as the process can serve requests.
+pub fn serve_live() -> rouille::Response {
+    rouille::Response::json(&LiveResponse {
+        status: status(true),
+        version: VERSION,
+    })
+}
+
+/// Answers with 503 when a dependency is unhealthy, so that no traffic is
+/// routed to the server until it recovers.
+pub fn serve_ready(repo: Arc<dyn Repository>) -> rouille::Response {
+    let _span = Span::enter("check_readiness");
+    let res = check_readiness::execute(repo);
+
+    let body = ReadyResponse {
+        status: status(res.is_ready()),
+        version: VERSION,
+        components: res
+            .components
+            .iter()
+            .map(|c| {
+                (
+                    c.name,
+                    ComponentResponse {
+                        status: status(c.healthy),
+                        latency_ms: c.latency.as_secs_f64() * 1000.0,
+                    },
+                )
+            })
+            .collect(),
+    };
+
+    let code = if res.is_ready() { 200 } else { 503 };
+    rouille::Response::json(&body).with_status_code(code)
+}
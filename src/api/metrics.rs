@@ -0,0 +1,15 @@
+use crate::domain::count_pokemons;
+use crate::logger::Span;
+use crate::metrics::Metrics;
+use crate::repositories::pokemon::Repository;
+use std::sync::Arc;
+
+pub fn serve(metrics: Arc<Metrics>, repo: Arc<dyn Repository>) -> rouille::Response {
+    let _span = Span::enter("count_pokemons");
+    let pokemons = count_pokemons::execute(repo).ok().map(|res| res.count);
+
+    rouille::Response::from_data(
+        "text/plain; version=0.0.4; charset=utf-8",
+        metrics.render(pokemons),
+    )
+}
@@ -1,35 +1,500 @@
+mod analyze_team;
+mod api_keys;
+mod audit;
+mod auth;
 mod create_pokemon;
 mod delete_pokemon;
+mod events;
 mod fetch_all_pokemons;
 mod fetch_pokemon;
+mod forms;
+mod generations;
 mod health;
+mod metrics;
+mod names;
+mod progress;
+mod random_pokemon;
+mod search_pokemon;
+mod server;
+mod simulate_battle;
+mod teams;
+mod trash;
+mod webhooks;
 
+use crate::config::LogLevel;
+use crate::domain::api_key::Role;
+use crate::domain::authorize::Credentials;
+use crate::domain::entities::PokemonStats;
+use crate::domain::events::{Event, EventBus, Subscriber};
+use crate::domain::fetch_pokemon::Identifier;
+use crate::domain::generation::Generations;
+use crate::domain::team::TeamRules;
+use crate::logger::{self, RequestScope};
+use crate::metrics::Metrics;
+use crate::rate_limit::{Class, RateLimiter};
+use crate::repositories::api_key::ApiKeyRepository;
+use crate::repositories::audit::{AuditRepository, AuditedRepository};
+use crate::repositories::metered::MeteredRepository;
 use crate::repositories::pokemon::Repository;
+use crate::repositories::progress::ProgressRepository;
+use crate::repositories::team::TeamRepository;
+use crate::repositories::webhook::WebhookRepository;
+use events::EventStream;
+use serde::{Deserialize, Serialize};
+use server::Server;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-pub fn serve(url: &str, repo: Arc<dyn Repository>) {
-    rouille::start_server(url, move |req| {
-        router!(req,
-            (GET) (/{number: u16}) => {
-                fetch_pokemon::serve(repo.clone(), number)
-            },
-            (GET) (/) => {
-                fetch_all_pokemons::serve(repo.clone())
-            },
-            (GET) (/health) => {
-                health::serve()
-            },
-            (POST) (/) => {
-                create_pokemon::serve(repo.clone(), req)
-            },
-            (DELETE) (/{number: u16}) => {
-                delete_pokemon::serve(repo.clone(), number)
-            },
-            _ => {
-                rouille::Response::from(Status::NotFound)
+/// Where the API listens, and how many requests it handles at once.
+pub struct ServerConfig {
+    pub host: String,
+    /// `0` lets the system pick a free port.
+    pub port: u16,
+    /// A thread is started for every request when not set.
+    pub threads: Option<usize>,
+    /// How long the requests in flight are given to finish on shutdown.
+    pub shutdown_timeout: Duration,
+    /// Requests per minute a client can make to read and to change the
+    /// Pokedex, `0` for no limit.
+    pub rate_limits: (u32, u32),
+    /// Keys allowed everything and tokens accepted, besides the keys issued.
+    pub credentials: Credentials,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 8080,
+            threads: None,
+            shutdown_timeout: Duration::from_secs(30),
+            rate_limits: (0, 0),
+            credentials: Credentials::default(),
+        }
+    }
+}
+
+/// Serves the requests until SIGINT or SIGTERM, then stops accepting
+/// connections and returns once the requests in flight are over, or the
+/// shutdown timeout elapsed, and the events published are delivered.
+#[allow(clippy::too_many_arguments)]
+pub fn serve(
+    config: &ServerConfig,
+    repo: Arc<dyn Repository>,
+    store: Arc<dyn Repository>,
+    audit_repo: Arc<dyn AuditRepository>,
+    subscribers: Vec<Arc<dyn Subscriber>>,
+    team_repo: Arc<dyn TeamRepository>,
+    progress_repo: Arc<dyn ProgressRepository>,
+    webhook_repo: Arc<dyn WebhookRepository>,
+    api_key_repo: Arc<dyn ApiKeyRepository>,
+    rules: TeamRules,
+    generations: Arc<Generations>,
+) {
+    // Subscribers run in the background so that they never slow a request
+    // down, and the clients of `GET /events` are one of them.
+    let event_stream = Arc::new(EventStream::new());
+    let mut subscribers = subscribers;
+    subscribers.push(event_stream.clone());
+    let events = Arc::new(EventBus::queued(subscribers, |event| {
+        let (number, form) = match event {
+            Event::PokemonCreated(pokemon) | Event::PokemonUpdated(pokemon) => {
+                (pokemon.number.to_u16(), pokemon.form.to_string())
             }
-        )
-    })
+            Event::PokemonDeleted { number, form } => (number.to_u16(), form.to_string()),
+        };
+        logger::log(
+            LogLevel::Error,
+            "event not handled by a subscriber",
+            &[("number", number.into()), ("form", form.into())],
+        );
+    }));
+    let bus = events.clone();
+    let stream = event_stream.clone();
+
+    let credentials = config.credentials.clone();
+    let limiter = RateLimiter::new(config.rate_limits.0, config.rate_limits.1);
+    let metrics = Arc::new(Metrics::new());
+    let repo: Arc<dyn Repository> = Arc::new(MeteredRepository::new(repo, metrics.clone()));
+
+    let handler = move |req: &rouille::Request| {
+        let started = Instant::now();
+        let request_id = logger::request_id(req.header("X-Request-Id"));
+        let _scope = RequestScope::enter(&request_id);
+        let matched = Cell::new(true);
+
+        let path = req.url();
+        let pattern = route(req.method(), &path);
+        let required = auth::required_role(req.method(), pattern.unwrap_or(&path));
+        let (key, denied) =
+            match required.map(|role| auth::check(api_key_repo.clone(), &credentials, req, role)) {
+                Some(Ok(key)) => (Some(key), None),
+                Some(Err(denied)) => (None, Some(denied)),
+                None => (None, None),
+            };
+
+        // Changes are recorded in the audit log on behalf of the holder of
+        // the key, the routes open to anyone changing nothing.
+        let actor = match &key {
+            Some(key) => format!("api:{}", key.name),
+            None => "api".to_string(),
+        };
+        let repo: Arc<dyn Repository> = Arc::new(
+            AuditedRepository::new(repo.clone(), audit_repo.clone(), actor)
+                .with_snapshots(store.clone()),
+        );
+
+        // Clients are told apart by their key, or their address when they
+        // have none, so that guessing keys is limited too. The probes and the
+        // metrics are never limited.
+        let limited = required.and_then(|role| {
+            let client = match &key {
+                Some(key) => key.client.clone(),
+                None => format!("ip:{}", req.remote_addr().ip()),
+            };
+            let class = match role {
+                Role::Reader => Class::Read,
+                _ => Class::Write,
+            };
+            limiter.check(&client, class, Instant::now())
+        });
+        let denied = match &limited {
+            Some(decision) if !decision.allowed => Some(
+                problem(
+                    429,
+                    "Too Many Requests",
+                    &format!(
+                        "At most {} such requests can be made per minute, retry in {} second(s)",
+                        decision.limit, decision.retry_after
+                    ),
+                )
+                .with_additional_header("Retry-After", decision.retry_after.to_string()),
+            ),
+            _ => denied,
+        };
+
+        let routed = denied.is_none();
+        let response = match denied {
+            Some(denied) => denied,
+            None => router!(req,
+                (GET) (/{number: u16}) => {
+                    fetch_pokemon::serve(repo.clone(), Identifier::Number(number), None, accept_languages(req))
+                },
+                (GET) (/{number: u16}/forms) => {
+                    forms::serve(repo.clone(), number, accept_languages(req))
+                },
+                (GET) (/{number: u16}/forms/{form: String}) => {
+                    fetch_pokemon::serve(repo.clone(), Identifier::Number(number), Some(form), accept_languages(req))
+                },
+                (GET) (/) => {
+                    fetch_all_pokemons::serve(repo.clone(), &generations, req, accept_languages(req))
+                },
+                (PUT) (/{number: u16}/names/{language: String}) => {
+                    names::serve(repo.clone(), events.clone(), number, None, language, req)
+                },
+                (PUT) (/{number: u16}/forms/{form: String}/names/{language: String}) => {
+                    names::serve(repo.clone(), events.clone(), number, Some(form), language, req)
+                },
+                (GET) (/by-name/{name: String}) => {
+                    fetch_pokemon::serve(repo.clone(), Identifier::Name(name), None, accept_languages(req))
+                },
+                (GET) (/random) => {
+                    random_pokemon::serve(repo.clone(), &generations, req, accept_languages(req))
+                },
+                (GET) (/search) => {
+                    search_pokemon::serve(repo.clone(), req, accept_languages(req))
+                },
+                (GET) (/generations) => {
+                    generations::serve_all(&generations)
+                },
+                (GET) (/regions/{region: String}/{number: u16}) => {
+                    generations::serve_regional(repo.clone(), &generations, region, number, accept_languages(req))
+                },
+                (GET) (/trash) => {
+                    trash::serve_trash(repo.clone())
+                },
+                (POST) (/trash/{number: u16}/restore) => {
+                    trash::serve_restore(repo.clone(), events.clone(), number, None, accept_languages(req))
+                },
+                (POST) (/trash/{number: u16}/forms/{form: String}/restore) => {
+                    trash::serve_restore(repo.clone(), events.clone(), number, Some(form), accept_languages(req))
+                },
+                (GET) (/events) => {
+                    events::serve(stream.clone(), req)
+                },
+                (GET) (/audit) => {
+                    audit::serve(audit_repo.clone(), req)
+                },
+                (GET) (/health) => {
+                    health::serve()
+                },
+                (GET) (/health/live) => {
+                    health::serve_live()
+                },
+                (GET) (/health/ready) => {
+                    health::serve_ready(repo.clone())
+                },
+                (GET) (/metrics) => {
+                    metrics::serve(metrics.clone(), repo.clone())
+                },
+                (POST) (/) => {
+                    create_pokemon::serve(repo.clone(), events.clone(), &generations, req)
+                },
+                (PUT) (/profiles/{profile: String}/dex/{number: u16}) => {
+                    progress::serve_mark(progress_repo.clone(), repo.clone(), profile, number, req)
+                },
+                (GET) (/profiles/{profile: String}/progress) => {
+                    progress::serve_progress(progress_repo.clone(), repo.clone(), &generations, profile)
+                },
+                (POST) (/team-analysis) => {
+                    analyze_team::serve(repo.clone(), req)
+                },
+                (POST) (/battles) => {
+                    simulate_battle::serve(repo.clone(), req)
+                },
+                (GET) (/teams) => {
+                    teams::serve_all(team_repo.clone())
+                },
+                (GET) (/teams/{id: u32}) => {
+                    teams::serve_one(team_repo.clone(), id)
+                },
+                (POST) (/teams) => {
+                    teams::serve_create(team_repo.clone(), repo.clone(), &rules, req)
+                },
+                (PUT) (/teams/{id: u32}) => {
+                    teams::serve_update(team_repo.clone(), repo.clone(), &rules, id, req)
+                },
+                (DELETE) (/teams/{id: u32}) => {
+                    teams::serve_delete(team_repo.clone(), id)
+                },
+                (GET) (/api-keys) => {
+                    api_keys::serve_all(api_key_repo.clone())
+                },
+                (POST) (/api-keys) => {
+                    api_keys::serve_issue(api_key_repo.clone(), req)
+                },
+                (DELETE) (/api-keys/{id: u32}) => {
+                    api_keys::serve_revoke(api_key_repo.clone(), id)
+                },
+                (GET) (/webhooks) => {
+                    webhooks::serve_all(webhook_repo.clone())
+                },
+                (POST) (/webhooks) => {
+                    webhooks::serve_create(webhook_repo.clone(), req)
+                },
+                (GET) (/webhooks/dead-letters) => {
+                    webhooks::serve_dead_letters(webhook_repo.clone())
+                },
+                (DELETE) (/webhooks/{id: u32}) => {
+                    webhooks::serve_delete(webhook_repo.clone(), id)
+                },
+                (GET) (/webhooks/{id: u32}/deliveries) => {
+                    webhooks::serve_deliveries(webhook_repo.clone(), id)
+                },
+                (DELETE) (/{number: u16}) => {
+                    delete_pokemon::serve(repo.clone(), events.clone(), number, None)
+                },
+                (DELETE) (/{number: u16}/forms/{form: String}) => {
+                    delete_pokemon::serve(repo.clone(), events.clone(), number, Some(form))
+                },
+                _ => {
+                    matched.set(false);
+                    rouille::Response::from(Status::NotFound)
+                }
+            ),
+        };
+
+        // Only the requests served by a route are labelled with its pattern,
+        // the denied ones being `unmatched` too, so that no client can add
+        // series by sending made up paths or methods.
+        let route = match pattern {
+            Some(route) if routed && matched.get() => route,
+            _ => "unmatched",
+        };
+        let method = match req.method() {
+            "GET" | "HEAD" | "POST" | "PUT" | "PATCH" | "DELETE" | "OPTIONS" => req.method(),
+            _ => "other",
+        };
+        metrics.observe_request(method, route, response.status_code, started.elapsed());
+
+        let level = if response.status_code >= 500 {
+            LogLevel::Error
+        } else {
+            LogLevel::Info
+        };
+        let mut fields = vec![
+            ("method", req.method().into()),
+            ("path", req.url().into()),
+            ("route", route.into()),
+            ("status", response.status_code.into()),
+            ("latency_ms", logger::latency_ms(started)),
+            ("remote", req.remote_addr().ip().to_string().into()),
+        ];
+        if let Some(key) = key {
+            fields.push(("key", key.name.into()));
+            fields.push(("role", key.role.to_string().into()));
+        }
+        logger::log(level, "request", &fields);
+
+        let response = match limited {
+            Some(decision) => response
+                .with_additional_header("RateLimit-Limit", decision.limit.to_string())
+                .with_additional_header("RateLimit-Remaining", decision.remaining.to_string())
+                .with_additional_header("RateLimit-Reset", decision.reset.to_string()),
+            None => response,
+        };
+        response.with_additional_header("X-Request-Id", request_id)
+    };
+
+    let address = format!("{}:{}", config.host, config.port);
+    let server = match Server::new(&address, config.threads, handler) {
+        Ok(server) => server,
+        Err(_) => panic!("Error while listening on {}", address),
+    };
+
+    let stopping = Arc::new(AtomicBool::new(false));
+    let signaled = stopping.clone();
+    if ctrlc::set_handler(move || signaled.store(true, Ordering::SeqCst)).is_err() {
+        panic!("Error while handling the termination signals");
+    }
+
+    // The address actually bound, in case the system picked the port.
+    println!("http://{}", server.server_addr());
+    while !stopping.load(Ordering::SeqCst) {
+        server.poll_timeout(Duration::from_millis(100));
+    }
+
+    // The streams of events end, so that their clients are not waited for
+    // until the timeout.
+    logger::log(LogLevel::Info, "shutting down", &[]);
+    event_stream.close();
+    let deadline = Instant::now() + config.shutdown_timeout;
+    match server.shutdown(deadline) {
+        0 => {}
+        left => logger::log(
+            LogLevel::Warn,
+            "requests still in flight after the shutdown timeout",
+            &[("in_flight", left.into())],
+        ),
+    }
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if !bus.flush(remaining.max(Duration::from_secs(1))) {
+        logger::log(
+            LogLevel::Warn,
+            "events not delivered before shutting down",
+            &[],
+        );
+    }
+}
+
+/// Every route served, by method and pattern.
+const ROUTES: [(&str, &str); 40] = [
+    ("GET", "/{number}"),
+    ("GET", "/{number}/forms"),
+    ("GET", "/{number}/forms/{form}"),
+    ("GET", "/"),
+    ("PUT", "/{number}/names/{language}"),
+    ("PUT", "/{number}/forms/{form}/names/{language}"),
+    ("GET", "/by-name/{name}"),
+    ("GET", "/random"),
+    ("GET", "/search"),
+    ("GET", "/generations"),
+    ("GET", "/regions/{region}/{number}"),
+    ("GET", "/trash"),
+    ("POST", "/trash/{number}/restore"),
+    ("POST", "/trash/{number}/forms/{form}/restore"),
+    ("GET", "/events"),
+    ("GET", "/audit"),
+    ("GET", "/health"),
+    ("GET", "/health/live"),
+    ("GET", "/health/ready"),
+    ("GET", "/metrics"),
+    ("POST", "/"),
+    ("PUT", "/profiles/{profile}/dex/{number}"),
+    ("GET", "/profiles/{profile}/progress"),
+    ("POST", "/team-analysis"),
+    ("POST", "/battles"),
+    ("GET", "/teams"),
+    ("GET", "/teams/{id}"),
+    ("POST", "/teams"),
+    ("PUT", "/teams/{id}"),
+    ("DELETE", "/teams/{id}"),
+    ("GET", "/api-keys"),
+    ("POST", "/api-keys"),
+    ("DELETE", "/api-keys/{id}"),
+    ("GET", "/webhooks"),
+    ("POST", "/webhooks"),
+    ("GET", "/webhooks/dead-letters"),
+    ("DELETE", "/webhooks/{id}"),
+    ("GET", "/webhooks/{id}/deliveries"),
+    ("DELETE", "/{number}"),
+    ("DELETE", "/{number}/forms/{form}"),
+];
+
+/// The pattern of the route serving a request, `None` when none does.
+fn route(method: &str, path: &str) -> Option<&'static str> {
+    ROUTES
+        .iter()
+        .find(|(m, p)| *m == method && matches(p, path))
+        .map(|(_, p)| *p)
+}
+
+/// Whether a path matches a pattern, its parameters parsed like the router does.
+fn matches(pattern: &str, path: &str) -> bool {
+    pattern.split('/').count() == path.split('/').count()
+        && pattern
+            .split('/')
+            .zip(path.split('/'))
+            .all(|(parameter, segment)| match parameter {
+                "{number}" => segment.parse::<u16>().is_ok(),
+                "{id}" => segment.parse::<u32>().is_ok(),
+                p if p.starts_with('{') => !segment.is_empty(),
+                p => p == segment,
+            })
+}
+
+/// Seed for the endpoints relying on chance when the client gives none.
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Languages of the `Accept-Language` header, most preferred first.
+fn accept_languages(req: &rouille::Request) -> Vec<String> {
+    let mut languages = req
+        .header("Accept-Language")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';').map(str::trim);
+            let tag = parts.next().filter(|t| !t.is_empty() && *t != "*")?;
+            let quality = parts
+                .find_map(|p| p.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+            Some((tag.to_string(), quality))
+        })
+        .filter(|(_, quality)| *quality > 0.0)
+        .collect::<Vec<(String, f32)>>();
+
+    languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+    languages.into_iter().map(|(tag, _)| tag).collect()
+}
+
+/// An RFC 7807 problem, telling the client what went wrong.
+fn problem(status_code: u16, title: &str, detail: &str) -> rouille::Response {
+    let body = serde_json::json!({
+        "type": "about:blank",
+        "title": title,
+        "status": status_code,
+        "detail": detail,
+    });
+    rouille::Response::from_data("application/problem+json", body.to_string())
+        .with_status_code(status_code)
 }
 
 enum Status {
@@ -57,3 +522,35 @@ impl From<Status> for rouille::Response {
         }
     }
 }
+
+#[derive(Serialize, Deserialize)]
+struct Stats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    speed: u16,
+}
+
+impl From<Vec<u16>> for Stats {
+    fn from(stats: Vec<u16>) -> Self {
+        let stat = |i: usize| stats.get(i).copied().unwrap_or_default();
+        Self {
+            hp: stat(0),
+            attack: stat(1),
+            defense: stat(2),
+            speed: stat(3),
+        }
+    }
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::from(PokemonStats::default().to_vec_u16())
+    }
+}
+
+impl From<Stats> for Vec<u16> {
+    fn from(stats: Stats) -> Self {
+        vec![stats.hp, stats.attack, stats.defense, stats.speed]
+    }
+}
@@ -0,0 +1,60 @@
+use crate::api::{Stats, Status};
+use crate::domain::events::EventBus;
+use crate::domain::save_pokemon_name;
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    form: String,
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    number: u16,
+    form: Option<String>,
+    language: String,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => save_pokemon_name::Request {
+            number,
+            form,
+            language,
+            name: req.name,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let _span = Span::enter("save_pokemon_name");
+    match save_pokemon_name::execute(repo, events, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            number: res.number,
+            form: res.form,
+            name: res.name,
+            names: res.names.into_iter().collect(),
+            types: res.types,
+            stats: res.stats.into(),
+        }),
+        Err(save_pokemon_name::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(save_pokemon_name::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(save_pokemon_name::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(save_pokemon_name::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::api::Status;
+use crate::domain::generation::Generations;
+use crate::domain::{fetch_progress, mark_entry};
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use crate::repositories::progress::ProgressRepository;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct Request {
+    status: String,
+    #[serde(default)]
+    shiny: bool,
+    form: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Entry {
+    number: u16,
+    status: String,
+    shiny: bool,
+    form: Option<String>,
+    seen_at: u64,
+    caught_at: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct Completion {
+    seen: usize,
+    caught: usize,
+    total: usize,
+    seen_percent: f32,
+    caught_percent: f32,
+}
+
+#[derive(Serialize)]
+struct TypeCompletion {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(flatten)]
+    completion: Completion,
+}
+
+#[derive(Serialize)]
+struct GenerationCompletion {
+    generation: u8,
+    #[serde(flatten)]
+    completion: Completion,
+}
+
+#[derive(Serialize)]
+struct Response {
+    profile: String,
+    overall: Completion,
+    by_type: Vec<TypeCompletion>,
+    by_generation: Vec<GenerationCompletion>,
+    entries: Vec<Entry>,
+}
+
+impl From<mark_entry::Response> for Entry {
+    fn from(e: mark_entry::Response) -> Self {
+        Self {
+            number: e.number,
+            status: e.status,
+            shiny: e.shiny,
+            form: e.form,
+            seen_at: e.seen_at,
+            caught_at: e.caught_at,
+        }
+    }
+}
+
+impl From<fetch_progress::Completion> for Completion {
+    fn from(c: fetch_progress::Completion) -> Self {
+        Self {
+            seen: c.seen,
+            caught: c.caught,
+            total: c.total,
+            seen_percent: c.seen_percent,
+            caught_percent: c.caught_percent,
+        }
+    }
+}
+
+pub fn serve_mark(
+    repo: Arc<dyn ProgressRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    profile: String,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => mark_entry::Request {
+            profile,
+            number,
+            status: req.status,
+            shiny: req.shiny,
+            form: req.form,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let _span = Span::enter("mark_entry");
+    match mark_entry::execute(repo, pokemon_repo, req) {
+        Ok(res) => rouille::Response::json(&Entry::from(res)),
+        Err(mark_entry::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(mark_entry::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(mark_entry::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+pub fn serve_progress(
+    repo: Arc<dyn ProgressRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    generations: &Generations,
+    profile: String,
+) -> rouille::Response {
+    let _span = Span::enter("fetch_progress");
+    match fetch_progress::execute(
+        repo,
+        pokemon_repo,
+        generations,
+        fetch_progress::Request { profile },
+    ) {
+        Ok(res) => rouille::Response::json(&Response {
+            profile: res.profile,
+            overall: res.overall.into(),
+            by_type: res
+                .by_type
+                .into_iter()
+                .map(|(kind, c)| TypeCompletion {
+                    kind,
+                    completion: c.into(),
+                })
+                .collect(),
+            by_generation: res
+                .by_generation
+                .into_iter()
+                .map(|(generation, c)| GenerationCompletion {
+                    generation,
+                    completion: c.into(),
+                })
+                .collect(),
+            entries: res.entries.into_iter().map(Entry::from).collect(),
+        }),
+        Err(fetch_progress::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_progress::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,61 @@
+use crate::api::{random_seed, Stats, Status};
+use crate::domain::generation::Generations;
+use crate::domain::random_pokemon;
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct Response {
+    seed: u64,
+    number: u16,
+    form: String,
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    generations: &Generations,
+    req: &rouille::Request,
+    languages: Vec<String>,
+) -> rouille::Response {
+    let (seed, generation) = match (
+        req.get_param("seed").map(|s| s.parse::<u64>()),
+        req.get_param("generation").map(|g| g.parse::<u8>()),
+    ) {
+        (Some(Err(_)), _) | (_, Some(Err(_))) => {
+            return rouille::Response::from(Status::BadRequest)
+        }
+        (seed, generation) => (
+            seed.and_then(Result::ok).unwrap_or_else(random_seed),
+            generation.and_then(Result::ok),
+        ),
+    };
+
+    let req = random_pokemon::Request {
+        seed,
+        kind: req.get_param("type"),
+        generation,
+        languages,
+    };
+    let _span = Span::enter("random_pokemon");
+    match random_pokemon::execute(repo, generations, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            seed,
+            number: res.number,
+            form: res.form,
+            name: res.name,
+            names: res.names.into_iter().collect(),
+            types: res.types,
+            stats: res.stats.into(),
+        }),
+        Err(random_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(random_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(random_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
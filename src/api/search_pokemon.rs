@@ -0,0 +1,53 @@
+use crate::api::Status;
+use crate::domain::search_pokemon;
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    form: String,
+    name: String,
+    matched_name: String,
+    types: Vec<String>,
+    score: f32,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    req: &rouille::Request,
+    languages: Vec<String>,
+) -> rouille::Response {
+    let limit = match req.get_param("limit").map(|l| l.parse::<usize>()) {
+        Some(Ok(limit)) => Some(limit),
+        Some(Err(_)) => return rouille::Response::from(Status::BadRequest),
+        None => None,
+    };
+
+    let req = search_pokemon::Request {
+        query: req.get_param("q").unwrap_or_default(),
+        kind: req.get_param("type"),
+        limit,
+        languages,
+    };
+
+    let _span = Span::enter("search_pokemon");
+    match search_pokemon::execute(repo, req) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|m| Response {
+                    number: m.number,
+                    form: m.form,
+                    name: m.name,
+                    matched_name: m.matched_name,
+                    types: m.types,
+                    score: m.score,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(search_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(search_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
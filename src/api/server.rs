@@ -0,0 +1,354 @@
+use crate::api::Status;
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+type Handler = Arc<dyn Fn(&rouille::Request) -> rouille::Response + Send + Sync>;
+
+/// Counts a request as in flight until it is dropped.
+struct InFlight(Arc<AtomicUsize>);
+
+impl InFlight {
+    fn start(count: &Arc<AtomicUsize>) -> Self {
+        count.fetch_add(1, Ordering::SeqCst);
+        Self(count.clone())
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A request accepted, counted until its response is written.
+struct Job {
+    request: tiny_http::Request,
+    _in_flight: InFlight,
+}
+
+/// Serves the requests with rouille handlers, counting every one of them as
+/// in flight from the moment it is accepted until its response is written,
+/// so that shutting down waits for the responses instead of the handlers.
+pub struct Server {
+    listener: tiny_http::Server,
+    handler: Handler,
+    /// The queue of the workers, a thread being started for every request
+    /// when there is none.
+    queue: Option<Sender<Job>>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Server {
+    pub fn new<F>(address: &str, threads: Option<usize>, handler: F) -> Result<Self, ()>
+    where
+        F: Fn(&rouille::Request) -> rouille::Response + Send + Sync + 'static,
+    {
+        let listener = tiny_http::Server::http(address).map_err(|_| ())?;
+        let handler: Handler = Arc::new(handler);
+
+        let queue = threads.map(|threads| {
+            let (queue, receiver) = mpsc::channel::<Job>();
+            let receiver = Arc::new(Mutex::new(receiver));
+            for _ in 0..threads.max(1) {
+                let receiver = receiver.clone();
+                let handler = handler.clone();
+                // Stops once the server is dropped.
+                thread::spawn(move || loop {
+                    let next = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        _ => return,
+                    };
+                    match next {
+                        Ok(job) => process(&handler, job),
+                        _ => return,
+                    }
+                });
+            }
+            queue
+        });
+
+        Ok(Self {
+            listener,
+            handler,
+            queue,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// The address actually bound, in case the system picked the port.
+    pub fn server_addr(&self) -> SocketAddr {
+        self.listener.server_addr()
+    }
+
+    /// Serves the request received within `timeout`, if any.
+    pub fn poll_timeout(&self, timeout: Duration) {
+        if let Ok(Some(request)) = self.listener.recv_timeout(timeout) {
+            self.dispatch(request);
+        }
+    }
+
+    fn dispatch(&self, request: tiny_http::Request) {
+        let job = Job {
+            request,
+            _in_flight: InFlight::start(&self.in_flight),
+        };
+        match &self.queue {
+            Some(queue) => {
+                queue.send(job).ok();
+            }
+            None => {
+                let handler = self.handler.clone();
+                thread::spawn(move || process(&handler, job));
+            }
+        }
+    }
+
+    /// Stops accepting connections, serves the requests already received and
+    /// waits until `deadline` for every response to be written. Returns the
+    /// number of requests still in flight then.
+    pub fn shutdown(self, deadline: Instant) -> usize {
+        while let Ok(Some(request)) = self.listener.try_recv() {
+            self.dispatch(request);
+        }
+        let Self {
+            listener,
+            in_flight,
+            ..
+        } = self;
+        // The connections coming from now on are refused.
+        drop(listener);
+
+        while in_flight.load(Ordering::SeqCst) > 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        in_flight.load(Ordering::SeqCst)
+    }
+}
+
+/// Runs the handler on the request and writes its response, a panicking
+/// handler answering with a `500`.
+fn process(handler: &Handler, job: Job) {
+    let mut request = job.request;
+
+    let mut data = vec![];
+    if request.as_reader().read_to_end(&mut data).is_err() {
+        respond(request, rouille::Response::from(Status::BadRequest)).ok();
+        return;
+    }
+    let headers = request
+        .headers()
+        .iter()
+        .map(|h| (h.field.to_string(), h.value.to_string()))
+        .collect();
+    let req = rouille::Request::fake_http_from(
+        *request.remote_addr(),
+        request.method().as_str(),
+        request.url(),
+        headers,
+        data,
+    );
+
+    let response = panic::catch_unwind(AssertUnwindSafe(|| handler(&req)))
+        .unwrap_or_else(|_| rouille::Response::from(Status::InternalServerError));
+    respond(request, response).ok();
+}
+
+/// Bodies of unknown length are streamed, those of known length being left
+/// to tiny_http.
+fn respond(request: tiny_http::Request, response: rouille::Response) -> io::Result<()> {
+    let headers = response
+        .headers
+        .iter()
+        .filter(|(field, _)| {
+            !field.eq_ignore_ascii_case("Content-Length")
+                && !field.eq_ignore_ascii_case("Transfer-Encoding")
+        })
+        .filter_map(|(field, value)| {
+            tiny_http::Header::from_bytes(field.as_bytes(), value.as_bytes()).ok()
+        })
+        .collect::<Vec<tiny_http::Header>>();
+
+    let (data, length) = response.data.into_reader_and_size();
+    if length.is_none() {
+        return stream(request, response.status_code, &headers, data);
+    }
+
+    let mut res = tiny_http::Response::empty(response.status_code).with_data(data, length);
+    for header in headers {
+        res.add_header(header);
+    }
+    request.respond(res)
+}
+
+/// Writes the body chunk by chunk, every read being sent and flushed at
+/// once, whereas tiny_http holds a chunk back until it is full.
+fn stream(
+    request: tiny_http::Request,
+    status_code: u16,
+    headers: &[tiny_http::Header],
+    mut body: impl Read,
+) -> io::Result<()> {
+    let mut writer = request.into_writer();
+    let reason = tiny_http::StatusCode(status_code).default_reason_phrase();
+    write!(writer, "HTTP/1.1 {} {}\r\n", status_code, reason)?;
+    for header in headers {
+        write!(writer, "{}\r\n", header)?;
+    }
+    write!(writer, "Transfer-Encoding: chunked\r\n\r\n")?;
+    writer.flush()?;
+
+    let mut buffer = [0; 4096];
+    loop {
+        let read = match body.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        write!(writer, "{:X}\r\n", read)?;
+        writer.write_all(&buffer[..read])?;
+        write!(writer, "\r\n")?;
+        writer.flush()?;
+    }
+    write!(writer, "0\r\n\r\n")?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpStream;
+
+    fn get(address: SocketAddr) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream
+                .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).ok();
+            response
+        })
+    }
+
+    #[test]
+    fn it_should_write_the_responses_in_flight_before_shutting_down() {
+        let body = "Pikachu ".repeat(1024);
+        let expected = body.clone();
+        let server = Server::new("localhost:0", Some(2), move |_| {
+            thread::sleep(Duration::from_millis(200));
+            rouille::Response::text(body.clone())
+        })
+        .unwrap();
+
+        let client = get(server.server_addr());
+        while server.in_flight.load(Ordering::SeqCst) == 0 {
+            server.poll_timeout(Duration::from_millis(10));
+        }
+        let address = server.server_addr();
+
+        let left = server.shutdown(Instant::now() + Duration::from_secs(5));
+        assert_eq!(left, 0);
+        assert!(client.join().unwrap().ends_with(&expected));
+        let refused = (0..100).any(|_| {
+            thread::sleep(Duration::from_millis(10));
+            TcpStream::connect(address).is_err()
+        });
+        assert!(refused);
+    }
+
+    #[test]
+    fn it_should_serve_the_requests_received_before_shutting_down() {
+        let server =
+            Server::new("localhost:0", None, |_| rouille::Response::text("Pikachu")).unwrap();
+
+        let client = get(server.server_addr());
+        thread::sleep(Duration::from_millis(200));
+
+        let left = server.shutdown(Instant::now() + Duration::from_secs(5));
+        assert_eq!(left, 0);
+        assert!(client.join().unwrap().ends_with("Pikachu"));
+    }
+
+    /// A body read as its parts are sent, ending once the sender is dropped.
+    struct Parts(mpsc::Receiver<&'static str>);
+
+    impl Read for Parts {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.recv() {
+                Ok(part) => {
+                    buf[..part.len()].copy_from_slice(part.as_bytes());
+                    Ok(part.len())
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_send_every_part_of_a_streamed_body_at_once() {
+        let (sender, receiver) = mpsc::channel();
+        let parts = Mutex::new(Some(receiver));
+        let server = Server::new("localhost:0", None, move |_| {
+            let body = Parts(parts.lock().unwrap().take().unwrap());
+            rouille::Response {
+                status_code: 200,
+                headers: vec![("Content-Type".into(), "text/event-stream".into())],
+                data: rouille::ResponseBody::from_reader(body),
+                upgrade: None,
+            }
+        })
+        .unwrap();
+
+        let mut stream = TcpStream::connect(server.server_addr()).unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        server.poll_timeout(Duration::from_secs(5));
+
+        sender.send("data: Pikachu\n\n").unwrap();
+        let mut received = String::new();
+        let mut buffer = [0; 1024];
+        while !received.ends_with("data: Pikachu\n\n\r\n") {
+            let read = stream.read(&mut buffer).unwrap();
+            assert_ne!(read, 0);
+            received.push_str(std::str::from_utf8(&buffer[..read]).unwrap());
+        }
+        assert!(received.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(received.contains("Content-Type: text/event-stream\r\n"));
+        assert!(received.contains("Transfer-Encoding: chunked\r\n"));
+
+        drop(sender);
+        stream.read_to_string(&mut received).unwrap();
+        assert!(received.ends_with("\r\n0\r\n\r\n"));
+        server.shutdown(Instant::now() + Duration::from_secs(5));
+    }
+
+    #[test]
+    fn it_should_answer_with_an_error_when_the_handler_panics() {
+        let server = Server::new("localhost:0", Some(1), |req| {
+            if req.url() == "/" {
+                panic!("Pikachu fainted");
+            }
+            rouille::Response::text("Pikachu")
+        })
+        .unwrap();
+
+        let client = get(server.server_addr());
+        while server.in_flight.load(Ordering::SeqCst) == 0 {
+            server.poll_timeout(Duration::from_millis(10));
+        }
+
+        server.shutdown(Instant::now() + Duration::from_secs(5));
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 500"));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::api::{random_seed, Status};
+use crate::domain::battle::{Event, Side};
+use crate::domain::simulate_battle;
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct Request {
+    seed: Option<u64>,
+    first: Vec<TeamMember>,
+    second: Vec<TeamMember>,
+}
+
+#[derive(Deserialize)]
+struct TeamMember {
+    number: u16,
+    level: u8,
+    moves: Vec<Move>,
+}
+
+#[derive(Deserialize)]
+struct Move {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    power: u16,
+    accuracy: u8,
+    #[serde(default)]
+    priority: i8,
+}
+
+#[derive(Serialize)]
+struct Response {
+    seed: u64,
+    winner: Option<&'static str>,
+    turns: u16,
+    log: Vec<LogEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum LogEntry {
+    SentOut {
+        side: &'static str,
+        pokemon: String,
+        hp: u16,
+    },
+    TurnStarted {
+        turn: u16,
+    },
+    MoveUsed {
+        side: &'static str,
+        pokemon: String,
+        #[serde(rename = "move")]
+        move_name: String,
+    },
+    Missed {
+        side: &'static str,
+        pokemon: String,
+    },
+    Damaged {
+        side: &'static str,
+        pokemon: String,
+        damage: u16,
+        remaining_hp: u16,
+        effectiveness: f32,
+    },
+    Fainted {
+        side: &'static str,
+        pokemon: String,
+    },
+    BattleEnded {
+        winner: Option<&'static str>,
+    },
+}
+
+fn side(side: Side) -> &'static str {
+    match side {
+        Side::First => "first",
+        Side::Second => "second",
+    }
+}
+
+impl From<Event> for LogEntry {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::SentOut {
+                side: s,
+                pokemon,
+                hp,
+            } => LogEntry::SentOut {
+                side: side(s),
+                pokemon,
+                hp,
+            },
+            Event::TurnStarted { turn } => LogEntry::TurnStarted { turn },
+            Event::MoveUsed {
+                side: s,
+                pokemon,
+                move_name,
+            } => LogEntry::MoveUsed {
+                side: side(s),
+                pokemon,
+                move_name,
+            },
+            Event::Missed { side: s, pokemon } => LogEntry::Missed {
+                side: side(s),
+                pokemon,
+            },
+            Event::Damaged {
+                side: s,
+                pokemon,
+                damage,
+                remaining_hp,
+                effectiveness,
+            } => LogEntry::Damaged {
+                side: side(s),
+                pokemon,
+                damage,
+                remaining_hp,
+                effectiveness,
+            },
+            Event::Fainted { side: s, pokemon } => LogEntry::Fainted {
+                side: side(s),
+                pokemon,
+            },
+            Event::BattleEnded { winner } => LogEntry::BattleEnded {
+                winner: winner.map(side),
+            },
+        }
+    }
+}
+
+fn to_team(members: Vec<TeamMember>) -> Vec<simulate_battle::TeamMember> {
+    members
+        .into_iter()
+        .map(|m| simulate_battle::TeamMember {
+            number: m.number,
+            level: m.level,
+            moves: m
+                .moves
+                .into_iter()
+                .map(|m| simulate_battle::MoveRequest {
+                    name: m.name,
+                    kind: m.kind,
+                    power: m.power,
+                    accuracy: m.accuracy,
+                    priority: m.priority,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => simulate_battle::Request {
+            seed: req.seed.unwrap_or_else(random_seed),
+            first: to_team(req.first),
+            second: to_team(req.second),
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let seed = req.seed;
+    let _span = Span::enter("simulate_battle");
+    match simulate_battle::execute(repo, req) {
+        Ok(simulate_battle::Response { winner, turns, log }) => {
+            rouille::Response::json(&Response {
+                seed,
+                winner: winner.map(side),
+                turns,
+                log: log.into_iter().map(LogEntry::from).collect(),
+            })
+        }
+        Err(simulate_battle::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(simulate_battle::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(simulate_battle::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
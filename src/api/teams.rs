@@ -0,0 +1,157 @@
+use crate::api::Status;
+use crate::domain::team::TeamRules;
+use crate::domain::{create_team, delete_team, fetch_all_teams, fetch_team, update_team};
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use crate::repositories::team::TeamRepository;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    members: Vec<Member>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Member {
+    number: u16,
+    nickname: Option<String>,
+    level: u8,
+    moves: Vec<String>,
+    held_item: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    id: u32,
+    name: String,
+    members: Vec<Member>,
+}
+
+#[derive(Serialize)]
+struct Violation {
+    message: String,
+}
+
+impl From<create_team::Member> for Member {
+    fn from(m: create_team::Member) -> Self {
+        Self {
+            number: m.number,
+            nickname: m.nickname,
+            level: m.level,
+            moves: m.moves,
+            held_item: m.held_item,
+        }
+    }
+}
+
+impl From<Member> for create_team::Member {
+    fn from(m: Member) -> Self {
+        Self {
+            number: m.number,
+            nickname: m.nickname,
+            level: m.level,
+            moves: m.moves,
+            held_item: m.held_item,
+        }
+    }
+}
+
+impl From<create_team::Response> for Response {
+    fn from(res: create_team::Response) -> Self {
+        Self {
+            id: res.id,
+            name: res.name,
+            members: res.members.into_iter().map(Member::from).collect(),
+        }
+    }
+}
+
+fn rule_violation(message: String) -> rouille::Response {
+    rouille::Response::json(&Violation { message }).with_status_code(422)
+}
+
+pub fn serve_all(repo: Arc<dyn TeamRepository>) -> rouille::Response {
+    let _span = Span::enter("fetch_all_teams");
+    match fetch_all_teams::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_all_teams::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
+
+pub fn serve_one(repo: Arc<dyn TeamRepository>, id: u32) -> rouille::Response {
+    let _span = Span::enter("fetch_team");
+    match fetch_team::execute(repo, fetch_team::Request { id }) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(fetch_team::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_team::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+pub fn serve_create(
+    repo: Arc<dyn TeamRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    rules: &TeamRules,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_team::Request {
+            name: req.name,
+            members: req.members.into_iter().map(Into::into).collect(),
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let _span = Span::enter("create_team");
+    match create_team::execute(repo, pokemon_repo, rules, req) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(create_team::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(create_team::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(create_team::Error::RuleViolation(message)) => rule_violation(message),
+        Err(create_team::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(create_team::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+pub fn serve_update(
+    repo: Arc<dyn TeamRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    rules: &TeamRules,
+    id: u32,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_team::Request {
+            id,
+            name: req.name,
+            members: req.members.into_iter().map(Into::into).collect(),
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let _span = Span::enter("update_team");
+    match update_team::execute(repo, pokemon_repo, rules, req) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(update_team::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(update_team::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_team::Error::RuleViolation(message)) => rule_violation(message),
+        Err(update_team::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(update_team::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+pub fn serve_delete(repo: Arc<dyn TeamRepository>, id: u32) -> rouille::Response {
+    let _span = Span::enter("delete_team");
+    match delete_team::execute(repo, delete_team::Request { id }) {
+        Ok(()) => rouille::Response::from(Status::Ok),
+        Err(delete_team::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(delete_team::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,75 @@
+use crate::api::{Stats, Status};
+use crate::domain::events::EventBus;
+use crate::domain::{fetch_trash, restore_pokemon};
+use crate::logger::Span;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct DeletedResponse {
+    number: u16,
+    form: String,
+    name: String,
+    types: Vec<String>,
+    deleted_at: u64,
+}
+
+#[derive(Serialize)]
+struct RestoredResponse {
+    number: u16,
+    form: String,
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+pub fn serve_trash(repo: Arc<dyn Repository>) -> rouille::Response {
+    let _span = Span::enter("fetch_trash");
+    match fetch_trash::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|d| DeletedResponse {
+                    number: d.number,
+                    form: d.form,
+                    name: d.name,
+                    types: d.types,
+                    deleted_at: d.deleted_at,
+                })
+                .collect::<Vec<DeletedResponse>>(),
+        ),
+        Err(fetch_trash::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+pub fn serve_restore(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    number: u16,
+    form: Option<String>,
+    languages: Vec<String>,
+) -> rouille::Response {
+    let req = restore_pokemon::Request {
+        number,
+        form,
+        languages,
+    };
+    let _span = Span::enter("restore_pokemon");
+    match restore_pokemon::execute(repo, events, req) {
+        Ok(res) => rouille::Response::json(&RestoredResponse {
+            number: res.number,
+            form: res.form,
+            name: res.name,
+            names: res.names.into_iter().collect(),
+            types: res.types,
+            stats: res.stats.into(),
+        }),
+        Err(restore_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(restore_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(restore_pokemon::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::api::Status;
+use crate::domain::{
+    create_webhook, delete_webhook, fetch_all_webhooks, fetch_dead_letters,
+    fetch_webhook_deliveries,
+};
+use crate::logger::Span;
+use crate::repositories::webhook::WebhookRepository;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct Request {
+    url: String,
+    secret: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    id: u32,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct DeliveryResponse {
+    event: String,
+    attempt: u32,
+    at: u64,
+    delivered: bool,
+    status_code: Option<u16>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DeadLetterResponse {
+    webhook: u32,
+    event: String,
+    payload: serde_json::Value,
+    attempts: u32,
+    failed_at: u64,
+}
+
+impl From<create_webhook::Response> for Response {
+    fn from(res: create_webhook::Response) -> Self {
+        Self {
+            id: res.id,
+            url: res.url,
+        }
+    }
+}
+
+pub fn serve_all(repo: Arc<dyn WebhookRepository>) -> rouille::Response {
+    let _span = Span::enter("fetch_all_webhooks");
+    match fetch_all_webhooks::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_all_webhooks::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
+
+pub fn serve_create(repo: Arc<dyn WebhookRepository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_webhook::Request {
+            url: req.url,
+            secret: req.secret,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let _span = Span::enter("create_webhook");
+    match create_webhook::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(create_webhook::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(create_webhook::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(create_webhook::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+pub fn serve_delete(repo: Arc<dyn WebhookRepository>, id: u32) -> rouille::Response {
+    let _span = Span::enter("delete_webhook");
+    match delete_webhook::execute(repo, delete_webhook::Request { id }) {
+        Ok(()) => rouille::Response::from(Status::Ok),
+        Err(delete_webhook::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(delete_webhook::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+pub fn serve_deliveries(repo: Arc<dyn WebhookRepository>, id: u32) -> rouille::Response {
+    let _span = Span::enter("fetch_webhook_deliveries");
+    match fetch_webhook_deliveries::execute(repo, fetch_webhook_deliveries::Request { id }) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|d| DeliveryResponse {
+                    event: d.event,
+                    attempt: d.attempt,
+                    at: d.at,
+                    delivered: d.delivered,
+                    status_code: d.status_code,
+                    error: d.error,
+                })
+                .collect::<Vec<DeliveryResponse>>(),
+        ),
+        Err(fetch_webhook_deliveries::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_webhook_deliveries::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
+
+pub fn serve_dead_letters(repo: Arc<dyn WebhookRepository>) -> rouille::Response {
+    let _span = Span::enter("fetch_dead_letters");
+    match fetch_dead_letters::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|d| DeadLetterResponse {
+                    webhook: d.webhook,
+                    event: d.event,
+                    payload: serde_json::from_str(&d.payload)
+                        .unwrap_or(serde_json::Value::String(d.payload)),
+                    attempts: d.attempts,
+                    failed_at: d.failed_at,
+                })
+                .collect::<Vec<DeadLetterResponse>>(),
+        ),
+        Err(fetch_dead_letters::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::domain::analyze_team;
+use crate::domain::entities::PokemonType;
+use crate::repositories::pokemon::Repository;
+use console::style;
+use dialoguer::{Input, MultiSelect};
+use std::sync::Arc;
+
+pub fn run(repo: Arc<dyn Repository>) {
+    let members = match prompt_members() {
+        Ok(members) => members,
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match analyze_team::execute(repo, analyze_team::Request { members }) {
+        Ok(res) => print_analysis(res),
+        Err(analyze_team::Error::BadRequest) => println!("The request is invalid"),
+        Err(analyze_team::Error::NotFound) => println!("A Pokemon does not exist"),
+        Err(analyze_team::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
+
+fn prompt_members() -> Result<Vec<analyze_team::Member>, ()> {
+    let numbers = match Input::<String>::new()
+        .with_prompt("Pokemon numbers (up to 6, comma separated)")
+        .interact_text()
+    {
+        Ok(numbers) => numbers,
+        _ => return Err(()),
+    };
+
+    let types = PokemonType::ALL
+        .iter()
+        .map(|t| t.get_value())
+        .collect::<Vec<String>>();
+
+    let mut members = vec![];
+    for number in numbers.split(',') {
+        let number = match number.trim().parse::<u16>() {
+            Ok(number) => number,
+            _ => return Err(()),
+        };
+
+        match MultiSelect::new()
+            .with_prompt(format!("Attacking move types of #{}", number))
+            .items(&types)
+            .interact()
+        {
+            Ok(indexes) => members.push(analyze_team::Member {
+                number,
+                move_types: indexes.into_iter().map(|i| types[i].clone()).collect(),
+            }),
+            _ => return Err(()),
+        }
+    }
+
+    Ok(members)
+}
+
+fn print_analysis(res: analyze_team::Response) {
+    let header = res
+        .members
+        .iter()
+        .map(|m| format!("{:>10}", truncate(&m.name)))
+        .collect::<String>();
+
+    println!();
+    println!("{}", style("Offensive coverage").bold());
+    println!("{:<10}{}", "", header);
+    for row in &res.offense {
+        println!(
+            "{:<10}{}",
+            row.defending_type,
+            row.multipliers
+                .iter()
+                .map(|m| cell(*m, true))
+                .collect::<String>()
+        );
+    }
+
+    println!();
+    println!("{}", style("Defensive matchups").bold());
+    println!("{:<10}{}", "", header);
+    for row in &res.defense {
+        println!(
+            "{:<10}{}",
+            row.attacking_type,
+            row.multipliers
+                .iter()
+                .map(|m| cell(*m, false))
+                .collect::<String>()
+        );
+    }
+
+    println!();
+    println!("Uncovered types: {}", list(&res.uncovered_types));
+    println!("Shared weaknesses: {}", list(&res.shared_weaknesses));
+    println!(
+        "Unresolved weaknesses: {}",
+        style(list(&res.unresolved_weaknesses)).red()
+    );
+}
+
+/// Colours a multiplier green when it favours the team and red otherwise.
+fn cell(multiplier: f32, offensive: bool) -> String {
+    let text = format!("{:>10}", format!("x{}", multiplier));
+    let good = if offensive {
+        multiplier > 1.0
+    } else {
+        multiplier < 1.0
+    };
+    let bad = if offensive {
+        multiplier < 1.0
+    } else {
+        multiplier > 1.0
+    };
+
+    if good {
+        style(text).green().to_string()
+    } else if bad {
+        style(text).red().to_string()
+    } else {
+        text
+    }
+}
+
+fn truncate(name: &str) -> String {
+    name.chars().take(9).collect()
+}
+
+fn list(types: &[String]) -> String {
+    if types.is_empty() {
+        "none".to_string()
+    } else {
+        types.join(", ")
+    }
+}
@@ -0,0 +1,50 @@
+use crate::domain::{fetch_all_api_keys, issue_api_key, revoke_api_key};
+use crate::repositories::api_key::ApiKeyRepository;
+use std::sync::Arc;
+
+/// Prints the new key, which cannot be shown again.
+pub fn issue(repo: Arc<dyn ApiKeyRepository>, name: &str, role: &str) {
+    let req = issue_api_key::Request {
+        name: name.to_string(),
+        role: role.to_string(),
+    };
+    match issue_api_key::execute(repo, req) {
+        Ok(res) => {
+            println!("Issued key #{} {} ({})", res.id, res.name, res.role);
+            println!("{}", res.key);
+            println!("Keep it safe, it cannot be shown again");
+        }
+        Err(issue_api_key::Error::BadRequest) => println!("The request is invalid"),
+        Err(issue_api_key::Error::Conflict) => println!("A key already has this name"),
+        Err(issue_api_key::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
+
+pub fn revoke(repo: Arc<dyn ApiKeyRepository>, id: &str) {
+    let id = match id.parse::<u32>() {
+        Ok(id) => id,
+        _ => {
+            println!("The request is invalid");
+            return;
+        }
+    };
+
+    match revoke_api_key::execute(repo, revoke_api_key::Request { id }) {
+        Ok(()) => println!("Revoked key #{}", id),
+        Err(revoke_api_key::Error::NotFound) => println!("The key does not exist"),
+        Err(revoke_api_key::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
+
+pub fn list(repo: Arc<dyn ApiKeyRepository>) {
+    match fetch_all_api_keys::execute(repo) {
+        Ok(keys) if keys.is_empty() => println!("No key has been issued"),
+        Ok(keys) => keys.into_iter().for_each(|k| {
+            println!(
+                "#{} {} ({}) issued at {}",
+                k.id, k.name, k.role, k.created_at
+            )
+        }),
+        Err(fetch_all_api_keys::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
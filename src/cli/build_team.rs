@@ -0,0 +1,95 @@
+use crate::cli::prompt_number;
+use crate::domain::create_team;
+use crate::domain::team::{TeamRules, MAX_MOVES, MAX_TEAM_SIZE};
+use crate::repositories::pokemon::Repository;
+use crate::repositories::team::TeamRepository;
+use dialoguer::{Confirm, Input};
+use std::sync::Arc;
+
+pub fn run(repo: Arc<dyn TeamRepository>, pokemon_repo: Arc<dyn Repository>, rules: &TeamRules) {
+    let name = match Input::<String>::new()
+        .with_prompt("Team name")
+        .interact_text()
+    {
+        Ok(name) => name,
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    let mut members = vec![];
+    while members.len() < MAX_TEAM_SIZE {
+        match prompt_member() {
+            Ok(member) => members.push(member),
+            _ => {
+                println!("An error occurred during the prompt");
+                return;
+            }
+        }
+
+        if members.len() < MAX_TEAM_SIZE
+            && !Confirm::new()
+                .with_prompt("Add another member?")
+                .interact()
+                .unwrap_or(false)
+        {
+            break;
+        }
+    }
+
+    match create_team::execute(
+        repo,
+        pokemon_repo,
+        rules,
+        create_team::Request { name, members },
+    ) {
+        Ok(res) => println!("{:#?}", res),
+        Err(create_team::Error::BadRequest) => println!("The request is invalid"),
+        Err(create_team::Error::NotFound) => println!("A Pokemon does not exist"),
+        Err(create_team::Error::RuleViolation(message)) => println!("{}", message),
+        Err(create_team::Error::Conflict) => println!("A team with this name already exists"),
+        Err(create_team::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
+
+fn prompt_member() -> Result<create_team::Member, ()> {
+    let number = prompt_number()?;
+    let nickname = prompt_optional("Nickname")?;
+    let level = match Input::<u8>::new()
+        .with_prompt("Level")
+        .default(50)
+        .interact_text()
+    {
+        Ok(level) => level,
+        _ => return Err(()),
+    };
+    let moves = match Input::<String>::new()
+        .with_prompt(format!("Moves (up to {}, comma separated)", MAX_MOVES))
+        .interact_text()
+    {
+        Ok(moves) => moves.split(',').map(|m| m.trim().to_string()).collect(),
+        _ => return Err(()),
+    };
+    let held_item = prompt_optional("Held item")?;
+
+    Ok(create_team::Member {
+        number,
+        nickname,
+        level,
+        moves,
+        held_item,
+    })
+}
+
+fn prompt_optional(prompt: &str) -> Result<Option<String>, ()> {
+    match Input::<String>::new()
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(value) if value.trim().is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value)),
+        _ => Err(()),
+    }
+}
@@ -1,25 +1,34 @@
-use crate::cli::{prompt_name, prompt_number, prompt_types};
+use crate::cli::{prompt_form, prompt_name, prompt_number, prompt_stats, prompt_types};
 use crate::domain::create_pokemon;
+use crate::domain::events::EventBus;
+use crate::domain::generation::Generations;
 use crate::repositories::pokemon::Repository;
 use std::sync::Arc;
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Response {
     number: u16,
+    form: String,
     name: String,
     types: Vec<String>,
+    stats: Vec<u16>,
 }
 
-pub fn run(repo: Arc<dyn Repository>) {
+pub fn run(repo: Arc<dyn Repository>, events: Arc<EventBus>, generations: &Generations) {
     let number = prompt_number();
+    let form = prompt_form();
     let name = prompt_name();
     let types = prompt_types();
+    let stats = prompt_stats();
 
-    let req = match (number, name, types) {
-        (Ok(number), Ok(name), Ok(types)) => create_pokemon::Request {
+    let req = match (number, form, name, types, stats) {
+        (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => create_pokemon::Request {
             number,
+            form,
             name,
             types,
+            stats,
         },
         _ => {
             print!("An error occured during the prompt");
@@ -27,15 +36,17 @@ pub fn run(repo: Arc<dyn Repository>) {
         }
     };
 
-    match create_pokemon::execute(repo, req.clone()) {
+    match create_pokemon::execute(repo, events, generations, req.clone()) {
         Ok(res) => println!(
             "{:#?}",
             Response {
                 number: res.number,
+                form: res.form,
                 name: res.name,
                 types: res.types,
+                stats: res.stats,
             }
         ),
-        Err(_) => println!("Error: {:#?}", req.clone()),
+        Err(_) => println!("Error: {:#?}", req),
     }
 }
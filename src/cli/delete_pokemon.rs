@@ -1,20 +1,22 @@
-use crate::cli::prompt_number;
+use crate::cli::{prompt_form, prompt_number};
 use crate::domain::delete_pokemon;
+use crate::domain::events::EventBus;
 use crate::repositories::pokemon::Repository;
 use std::sync::Arc;
 
-pub fn run(repo: Arc<dyn Repository>) {
+pub fn run(repo: Arc<dyn Repository>, events: Arc<EventBus>) {
     let number = prompt_number();
+    let form = prompt_form();
 
-    let req = match number {
-        Ok(number) => delete_pokemon::Request { number },
+    let req = match (number, form) {
+        (Ok(number), Ok(form)) => delete_pokemon::Request { number, form },
         _ => {
             println!("An error occurred during the prompt");
             return;
         }
     };
-    match delete_pokemon::execute(repo, req) {
-        Ok(()) => println!("The Pokemon has been deleted"),
+    match delete_pokemon::execute(repo, events, req) {
+        Ok(()) => println!("The Pokemon has been moved to the trash"),
         Err(delete_pokemon::Error::BadRequest) => println!("The request is invalid"),
         Err(delete_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
         Err(delete_pokemon::Error::Unknown) => println!("An unknown error occurred"),
@@ -0,0 +1,20 @@
+use crate::domain::delete_team;
+use crate::repositories::team::TeamRepository;
+use dialoguer::Input;
+use std::sync::Arc;
+
+pub fn run(repo: Arc<dyn TeamRepository>) {
+    let req = match Input::<u32>::new().with_prompt("Team id").interact_text() {
+        Ok(id) => delete_team::Request { id },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match delete_team::execute(repo, req) {
+        Ok(()) => println!("The team has been deleted"),
+        Err(delete_team::Error::NotFound) => println!("The team does not exist"),
+        Err(delete_team::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
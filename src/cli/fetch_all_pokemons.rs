@@ -1,28 +1,61 @@
+use crate::cli::prompt_generation;
 use crate::domain::fetch_all_pokemons;
+use crate::domain::generation::Generations;
 use crate::repositories::pokemon::Repository;
+use dialoguer::Input;
 use std::sync::Arc;
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Response {
     number: u16,
+    form: String,
     name: String,
     types: Vec<String>,
+    stats: Vec<u16>,
 }
 
-pub fn run(repo: Arc<dyn Repository>) {
-    match fetch_all_pokemons::execute(repo) {
+pub fn run(repo: Arc<dyn Repository>, generations: &Generations, languages: &[String]) {
+    let req = match (prompt_generation(generations), prompt_name_filter()) {
+        (Ok(generation), Ok(name)) => fetch_all_pokemons::Request {
+            generation,
+            name,
+            languages: languages.to_vec(),
+        },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match fetch_all_pokemons::execute(repo, generations, req) {
         Ok(res) => {
             res.into_iter().for_each(|p| {
                 println!(
                     "{:#?}",
                     Response {
                         number: p.number,
+                        form: p.form,
                         name: p.name,
-                        types: p.types
+                        types: p.types,
+                        stats: p.stats,
                     }
                 )
             });
         }
+        Err(fetch_all_pokemons::Error::BadRequest) => println!("The request is invalid"),
         Err(fetch_all_pokemons::Error::Unknown) => println!("an error."),
     }
 }
+
+fn prompt_name_filter() -> Result<Option<String>, ()> {
+    match Input::<String>::new()
+        .with_prompt("Name contains (leave empty for all)")
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(name) if name.trim().is_empty() => Ok(None),
+        Ok(name) => Ok(Some(name)),
+        _ => Err(()),
+    }
+}
@@ -0,0 +1,10 @@
+use crate::domain::fetch_all_teams;
+use crate::repositories::team::TeamRepository;
+use std::sync::Arc;
+
+pub fn run(repo: Arc<dyn TeamRepository>) {
+    match fetch_all_teams::execute(repo) {
+        Ok(res) => res.into_iter().for_each(|team| println!("{:#?}", team)),
+        Err(fetch_all_teams::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
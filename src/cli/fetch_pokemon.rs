@@ -1,20 +1,41 @@
-use crate::cli::prompt_number;
-use crate::domain::fetch_pokemon;
+use crate::cli::prompt_form;
+use crate::domain::fetch_pokemon::{self, Identifier};
 use crate::repositories::pokemon::Repository;
+use dialoguer::Input;
 use std::sync::Arc;
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Response {
     number: u16,
+    form: String,
     name: String,
+    names: Vec<(String, String)>,
     types: Vec<String>,
+    stats: Vec<u16>,
 }
 
-pub fn run(repo: Arc<dyn Repository>) {
-    let number = prompt_number();
+pub fn run(repo: Arc<dyn Repository>, languages: &[String]) {
+    let identifier = match prompt_identifier() {
+        Ok(identifier) => identifier,
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
 
-    let req = match number {
-        Ok(number) => fetch_pokemon::Request { number },
+    // A name already designates a single form.
+    let form = match identifier {
+        Identifier::Number(_) => prompt_form(),
+        Identifier::Name(_) => Ok(None),
+    };
+
+    let req = match form {
+        Ok(form) => fetch_pokemon::Request {
+            identifier,
+            form,
+            languages: languages.to_vec(),
+        },
         _ => {
             println!("An error occurred during the prompt");
             return;
@@ -25,8 +46,11 @@ pub fn run(repo: Arc<dyn Repository>) {
             "{:#?}",
             Response {
                 number: res.number,
+                form: res.form,
                 name: res.name,
+                names: res.names,
                 types: res.types,
+                stats: res.stats,
             }
         ),
         Err(fetch_pokemon::Error::BadRequest) => println!("The request is invalid"),
@@ -34,3 +58,13 @@ pub fn run(repo: Arc<dyn Repository>) {
         Err(fetch_pokemon::Error::Unknown) => println!("An unknown error occurred"),
     }
 }
+
+fn prompt_identifier() -> Result<Identifier, ()> {
+    match Input::<String>::new()
+        .with_prompt("Pokemon number or name")
+        .interact_text()
+    {
+        Ok(input) => Ok(Identifier::from(input)),
+        _ => Err(()),
+    }
+}
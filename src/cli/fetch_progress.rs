@@ -0,0 +1,44 @@
+use crate::cli::prompt_profile;
+use crate::domain::fetch_progress::{self, Completion};
+use crate::domain::generation::Generations;
+use crate::repositories::pokemon::Repository;
+use crate::repositories::progress::ProgressRepository;
+use std::sync::Arc;
+
+pub fn run(
+    repo: Arc<dyn ProgressRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    generations: &Generations,
+) {
+    let req = match prompt_profile() {
+        Ok(profile) => fetch_progress::Request { profile },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match fetch_progress::execute(repo, pokemon_repo, generations, req) {
+        Ok(res) => {
+            println!("{}", line("Overall", &res.overall));
+            for (kind, completion) in &res.by_type {
+                println!("{}", line(kind, completion));
+            }
+            for (generation, completion) in &res.by_generation {
+                println!(
+                    "{}",
+                    line(&format!("Generation {}", generation), completion)
+                );
+            }
+        }
+        Err(fetch_progress::Error::BadRequest) => println!("The request is invalid"),
+        Err(fetch_progress::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
+
+fn line(label: &str, c: &Completion) -> String {
+    format!(
+        "{:<14} seen {}/{} ({}%)  caught {}/{} ({}%)",
+        label, c.seen, c.total, c.seen_percent, c.caught, c.total, c.caught_percent
+    )
+}
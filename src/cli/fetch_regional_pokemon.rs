@@ -0,0 +1,65 @@
+use crate::cli::prompt_number;
+use crate::domain::generation::Generations;
+use crate::domain::{fetch_generations, fetch_regional_pokemon};
+use crate::repositories::pokemon::Repository;
+use dialoguer::Select;
+use std::sync::Arc;
+
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Response {
+    number: u16,
+    form: String,
+    name: String,
+    names: Vec<(String, String)>,
+    types: Vec<String>,
+    stats: Vec<u16>,
+}
+
+pub fn run(repo: Arc<dyn Repository>, generations: &Generations, languages: &[String]) {
+    let regions = fetch_generations::execute(generations)
+        .into_iter()
+        .map(|g| g.region)
+        .collect::<Vec<String>>();
+
+    let region = match Select::new()
+        .with_prompt("Region")
+        .items(&regions)
+        .interact()
+    {
+        Ok(index) => regions[index].clone(),
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    let req = match prompt_number() {
+        Ok(number) => fetch_regional_pokemon::Request {
+            region,
+            number,
+            languages: languages.to_vec(),
+        },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match fetch_regional_pokemon::execute(repo, generations, req) {
+        Ok(res) => println!(
+            "{:#?}",
+            Response {
+                number: res.number,
+                form: res.form,
+                name: res.name,
+                names: res.names,
+                types: res.types,
+                stats: res.stats,
+            }
+        ),
+        Err(fetch_regional_pokemon::Error::BadRequest) => println!("The request is invalid"),
+        Err(fetch_regional_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
+        Err(fetch_regional_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
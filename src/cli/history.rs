@@ -0,0 +1,54 @@
+use crate::domain::audit::PokemonSnapshot;
+use crate::domain::fetch_audit;
+use crate::repositories::audit::AuditRepository;
+use std::sync::Arc;
+
+/// Prints every change made to a Pokemon, oldest first.
+pub fn run(audit_repo: Arc<dyn AuditRepository>, number: &str) {
+    let number = match number.parse::<u16>() {
+        Ok(number) => number,
+        _ => {
+            println!("The request is invalid");
+            return;
+        }
+    };
+
+    let req = fetch_audit::Request {
+        number: Some(number),
+        since: None,
+    };
+    match fetch_audit::execute(audit_repo, req) {
+        Ok(entries) if entries.is_empty() => println!("No change has been recorded"),
+        Ok(entries) => entries.into_iter().for_each(|e| {
+            println!(
+                "{} {} {} ({}) by {}",
+                e.at, e.action, e.number, e.form, e.actor
+            );
+            if let Some(before) = e.before {
+                println!("  before: {}", describe(before));
+            }
+            if let Some(after) = e.after {
+                println!("  after:  {}", describe(after));
+            }
+        }),
+        Err(fetch_audit::Error::BadRequest) => println!("The request is invalid"),
+        Err(fetch_audit::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
+
+fn describe(snapshot: PokemonSnapshot) -> String {
+    let mut names = vec![snapshot.name];
+    names.extend(
+        snapshot
+            .names
+            .into_iter()
+            .map(|(language, name)| format!("{}: {}", language, name)),
+    );
+
+    format!(
+        "{} [{}] {:?}",
+        names.join(", "),
+        snapshot.types.join("/"),
+        snapshot.stats
+    )
+}
@@ -0,0 +1,47 @@
+use crate::cli::{prompt_number, prompt_profile};
+use crate::domain::mark_entry;
+use crate::repositories::pokemon::Repository;
+use crate::repositories::progress::ProgressRepository;
+use dialoguer::{Confirm, Input, Select};
+use std::sync::Arc;
+
+pub fn run(repo: Arc<dyn ProgressRepository>, pokemon_repo: Arc<dyn Repository>) {
+    let statuses = ["seen", "caught"];
+
+    let profile = prompt_profile();
+    let number = prompt_number();
+    let status = Select::new()
+        .with_prompt("Status")
+        .items(&statuses)
+        .default(0)
+        .interact();
+    let shiny = Confirm::new()
+        .with_prompt("Shiny?")
+        .default(false)
+        .interact();
+    let form = Input::<String>::new()
+        .with_prompt("Form")
+        .allow_empty(true)
+        .interact_text();
+
+    let req = match (profile, number, status, shiny, form) {
+        (Ok(profile), Ok(number), Ok(status), Ok(shiny), Ok(form)) => mark_entry::Request {
+            profile,
+            number,
+            status: statuses[status].to_string(),
+            shiny,
+            form: Some(form),
+        },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match mark_entry::execute(repo, pokemon_repo, req) {
+        Ok(res) => println!("{:#?}", res),
+        Err(mark_entry::Error::BadRequest) => println!("The request is invalid"),
+        Err(mark_entry::Error::NotFound) => println!("The Pokemon does not exist"),
+        Err(mark_entry::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
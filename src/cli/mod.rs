@@ -1,24 +1,67 @@
+mod analyze_team;
+pub mod api_keys;
+mod build_team;
 mod create_pokemon;
 mod delete_pokemon;
+mod delete_team;
 mod fetch_all_pokemons;
+mod fetch_all_teams;
 mod fetch_pokemon;
+mod fetch_progress;
+mod fetch_regional_pokemon;
+pub mod history;
+mod mark_entry;
+pub mod purge_trash;
+mod quiz;
+mod restore_pokemon;
+mod save_pokemon_name;
+mod search_pokemon;
+pub mod show_config;
+mod simulate_battle;
 
+use crate::domain::entities::PokemonType;
+use crate::domain::events::EventBus;
+use crate::domain::fetch_generations;
+use crate::domain::generation::Generations;
+use crate::domain::team::TeamRules;
 use crate::repositories::pokemon::Repository;
+use crate::repositories::progress::ProgressRepository;
+use crate::repositories::team::TeamRepository;
 use std::sync::Arc;
 
 use dialoguer::{theme::ColorfulTheme, Input, MultiSelect, Select};
 
-pub fn run(repo: Arc<dyn Repository>) {
+pub fn run(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    team_repo: Arc<dyn TeamRepository>,
+    progress_repo: Arc<dyn ProgressRepository>,
+    rules: TeamRules,
+    generations: &Generations,
+    languages: Vec<String>,
+) {
     loop {
         let choices = [
             "Fetch all Pokemons",
             "Fetch a Pokemon",
+            "Fetch a Pokemon by regional number",
+            "Search a Pokemon by name",
             "Create a Pokemon",
+            "Name a Pokemon in another language",
             "Delete a Pokemon",
+            "Restore a deleted Pokemon",
+            "Simulate battle",
+            "Build a team",
+            "Fetch all teams",
+            "Delete a team",
+            "Analyze team coverage",
+            "Mark a Pokedex entry",
+            "View Pokedex progress",
+            "Who's that Pokemon? (quiz)",
             "Exit",
         ];
 
-        println!("");
+        println!();
         let index = match Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Make your choice")
             .items(&choices)
@@ -30,11 +73,23 @@ pub fn run(repo: Arc<dyn Repository>) {
         };
 
         match index {
-            0 => fetch_all_pokemons::run(repo.clone()),
-            1 => fetch_pokemon::run(repo.clone()),
-            2 => create_pokemon::run(repo.clone()),
-            3 => delete_pokemon::run(repo.clone()),
-            4 => break,
+            0 => fetch_all_pokemons::run(repo.clone(), generations, &languages),
+            1 => fetch_pokemon::run(repo.clone(), &languages),
+            2 => fetch_regional_pokemon::run(repo.clone(), generations, &languages),
+            3 => search_pokemon::run(repo.clone(), &languages),
+            4 => create_pokemon::run(repo.clone(), events.clone(), generations),
+            5 => save_pokemon_name::run(repo.clone(), events.clone()),
+            6 => delete_pokemon::run(repo.clone(), events.clone()),
+            7 => restore_pokemon::run(repo.clone(), events.clone(), &languages),
+            8 => simulate_battle::run(repo.clone()),
+            9 => build_team::run(team_repo.clone(), repo.clone(), &rules),
+            10 => fetch_all_teams::run(team_repo.clone()),
+            11 => delete_team::run(team_repo.clone()),
+            12 => analyze_team::run(repo.clone()),
+            13 => mark_entry::run(progress_repo.clone(), repo.clone()),
+            14 => fetch_progress::run(progress_repo.clone(), repo.clone(), generations),
+            15 => quiz::run(repo.clone(), generations, &languages),
+            16 => break,
             _ => continue,
         };
     }
@@ -47,6 +102,26 @@ pub fn prompt_number() -> Result<u16, ()> {
     }
 }
 
+/// An empty answer means the default form.
+pub fn prompt_form() -> Result<Option<String>, ()> {
+    match Input::<String>::new()
+        .with_prompt("Pokemon form (leave empty for the default one)")
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(form) if form.trim().is_empty() => Ok(None),
+        Ok(form) => Ok(Some(form)),
+        _ => Err(()),
+    }
+}
+
+pub fn prompt_profile() -> Result<String, ()> {
+    match Input::new().with_prompt("Profile").interact_text() {
+        Ok(profile) => Ok(profile),
+        _ => Err(()),
+    }
+}
+
 pub fn prompt_name() -> Result<String, ()> {
     match Input::new().with_prompt("Pokemon name").interact_text() {
         Ok(name) => Ok(name),
@@ -55,7 +130,10 @@ pub fn prompt_name() -> Result<String, ()> {
 }
 
 pub fn prompt_types() -> Result<Vec<String>, ()> {
-    let types = ["Electric", "Fire"];
+    let types = PokemonType::ALL
+        .iter()
+        .map(|t| t.get_value())
+        .collect::<Vec<String>>();
     match MultiSelect::new()
         .with_prompt("Pokemon types")
         .items(&types)
@@ -63,8 +141,43 @@ pub fn prompt_types() -> Result<Vec<String>, ()> {
     {
         Ok(indexes) => Ok(indexes
             .into_iter()
-            .map(|i| types[i].to_string())
+            .map(|i| types[i].clone())
             .collect::<Vec<String>>()),
         _ => Err(()),
     }
 }
+
+pub fn prompt_generation(generations: &Generations) -> Result<Option<u8>, ()> {
+    let generations = fetch_generations::execute(generations);
+    let mut choices = vec!["All generations".to_string()];
+    choices.extend(
+        generations
+            .iter()
+            .map(|g| format!("Generation {} ({})", g.number, g.region)),
+    );
+
+    match Select::new()
+        .with_prompt("Generation")
+        .items(&choices)
+        .default(0)
+        .interact()
+    {
+        Ok(0) => Ok(None),
+        Ok(index) => Ok(Some(generations[index - 1].number)),
+        _ => Err(()),
+    }
+}
+
+pub fn prompt_stats() -> Result<Vec<u16>, ()> {
+    let mut stats = vec![];
+    for stat in ["HP", "Attack", "Defense", "Speed"] {
+        match Input::new()
+            .with_prompt(format!("Base {}", stat))
+            .interact_text()
+        {
+            Ok(value) => stats.push(value),
+            _ => return Err(()),
+        }
+    }
+    Ok(stats)
+}
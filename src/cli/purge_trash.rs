@@ -0,0 +1,11 @@
+use crate::domain::purge_trash;
+use crate::repositories::pokemon::Repository;
+use std::sync::Arc;
+
+/// Purges the Pokemons deleted longer than `retention` seconds ago.
+pub fn run(repo: Arc<dyn Repository>, retention: u64) {
+    match purge_trash::execute(repo, purge_trash::Request { retention }) {
+        Ok(res) => println!("{} Pokemon(s) purged", res.purged),
+        Err(purge_trash::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
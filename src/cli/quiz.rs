@@ -0,0 +1,109 @@
+use crate::cli::prompt_generation;
+use crate::domain::entities::PokemonType;
+use crate::domain::fuzzy::is_close_enough;
+use crate::domain::generation::Generations;
+use crate::domain::random_pokemon;
+use crate::repositories::pokemon::Repository;
+use dialoguer::{Confirm, Input, Select};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Shows the types and stats of a random Pokemon and asks for its name, in
+/// any language, until the player stops.
+pub fn run(repo: Arc<dyn Repository>, generations: &Generations, languages: &[String]) {
+    let (kind, generation) = match (prompt_type(), prompt_generation(generations)) {
+        (Ok(kind), Ok(generation)) => (kind, generation),
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    let (mut score, mut rounds) = (0, 0);
+
+    loop {
+        let req = random_pokemon::Request {
+            seed: seed.wrapping_add(rounds),
+            kind: kind.clone(),
+            generation,
+            languages: languages.to_vec(),
+        };
+        let pokemon = match random_pokemon::execute(repo.clone(), generations, req) {
+            Ok(pokemon) => pokemon,
+            Err(random_pokemon::Error::BadRequest) => {
+                println!("The request is invalid");
+                return;
+            }
+            Err(random_pokemon::Error::NotFound) => {
+                println!("No Pokemon matches these filters");
+                return;
+            }
+            Err(random_pokemon::Error::Unknown) => {
+                println!("An unknown error occurred");
+                return;
+            }
+        };
+
+        println!();
+        println!("Who's that Pokemon?");
+        println!("Types: {}", pokemon.types.join(", "));
+        println!(
+            "HP {} / Attack {} / Defense {} / Speed {}",
+            pokemon.stats[0], pokemon.stats[1], pokemon.stats[2], pokemon.stats[3]
+        );
+
+        let answer = match Input::<String>::new()
+            .with_prompt("Your answer")
+            .allow_empty(true)
+            .interact_text()
+        {
+            Ok(answer) => answer,
+            _ => {
+                println!("An error occurred during the prompt");
+                return;
+            }
+        };
+
+        rounds += 1;
+        if pokemon
+            .names
+            .iter()
+            .any(|(_, name)| is_close_enough(&answer, name))
+        {
+            score += 1;
+            println!("Correct, it's {}!", pokemon.name);
+        } else {
+            println!("Wrong, it was {}", pokemon.name);
+        }
+        println!("Score: {}/{}", score, rounds);
+
+        match Confirm::new()
+            .with_prompt("Play another round?")
+            .default(true)
+            .interact()
+        {
+            Ok(true) => continue,
+            _ => break,
+        }
+    }
+}
+
+fn prompt_type() -> Result<Option<String>, ()> {
+    let mut choices = vec!["All types".to_string()];
+    choices.extend(PokemonType::ALL.iter().map(|t| t.get_value()));
+
+    match Select::new()
+        .with_prompt("Type")
+        .items(&choices)
+        .default(0)
+        .interact()
+    {
+        Ok(0) => Ok(None),
+        Ok(index) => Ok(Some(choices[index].clone())),
+        _ => Err(()),
+    }
+}
@@ -0,0 +1,68 @@
+use crate::domain::events::EventBus;
+use crate::domain::{fetch_trash, restore_pokemon};
+use crate::repositories::pokemon::Repository;
+use dialoguer::Select;
+use std::sync::Arc;
+
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Response {
+    number: u16,
+    form: String,
+    name: String,
+    types: Vec<String>,
+    stats: Vec<u16>,
+}
+
+/// Lists the trash and brings back the Pokemon picked.
+pub fn run(repo: Arc<dyn Repository>, events: Arc<EventBus>, languages: &[String]) {
+    let deleted = match fetch_trash::execute(repo.clone()) {
+        Ok(deleted) if deleted.is_empty() => {
+            println!("The trash is empty");
+            return;
+        }
+        Ok(deleted) => deleted,
+        Err(fetch_trash::Error::Unknown) => {
+            println!("An unknown error occurred");
+            return;
+        }
+    };
+
+    let choices = deleted
+        .iter()
+        .map(|d| format!("#{} {} ({})", d.number, d.name, d.form))
+        .collect::<Vec<String>>();
+    let choice = match Select::new()
+        .with_prompt("Pokemon to restore")
+        .items(&choices)
+        .default(0)
+        .interact()
+    {
+        Ok(index) => &deleted[index],
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    let req = restore_pokemon::Request {
+        number: choice.number,
+        form: Some(choice.form.clone()),
+        languages: languages.to_vec(),
+    };
+    match restore_pokemon::execute(repo, events, req) {
+        Ok(res) => println!(
+            "{:#?}",
+            Response {
+                number: res.number,
+                form: res.form,
+                name: res.name,
+                types: res.types,
+                stats: res.stats,
+            }
+        ),
+        Err(restore_pokemon::Error::BadRequest) => println!("The request is invalid"),
+        Err(restore_pokemon::Error::NotFound) => println!("The Pokemon is not in the trash"),
+        Err(restore_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
@@ -0,0 +1,61 @@
+use crate::cli::{prompt_form, prompt_name, prompt_number};
+use crate::domain::events::EventBus;
+use crate::domain::save_pokemon_name;
+use crate::repositories::pokemon::Repository;
+use dialoguer::Input;
+use std::sync::Arc;
+
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Response {
+    number: u16,
+    form: String,
+    names: Vec<(String, String)>,
+}
+
+pub fn run(repo: Arc<dyn Repository>, events: Arc<EventBus>) {
+    let number = prompt_number();
+    let form = prompt_form();
+    let language = prompt_language();
+    let name = prompt_name();
+
+    let req = match (number, form, language, name) {
+        (Ok(number), Ok(form), Ok(language), Ok(name)) => save_pokemon_name::Request {
+            number,
+            form,
+            language,
+            name,
+        },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match save_pokemon_name::execute(repo, events, req) {
+        Ok(res) => println!(
+            "{:#?}",
+            Response {
+                number: res.number,
+                form: res.form,
+                names: res.names,
+            }
+        ),
+        Err(save_pokemon_name::Error::BadRequest) => println!("The request is invalid"),
+        Err(save_pokemon_name::Error::NotFound) => println!("The Pokemon does not exist"),
+        Err(save_pokemon_name::Error::Conflict) => {
+            println!("Another Pokemon already has this name")
+        }
+        Err(save_pokemon_name::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
+
+fn prompt_language() -> Result<String, ()> {
+    match Input::new()
+        .with_prompt("Language (en, fr, ja...)")
+        .interact_text()
+    {
+        Ok(language) => Ok(language),
+        _ => Err(()),
+    }
+}
@@ -0,0 +1,91 @@
+use crate::domain::fetch_pokemon::{self, Identifier};
+use crate::domain::search_pokemon;
+use crate::repositories::pokemon::Repository;
+use dialoguer::{Input, Select};
+use std::sync::Arc;
+
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Response {
+    number: u16,
+    form: String,
+    name: String,
+    names: Vec<(String, String)>,
+    types: Vec<String>,
+    stats: Vec<u16>,
+}
+
+/// Suggests the closest names to what was typed and shows the one picked.
+pub fn run(repo: Arc<dyn Repository>, languages: &[String]) {
+    let query = match Input::<String>::new()
+        .with_prompt("Pokemon name (typos are fine)")
+        .interact_text()
+    {
+        Ok(query) => query,
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    let req = search_pokemon::Request {
+        query,
+        kind: None,
+        limit: None,
+        languages: languages.to_vec(),
+    };
+    let matches = match search_pokemon::execute(repo.clone(), req) {
+        Ok(matches) if matches.is_empty() => {
+            println!("No Pokemon has a name close to this one");
+            return;
+        }
+        Ok(matches) => matches,
+        Err(search_pokemon::Error::BadRequest) => {
+            println!("The request is invalid");
+            return;
+        }
+        Err(search_pokemon::Error::Unknown) => {
+            println!("An unknown error occurred");
+            return;
+        }
+    };
+
+    let choices = matches
+        .iter()
+        .map(|m| format!("#{} {} ({})", m.number, m.name, m.form))
+        .collect::<Vec<String>>();
+    let choice = match Select::new()
+        .with_prompt("Did you mean")
+        .items(&choices)
+        .default(0)
+        .interact()
+    {
+        Ok(index) => &matches[index],
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    let req = fetch_pokemon::Request {
+        identifier: Identifier::Number(choice.number),
+        form: Some(choice.form.clone()),
+        languages: languages.to_vec(),
+    };
+    match fetch_pokemon::execute(repo, req) {
+        Ok(res) => println!(
+            "{:#?}",
+            Response {
+                number: res.number,
+                form: res.form,
+                name: res.name,
+                names: res.names,
+                types: res.types,
+                stats: res.stats,
+            }
+        ),
+        Err(fetch_pokemon::Error::BadRequest) => println!("The request is invalid"),
+        Err(fetch_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
+        Err(fetch_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
@@ -0,0 +1,8 @@
+use crate::config::Config;
+
+/// Prints every setting, its value and where it comes from.
+pub fn run(config: &Config) {
+    for (name, value, source) in config.entries() {
+        println!("{:<20} {:<24} ({})", name, value, source);
+    }
+}
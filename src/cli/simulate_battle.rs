@@ -0,0 +1,169 @@
+use crate::domain::battle::{Event, Side};
+use crate::domain::entities::PokemonType;
+use crate::domain::simulate_battle;
+use crate::domain::team::MAX_MOVES;
+use crate::repositories::pokemon::Repository;
+use dialoguer::{Confirm, Input, Select};
+use std::sync::Arc;
+
+pub fn run(repo: Arc<dyn Repository>) {
+    let seed = Input::<u64>::new()
+        .with_prompt("Seed")
+        .default(0)
+        .interact_text();
+
+    let req = match (seed, prompt_team("First team"), prompt_team("Second team")) {
+        (Ok(seed), Ok(first), Ok(second)) => simulate_battle::Request {
+            seed,
+            first,
+            second,
+        },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match simulate_battle::execute(repo, req) {
+        Ok(res) => res
+            .log
+            .into_iter()
+            .for_each(|event| println!("{}", describe(event))),
+        Err(simulate_battle::Error::BadRequest) => println!("The request is invalid"),
+        Err(simulate_battle::Error::NotFound) => println!("A Pokemon does not exist"),
+        Err(simulate_battle::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
+
+fn prompt_team(team: &str) -> Result<Vec<simulate_battle::TeamMember>, ()> {
+    let numbers = match Input::<String>::new()
+        .with_prompt(format!("{} Pokemon numbers (comma separated)", team))
+        .interact_text()
+    {
+        Ok(numbers) => numbers,
+        _ => return Err(()),
+    };
+
+    let mut members = vec![];
+    for number in numbers.split(',') {
+        let number = match number.trim().parse::<u16>() {
+            Ok(number) => number,
+            _ => return Err(()),
+        };
+
+        let level = match Input::<u8>::new()
+            .with_prompt(format!("Level of #{}", number))
+            .default(50)
+            .interact_text()
+        {
+            Ok(level) => level,
+            _ => return Err(()),
+        };
+
+        let mut moves = vec![];
+        loop {
+            moves.push(prompt_move()?);
+
+            if moves.len() == MAX_MOVES {
+                break;
+            }
+            match Confirm::new().with_prompt("Add another move?").interact() {
+                Ok(true) => continue,
+                Ok(false) => break,
+                _ => return Err(()),
+            }
+        }
+
+        members.push(simulate_battle::TeamMember {
+            number,
+            level,
+            moves,
+        });
+    }
+
+    Ok(members)
+}
+
+fn prompt_move() -> Result<simulate_battle::MoveRequest, ()> {
+    let types = PokemonType::ALL
+        .iter()
+        .map(|t| t.get_value())
+        .collect::<Vec<String>>();
+
+    let name = Input::<String>::new()
+        .with_prompt("Move name")
+        .interact_text();
+    let kind = Select::new()
+        .with_prompt("Move type")
+        .items(&types)
+        .default(0)
+        .interact();
+    let power = Input::<u16>::new()
+        .with_prompt("Move power")
+        .interact_text();
+    let accuracy = Input::<u8>::new()
+        .with_prompt("Move accuracy")
+        .default(100)
+        .interact_text();
+    let priority = Input::<i8>::new()
+        .with_prompt("Move priority")
+        .default(0)
+        .interact_text();
+
+    match (name, kind, power, accuracy, priority) {
+        (Ok(name), Ok(kind), Ok(power), Ok(accuracy), Ok(priority)) => {
+            Ok(simulate_battle::MoveRequest {
+                name,
+                kind: types[kind].clone(),
+                power,
+                accuracy,
+                priority,
+            })
+        }
+        _ => Err(()),
+    }
+}
+
+fn team(side: Side) -> &'static str {
+    match side {
+        Side::First => "First team",
+        Side::Second => "Second team",
+    }
+}
+
+fn describe(event: Event) -> String {
+    match event {
+        Event::SentOut { side, pokemon, hp } => {
+            format!("{} sends out {} ({} HP)", team(side), pokemon, hp)
+        }
+        Event::TurnStarted { turn } => format!("-- Turn {} --", turn),
+        Event::MoveUsed {
+            pokemon, move_name, ..
+        } => format!("{} uses {}", pokemon, move_name),
+        Event::Missed { pokemon, .. } => format!("{}'s attack missed", pokemon),
+        Event::Damaged {
+            pokemon,
+            damage,
+            remaining_hp,
+            effectiveness,
+            ..
+        } => {
+            let note = if effectiveness == 0.0 {
+                " It has no effect..."
+            } else if effectiveness > 1.0 {
+                " It's super effective!"
+            } else if effectiveness < 1.0 {
+                " It's not very effective..."
+            } else {
+                ""
+            };
+            format!(
+                "{} takes {} damage ({} HP left).{}",
+                pokemon, damage, remaining_hp, note
+            )
+        }
+        Event::Fainted { pokemon, .. } => format!("{} fainted!", pokemon),
+        Event::BattleEnded { winner: Some(side) } => format!("{} wins!", team(side)),
+        Event::BattleEnded { winner: None } => "The battle ended in a draw".to_string(),
+    }
+}
@@ -0,0 +1,886 @@
+use crate::domain::purge_trash::DEFAULT_RETENTION_DAYS;
+use crate::domain::team::{TeamRules, MAX_LEVEL};
+use serde::Deserialize;
+use std::fmt;
+
+/// Where the value of a setting comes from, the later ones overriding the
+/// earlier ones.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Default,
+    File(String),
+    Env(&'static str),
+    Flag(&'static str),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "file {}", path),
+            Source::Env(name) => write!(f, "env {}", name),
+            Source::Flag(name) => write!(f, "flag --{}", name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Backend {
+    Memory,
+    Sqlite,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Backend::Memory => write!(f, "memory"),
+            Backend::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+fn parse_backend(value: &str) -> Option<Backend> {
+    match value {
+        "memory" => Some(Backend::Memory),
+        "sqlite" => Some(Backend::Sqlite),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogLevel::Error => write!(f, "error"),
+            LogLevel::Warn => write!(f, "warn"),
+            LogLevel::Info => write!(f, "info"),
+            LogLevel::Debug => write!(f, "debug"),
+        }
+    }
+}
+
+fn parse_log_level(value: &str) -> Option<LogLevel> {
+    match value {
+        "error" => Some(LogLevel::Error),
+        "warn" => Some(LogLevel::Warn),
+        "info" => Some(LogLevel::Info),
+        "debug" => Some(LogLevel::Debug),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LogFormat {
+    Logfmt,
+    Json,
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogFormat::Logfmt => write!(f, "logfmt"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+fn parse_log_format(value: &str) -> Option<LogFormat> {
+    match value {
+        "logfmt" => Some(LogFormat::Logfmt),
+        "json" => Some(LogFormat::Json),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct File {
+    #[serde(default)]
+    repository: RepositoryTable,
+    #[serde(default)]
+    api: ApiTable,
+    #[serde(default)]
+    log: LogTable,
+    #[serde(default)]
+    cache: CacheTable,
+    #[serde(default)]
+    auth: AuthTable,
+    #[serde(default)]
+    rate_limit: RateLimitTable,
+    #[serde(default)]
+    pokedex: PokedexTable,
+    #[serde(default)]
+    team: TeamTable,
+    #[serde(default)]
+    trash: TrashTable,
+    #[serde(default)]
+    cli: CliTable,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct RepositoryTable {
+    backend: Option<String>,
+    path: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ApiTable {
+    host: Option<String>,
+    port: Option<u16>,
+    threads: Option<usize>,
+    shutdown_timeout: Option<u64>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct LogTable {
+    level: Option<String>,
+    format: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct CacheTable {
+    ttl: Option<u64>,
+    capacity: Option<usize>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct AuthTable {
+    keys: Option<Vec<String>>,
+    jwt_secret: Option<String>,
+    jwt_audience: Option<String>,
+    jwt_role_claim: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct RateLimitTable {
+    read: Option<u32>,
+    write: Option<u32>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct PokedexTable {
+    generations: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct TeamTable {
+    level_cap: Option<u8>,
+    species_clause: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct TrashTable {
+    retention_days: Option<u64>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct CliTable {
+    language: Option<String>,
+}
+
+/// The values given on the command line, not parsed yet.
+#[derive(Default)]
+pub struct Flags<'a> {
+    pub backend: Option<&'a str>,
+    pub sqlite: Option<&'a str>,
+    pub host: Option<&'a str>,
+    pub port: Option<&'a str>,
+    pub threads: Option<&'a str>,
+    pub shutdown_timeout: Option<&'a str>,
+    pub rate_limit_read: Option<&'a str>,
+    pub rate_limit_write: Option<&'a str>,
+    pub log_level: Option<&'a str>,
+    pub log_format: Option<&'a str>,
+    pub cache_ttl: Option<&'a str>,
+    pub cache_capacity: Option<&'a str>,
+    pub api_keys: Option<Vec<&'a str>>,
+    pub jwt_secret: Option<&'a str>,
+    pub jwt_audience: Option<&'a str>,
+    pub jwt_role_claim: Option<&'a str>,
+    pub generations: Option<&'a str>,
+    pub level_cap: Option<&'a str>,
+    /// `true` when the switch is given.
+    pub species_clause: Option<&'a str>,
+    pub trash_retention: Option<&'a str>,
+    pub language: Option<&'a str>,
+}
+
+pub struct Config {
+    pub backend: Setting<Backend>,
+    pub sqlite: Setting<Option<String>>,
+    pub host: Setting<String>,
+    /// `0` lets the system pick a free port.
+    pub port: Setting<u16>,
+    /// A thread is started for every request when not set.
+    pub threads: Setting<Option<usize>>,
+    /// Seconds the requests in flight are given to finish on shutdown.
+    pub shutdown_timeout: Setting<u64>,
+    /// Requests per minute a client can make to read the Pokedex, `0`
+    /// disabling the limit.
+    pub rate_limit_read: Setting<u32>,
+    /// Requests per minute a client can make to change the Pokedex.
+    pub rate_limit_write: Setting<u32>,
+    pub log_level: Setting<LogLevel>,
+    pub log_format: Setting<LogFormat>,
+    /// Seconds the reads of the Pokedex stay cached, `0` disabling the cache.
+    pub cache_ttl: Setting<u64>,
+    /// Most reads kept in the cache.
+    pub cache_capacity: Setting<usize>,
+    pub api_keys: Setting<Vec<String>>,
+    /// Secret the HS256 tokens are signed with, the tokens being refused
+    /// when not set.
+    pub jwt_secret: Setting<Option<String>>,
+    /// Expected in the `aud` claim of the tokens, when set.
+    pub jwt_audience: Setting<Option<String>>,
+    /// Claim of the tokens holding the role.
+    pub jwt_role_claim: Setting<String>,
+    /// JSON file describing the generations and regional Pokedexes, the
+    /// nine main series ones being used when not set.
+    pub generations: Setting<Option<String>>,
+    /// Highest level allowed in a team.
+    pub level_cap: Setting<u8>,
+    /// Whether a team may hold only one Pokemon of each species.
+    pub species_clause: Setting<bool>,
+    /// Days the deleted Pokemons stay in the trash before being purged.
+    pub trash_retention: Setting<u64>,
+    /// Language of the Pokemon names in the cli, English when not set.
+    pub language: Setting<Option<String>>,
+}
+
+/// Looks a setting up in the flags, then the environment, then the file, the
+/// value found being checked the same way wherever it comes from.
+struct Layers<'a> {
+    file: File,
+    path: Option<&'a str>,
+    env: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl<'a> Layers<'a> {
+    fn resolve<T>(
+        &self,
+        flag: (&'static str, Option<&str>),
+        env: &'static str,
+        file: Option<String>,
+        parse: impl Fn(&str) -> Option<T>,
+        default: T,
+    ) -> Result<Setting<T>, String> {
+        let parsed = |value: &str, source: Source| match parse(value.trim()) {
+            Some(value) => Ok(Setting { value, source }),
+            None => Err(format!("Invalid value \"{}\" from {}", value, source)),
+        };
+
+        if let Some(value) = flag.1 {
+            return parsed(value, Source::Flag(flag.0));
+        }
+        if let Some(value) = (self.env)(env) {
+            return parsed(&value, Source::Env(env));
+        }
+        match (file, self.path) {
+            (Some(value), Some(path)) => parsed(&value, Source::File(path.to_string())),
+            _ => Ok(Setting {
+                value: default,
+                source: Source::Default,
+            }),
+        }
+    }
+
+    /// Looks a list up like `resolve`, its values given one by one by the
+    /// repeated flag and the file, and separated by commas in the environment.
+    /// The blank values are left out.
+    fn resolve_list(
+        &self,
+        flag: (&'static str, Option<Vec<&str>>),
+        env: &'static str,
+        file: Option<Vec<String>>,
+    ) -> Setting<Vec<String>> {
+        let listed = |values: Vec<String>, source: Source| Setting {
+            value: values
+                .iter()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+                .collect(),
+            source,
+        };
+
+        if let Some(values) = flag.1 {
+            return listed(
+                values.into_iter().map(String::from).collect(),
+                Source::Flag(flag.0),
+            );
+        }
+        if let Some(value) = (self.env)(env) {
+            return listed(
+                value.split(',').map(String::from).collect(),
+                Source::Env(env),
+            );
+        }
+        match (file, self.path) {
+            (Some(values), Some(path)) => listed(values, Source::File(path.to_string())),
+            _ => Setting {
+                value: vec![],
+                source: Source::Default,
+            },
+        }
+    }
+}
+
+/// A number of the file as written, to be parsed like the other values.
+fn raw<T: ToString>(value: Option<T>) -> Option<String> {
+    value.map(|value| value.to_string())
+}
+
+impl Config {
+    /// `file` is the path and the content of the TOML file, if any, and `env`
+    /// reads an environment variable.
+    pub fn load(
+        file: Option<(&str, &str)>,
+        env: &dyn Fn(&str) -> Option<String>,
+        flags: Flags,
+    ) -> Result<Self, String> {
+        let layers = Layers {
+            file: match file {
+                Some((path, content)) => match toml::from_str::<File>(content) {
+                    Ok(file) => file,
+                    Err(e) => return Err(format!("The config file {} is invalid: {}", path, e)),
+                },
+                None => File::default(),
+            },
+            path: file.map(|(path, _)| path),
+            env,
+        };
+        let file = &layers.file;
+
+        let some = |value: &str| Some(Some(value.to_string()));
+        let sqlite = layers.resolve(
+            ("sqlite", flags.sqlite),
+            "POKEMON_SQLITE",
+            file.repository.path.clone(),
+            some,
+            None,
+        )?;
+
+        // Giving a database is enough to use it.
+        let default_backend = match &sqlite.value {
+            Some(_) => Backend::Sqlite,
+            None => Backend::Memory,
+        };
+        let mut backend = layers.resolve(
+            ("backend", flags.backend),
+            "POKEMON_BACKEND",
+            file.repository.backend.clone(),
+            parse_backend,
+            default_backend,
+        )?;
+        if backend.source == Source::Default && sqlite.source != Source::Default {
+            backend.source = sqlite.source.clone();
+        }
+        if backend.value == Backend::Sqlite && sqlite.value.is_none() {
+            return Err("The sqlite backend needs the path of the database".into());
+        }
+
+        let threads = layers.resolve(
+            ("threads", flags.threads),
+            "POKEMON_THREADS",
+            raw(file.api.threads),
+            |v| v.parse::<usize>().ok().map(Some),
+            None,
+        )?;
+        if threads.value == Some(0) {
+            return Err("The number of threads must be a positive number".into());
+        }
+
+        // An empty secret would let anyone sign tokens.
+        let non_empty = |value: &str| Some(Some(value.to_string())).filter(|_| !value.is_empty());
+
+        Ok(Self {
+            host: layers.resolve(
+                ("host", flags.host),
+                "POKEMON_HOST",
+                file.api.host.clone(),
+                |v| Some(v.to_string()),
+                "localhost".to_string(),
+            )?,
+            port: layers.resolve(
+                ("port", flags.port),
+                "POKEMON_PORT",
+                raw(file.api.port),
+                |v| v.parse::<u16>().ok(),
+                8080,
+            )?,
+            threads,
+            shutdown_timeout: layers.resolve(
+                ("shutdown-timeout", flags.shutdown_timeout),
+                "POKEMON_SHUTDOWN_TIMEOUT",
+                raw(file.api.shutdown_timeout),
+                |v| v.parse::<u64>().ok(),
+                30,
+            )?,
+            rate_limit_read: layers.resolve(
+                ("rate-limit-read", flags.rate_limit_read),
+                "POKEMON_RATE_LIMIT_READ",
+                raw(file.rate_limit.read),
+                |v| v.parse::<u32>().ok(),
+                600,
+            )?,
+            rate_limit_write: layers.resolve(
+                ("rate-limit-write", flags.rate_limit_write),
+                "POKEMON_RATE_LIMIT_WRITE",
+                raw(file.rate_limit.write),
+                |v| v.parse::<u32>().ok(),
+                60,
+            )?,
+            log_level: layers.resolve(
+                ("log-level", flags.log_level),
+                "POKEMON_LOG_LEVEL",
+                file.log.level.clone(),
+                parse_log_level,
+                LogLevel::Info,
+            )?,
+            log_format: layers.resolve(
+                ("log-format", flags.log_format),
+                "POKEMON_LOG_FORMAT",
+                file.log.format.clone(),
+                parse_log_format,
+                LogFormat::Logfmt,
+            )?,
+            cache_ttl: layers.resolve(
+                ("cache-ttl", flags.cache_ttl),
+                "POKEMON_CACHE_TTL",
+                raw(file.cache.ttl),
+                |v| v.parse::<u64>().ok(),
+                0,
+            )?,
+            cache_capacity: layers.resolve(
+                ("cache-capacity", flags.cache_capacity),
+                "POKEMON_CACHE_CAPACITY",
+                raw(file.cache.capacity),
+                |v| v.parse::<usize>().ok(),
+                1000,
+            )?,
+            api_keys: layers.resolve_list(
+                ("api-key", flags.api_keys),
+                "POKEMON_API_KEYS",
+                file.auth.keys.clone(),
+            ),
+            jwt_secret: layers.resolve(
+                ("jwt-secret", flags.jwt_secret),
+                "POKEMON_JWT_SECRET",
+                file.auth.jwt_secret.clone(),
+                non_empty,
+                None,
+            )?,
+            jwt_audience: layers.resolve(
+                ("jwt-audience", flags.jwt_audience),
+                "POKEMON_JWT_AUDIENCE",
+                file.auth.jwt_audience.clone(),
+                non_empty,
+                None,
+            )?,
+            jwt_role_claim: layers.resolve(
+                ("jwt-role-claim", flags.jwt_role_claim),
+                "POKEMON_JWT_ROLE_CLAIM",
+                file.auth.jwt_role_claim.clone(),
+                |v| Some(v.to_string()).filter(|v| !v.is_empty()),
+                "role".to_string(),
+            )?,
+            generations: layers.resolve(
+                ("generations", flags.generations),
+                "POKEMON_GENERATIONS",
+                file.pokedex.generations.clone(),
+                some,
+                None,
+            )?,
+            level_cap: layers.resolve(
+                ("level-cap", flags.level_cap),
+                "POKEMON_LEVEL_CAP",
+                raw(file.team.level_cap),
+                |v| v.parse::<u8>().ok().filter(|l| (1..=MAX_LEVEL).contains(l)),
+                TeamRules::default().level_cap,
+            )?,
+            species_clause: layers.resolve(
+                ("species-clause", flags.species_clause),
+                "POKEMON_SPECIES_CLAUSE",
+                raw(file.team.species_clause),
+                |v| v.parse::<bool>().ok(),
+                TeamRules::default().species_clause,
+            )?,
+            trash_retention: layers.resolve(
+                ("trash-retention", flags.trash_retention),
+                "POKEMON_TRASH_RETENTION",
+                raw(file.trash.retention_days),
+                |v| v.parse::<u64>().ok(),
+                DEFAULT_RETENTION_DAYS,
+            )?,
+            language: layers.resolve(
+                ("lang", flags.language),
+                "POKEMON_LANG",
+                file.cli.language.clone(),
+                non_empty,
+                None,
+            )?,
+            backend,
+            sqlite,
+        })
+    }
+
+    /// The path of the database when the sqlite backend is used.
+    pub fn sqlite_path(&self) -> Option<&str> {
+        match self.backend.value {
+            Backend::Sqlite => self.sqlite.value.as_deref(),
+            Backend::Memory => None,
+        }
+    }
+
+    /// Every setting, its value and where it comes from, secrets hidden.
+    pub fn entries(&self) -> Vec<(&'static str, String, &Source)> {
+        let or_none = |value: Option<String>| value.unwrap_or_else(|| "none".to_string());
+
+        vec![
+            (
+                "repository.backend",
+                self.backend.value.to_string(),
+                &self.backend.source,
+            ),
+            (
+                "repository.path",
+                or_none(self.sqlite.value.clone()),
+                &self.sqlite.source,
+            ),
+            ("api.host", self.host.value.clone(), &self.host.source),
+            ("api.port", self.port.value.to_string(), &self.port.source),
+            (
+                "api.threads",
+                or_none(self.threads.value.map(|t| t.to_string())),
+                &self.threads.source,
+            ),
+            (
+                "api.shutdown_timeout",
+                self.shutdown_timeout.value.to_string(),
+                &self.shutdown_timeout.source,
+            ),
+            (
+                "rate_limit.read",
+                self.rate_limit_read.value.to_string(),
+                &self.rate_limit_read.source,
+            ),
+            (
+                "rate_limit.write",
+                self.rate_limit_write.value.to_string(),
+                &self.rate_limit_write.source,
+            ),
+            (
+                "log.level",
+                self.log_level.value.to_string(),
+                &self.log_level.source,
+            ),
+            (
+                "log.format",
+                self.log_format.value.to_string(),
+                &self.log_format.source,
+            ),
+            (
+                "cache.ttl",
+                self.cache_ttl.value.to_string(),
+                &self.cache_ttl.source,
+            ),
+            (
+                "cache.capacity",
+                self.cache_capacity.value.to_string(),
+                &self.cache_capacity.source,
+            ),
+            (
+                "auth.keys",
+                format!("{} key(s)", self.api_keys.value.len()),
+                &self.api_keys.source,
+            ),
+            (
+                "auth.jwt_secret",
+                or_none(self.jwt_secret.value.as_ref().map(|_| "set".to_string())),
+                &self.jwt_secret.source,
+            ),
+            (
+                "auth.jwt_audience",
+                or_none(self.jwt_audience.value.clone()),
+                &self.jwt_audience.source,
+            ),
+            (
+                "auth.jwt_role_claim",
+                self.jwt_role_claim.value.clone(),
+                &self.jwt_role_claim.source,
+            ),
+            (
+                "pokedex.generations",
+                or_none(self.generations.value.clone()),
+                &self.generations.source,
+            ),
+            (
+                "team.level_cap",
+                self.level_cap.value.to_string(),
+                &self.level_cap.source,
+            ),
+            (
+                "team.species_clause",
+                self.species_clause.value.to_string(),
+                &self.species_clause.source,
+            ),
+            (
+                "trash.retention_days",
+                self.trash_retention.value.to_string(),
+                &self.trash_retention.source,
+            ),
+            (
+                "cli.language",
+                or_none(self.language.value.clone()),
+                &self.language.source,
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const FILE: &str = r#"
+        [repository]
+        path = "./file.sqlite"
+
+        [api]
+        host = "0.0.0.0"
+        port = 9000
+
+        [log]
+        level = "debug"
+
+        [auth]
+        keys = ["first", "second"]
+        jwt_secret = "gateway secret"
+
+        [rate_limit]
+        read = 120
+
+        [team]
+        level_cap = 50
+        species_clause = true
+    "#;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<String, String>>();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn it_should_use_the_defaults_when_nothing_is_given() {
+        let config = Config::load(None, &env(&[]), Flags::default())
+            .ok()
+            .unwrap();
+
+        assert_eq!(config.backend.value, Backend::Memory);
+        assert_eq!(config.sqlite_path(), None);
+        assert_eq!(config.host.value, "localhost".to_string());
+        assert_eq!(config.port.value, 8080);
+        assert_eq!(config.threads.value, None);
+        assert_eq!(config.shutdown_timeout.value, 30);
+        assert_eq!(config.rate_limit_read.value, 600);
+        assert_eq!(config.rate_limit_write.value, 60);
+        assert_eq!(config.log_level.value, LogLevel::Info);
+        assert_eq!(config.log_format.value, LogFormat::Logfmt);
+        assert!(config.api_keys.value.is_empty());
+        assert_eq!(config.jwt_secret.value, None);
+        assert_eq!(config.generations.value, None);
+        assert_eq!(config.level_cap.value, 100);
+        assert!(!config.species_clause.value);
+        assert_eq!(config.trash_retention.value, 30);
+        assert_eq!(config.language.value, None);
+        assert!(config
+            .entries()
+            .iter()
+            .all(|(_, _, source)| **source == Source::Default));
+    }
+
+    #[test]
+    fn it_should_read_the_file() {
+        let config = Config::load(Some(("pokemon.toml", FILE)), &env(&[]), Flags::default())
+            .ok()
+            .unwrap();
+
+        assert_eq!(config.sqlite_path(), Some("./file.sqlite"));
+        assert_eq!(config.backend.value, Backend::Sqlite);
+        assert_eq!(config.backend.source, Source::File("pokemon.toml".into()));
+        assert_eq!(config.port.value, 9000);
+        assert_eq!(config.log_level.value, LogLevel::Debug);
+        assert_eq!(config.api_keys.value, vec!["first", "second"]);
+        assert_eq!(config.jwt_secret.value, Some("gateway secret".to_string()));
+        assert_eq!(config.jwt_role_claim.value, "role".to_string());
+        assert_eq!(config.rate_limit_read.value, 120);
+        assert_eq!(config.rate_limit_write.source, Source::Default);
+        assert_eq!(config.threads.source, Source::Default);
+        assert_eq!(config.level_cap.value, 50);
+        assert!(config.species_clause.value);
+        assert_eq!(config.trash_retention.source, Source::Default);
+    }
+
+    #[test]
+    fn it_should_override_the_file_with_the_environment() {
+        let env = env(&[
+            ("POKEMON_PORT", "9001"),
+            ("POKEMON_API_KEYS", "third"),
+            ("POKEMON_RATE_LIMIT_READ", "0"),
+        ]);
+
+        let config = Config::load(Some(("pokemon.toml", FILE)), &env, Flags::default())
+            .ok()
+            .unwrap();
+
+        assert_eq!(config.port.value, 9001);
+        assert_eq!(config.port.source, Source::Env("POKEMON_PORT"));
+        assert_eq!(config.api_keys.value, vec!["third"]);
+        assert_eq!(config.rate_limit_read.value, 0);
+        assert_eq!(config.host.value, "0.0.0.0".to_string());
+    }
+
+    #[test]
+    fn it_should_keep_the_keys_of_the_file_and_the_flags_whole() {
+        let file = "[auth]\nkeys = [\"first,second\", \" \"]";
+
+        let config = Config::load(Some(("pokemon.toml", file)), &env(&[]), Flags::default())
+            .ok()
+            .unwrap();
+        assert_eq!(config.api_keys.value, vec!["first,second"]);
+
+        let flags = Flags {
+            api_keys: Some(vec!["third,fourth"]),
+            ..Flags::default()
+        };
+        let config = Config::load(Some(("pokemon.toml", file)), &env(&[]), flags)
+            .ok()
+            .unwrap();
+        assert_eq!(config.api_keys.value, vec!["third,fourth"]);
+        assert_eq!(config.api_keys.source, Source::Flag("api-key"));
+    }
+
+    #[test]
+    fn it_should_override_the_environment_with_the_flags() {
+        let env = env(&[
+            ("POKEMON_PORT", "9001"),
+            ("POKEMON_BACKEND", "sqlite"),
+            ("POKEMON_LEVEL_CAP", "60"),
+            ("POKEMON_TRASH_RETENTION", "7"),
+        ]);
+        let flags = Flags {
+            port: Some("9002"),
+            backend: Some("memory"),
+            level_cap: Some("70"),
+            language: Some("fr"),
+            ..Flags::default()
+        };
+
+        let config = Config::load(Some(("pokemon.toml", FILE)), &env, flags)
+            .ok()
+            .unwrap();
+
+        assert_eq!(config.port.value, 9002);
+        assert_eq!(config.port.source, Source::Flag("port"));
+        assert_eq!(config.backend.value, Backend::Memory);
+        assert_eq!(config.sqlite_path(), None);
+        assert_eq!(config.level_cap.value, 70);
+        assert_eq!(config.trash_retention.value, 7);
+        assert_eq!(config.language.value, Some("fr".to_string()));
+    }
+
+    #[test]
+    fn it_should_reject_invalid_values() {
+        let invalid = |flags: Flags| Config::load(None, &env(&[]), flags).is_err();
+
+        assert!(invalid(Flags {
+            port: Some("80000"),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            threads: Some("0"),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            rate_limit_write: Some("-1"),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            log_level: Some("loud"),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            log_format: Some("xml"),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            jwt_secret: Some(""),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            jwt_role_claim: Some(""),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            backend: Some("sqlite"),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            level_cap: Some("0"),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            level_cap: Some("101"),
+            ..Flags::default()
+        }));
+        assert!(invalid(Flags {
+            trash_retention: Some("a month"),
+            ..Flags::default()
+        }));
+        assert!(Config::load(
+            Some(("pokemon.toml", "[auth]\njwt_secret = \"\"")),
+            &env(&[]),
+            Flags::default()
+        )
+        .is_err());
+        assert!(Config::load(
+            Some(("pokemon.toml", "[log]\nlevel = \"loud\"")),
+            &env(&[]),
+            Flags::default()
+        )
+        .is_err());
+        assert!(Config::load(
+            Some(("pokemon.toml", "[api]\nhots = 1")),
+            &env(&[]),
+            Flags::default()
+        )
+        .is_err());
+    }
+}
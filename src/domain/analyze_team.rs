@@ -0,0 +1,273 @@
+use crate::domain::entities::{Pokemon, PokemonForm, PokemonNumber, PokemonType};
+use crate::domain::team::MAX_TEAM_SIZE;
+use crate::repositories::pokemon::{FetchOneError, Repository};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub number: u16,
+    pub move_types: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub members: Vec<MemberSummary>,
+    pub offense: Vec<OffenseRow>,
+    pub defense: Vec<DefenseRow>,
+    /// Defending types no member can hit super-effectively.
+    pub uncovered_types: Vec<String>,
+    /// Attacking types that hit at least two members super-effectively.
+    pub shared_weaknesses: Vec<String>,
+    /// Attacking types hitting a member super-effectively that nobody resists.
+    pub unresolved_weaknesses: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct MemberSummary {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct OffenseRow {
+    pub defending_type: String,
+    /// Best multiplier each member's moves reach against this type.
+    pub multipliers: Vec<f32>,
+}
+
+#[derive(Debug)]
+pub struct DefenseRow {
+    pub attacking_type: String,
+    /// Multiplier taken by each member from this type.
+    pub multipliers: Vec<f32>,
+    pub weak: usize,
+    pub resistant: usize,
+    pub immune: usize,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    if req.members.is_empty() || req.members.len() > MAX_TEAM_SIZE {
+        return Err(Error::BadRequest);
+    }
+
+    let mut members: Vec<(Pokemon, Vec<PokemonType>)> = vec![];
+    for member in req.members {
+        let number = match PokemonNumber::try_from(member.number) {
+            Ok(number) => number,
+            _ => return Err(Error::BadRequest),
+        };
+
+        let mut move_types = vec![];
+        for t in member.move_types {
+            match PokemonType::try_from(t) {
+                Ok(t) => move_types.push(t),
+                _ => return Err(Error::BadRequest),
+            }
+        }
+
+        match repo.fetch_one(number, PokemonForm::default()) {
+            Ok(pokemon) => members.push((pokemon, move_types)),
+            Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+            Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+        }
+    }
+
+    let offense = PokemonType::ALL
+        .iter()
+        .map(|defending| OffenseRow {
+            defending_type: defending.get_value(),
+            multipliers: members
+                .iter()
+                .map(|(_, move_types)| {
+                    move_types
+                        .iter()
+                        .map(|m| m.effectiveness_against(*defending))
+                        .fold(0.0, f32::max)
+                })
+                .collect(),
+        })
+        .collect::<Vec<OffenseRow>>();
+
+    let defense = PokemonType::ALL
+        .iter()
+        .map(|attacking| {
+            let multipliers = members
+                .iter()
+                .map(|(pokemon, _)| pokemon.types.effectiveness_of(*attacking))
+                .collect::<Vec<f32>>();
+
+            DefenseRow {
+                attacking_type: attacking.get_value(),
+                weak: multipliers.iter().filter(|m| **m > 1.0).count(),
+                resistant: multipliers
+                    .iter()
+                    .filter(|m| **m > 0.0 && **m < 1.0)
+                    .count(),
+                immune: multipliers.iter().filter(|m| **m == 0.0).count(),
+                multipliers,
+            }
+        })
+        .collect::<Vec<DefenseRow>>();
+
+    Ok(Response {
+        uncovered_types: offense
+            .iter()
+            .filter(|row| row.multipliers.iter().all(|m| *m < 2.0))
+            .map(|row| row.defending_type.clone())
+            .collect(),
+        shared_weaknesses: defense
+            .iter()
+            .filter(|row| row.weak >= 2)
+            .map(|row| row.attacking_type.clone())
+            .collect(),
+        unresolved_weaknesses: defense
+            .iter()
+            .filter(|row| row.weak > 0 && row.resistant + row.immune == 0)
+            .map(|row| row.attacking_type.clone())
+            .collect(),
+        members: members
+            .into_iter()
+            .map(|(pokemon, _)| MemberSummary {
+                number: pokemon.number.to_u16(),
+                name: pokemon.name.to_string(),
+                types: pokemon.types.to_vec_string(),
+            })
+            .collect(),
+        offense,
+        defense,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonForm, PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repo_with_pokemons() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo
+    }
+
+    fn member(number: PokemonNumber, move_types: &[&str]) -> Member {
+        Member {
+            number: number.to_u16(),
+            move_types: move_types.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_team_is_empty() {
+        let repo = repo_with_pokemons();
+
+        match execute(repo, Request { members: vec![] }) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_move_type_is_invalid() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            members: vec![member(PokemonNumber::pikachu(), &["Sound"])],
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_a_pokemon_is_not_stored() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            members: vec![member(PokemonNumber::pikachu(), &["Electric"])],
+        };
+
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            members: vec![member(PokemonNumber::pikachu(), &["Electric"])],
+        };
+
+        match execute(repo, req) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_coverage_matrix_otherwise() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            members: vec![
+                member(PokemonNumber::pikachu(), &["Electric"]),
+                member(PokemonNumber::charmander(), &["Fire"]),
+            ],
+        };
+
+        match execute(repo, req) {
+            Ok(res) => {
+                let water = res
+                    .offense
+                    .iter()
+                    .find(|row| row.defending_type == "Water")
+                    .unwrap();
+                assert_eq!(water.multipliers, vec![2.0, 0.5]);
+                assert!(!res.uncovered_types.contains(&"Grass".to_string()));
+                assert!(res.uncovered_types.contains(&"Dragon".to_string()));
+
+                let ground = res
+                    .defense
+                    .iter()
+                    .find(|row| row.attacking_type == "Ground")
+                    .unwrap();
+                assert_eq!(ground.multipliers, vec![2.0, 2.0]);
+                assert_eq!(ground.weak, 2);
+                assert_eq!(res.shared_weaknesses, vec!["Ground".to_string()]);
+                assert!(res.unresolved_weaknesses.contains(&"Ground".to_string()));
+                assert!(res.unresolved_weaknesses.contains(&"Water".to_string()));
+                assert!(!res.unresolved_weaknesses.contains(&"Fire".to_string()));
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,162 @@
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Prefix of the keys, so that they are easy to spot in a leaked file.
+const KEY_PREFIX: &str = "pkdx_";
+const KEY_BYTES: usize = 24;
+
+/// What a key allows, every role allowing what the previous ones do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    /// Reads the Pokedex.
+    Reader,
+    /// Creates and updates.
+    Editor,
+    /// Deletes, and manages the webhooks and the keys.
+    Admin,
+}
+
+impl TryFrom<String> for Role {
+    type Error = ();
+
+    fn try_from(role: String) -> Result<Self, Self::Error> {
+        match role.trim() {
+            "reader" => Ok(Self::Reader),
+            "editor" => Ok(Self::Editor),
+            "admin" => Ok(Self::Admin),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::Reader => write!(f, "reader"),
+            Role::Editor => write!(f, "editor"),
+            Role::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, Eq, PartialOrd, Ord)]
+pub struct ApiKeyId(u32);
+
+impl ApiKeyId {
+    pub fn to_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for ApiKeyId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+/// Tells the keys apart, since only their hash is kept.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiKeyName(String);
+
+impl TryFrom<String> for ApiKeyName {
+    type Error = ();
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            Err(())
+        } else {
+            Ok(Self(name))
+        }
+    }
+}
+
+impl From<ApiKeyName> for String {
+    fn from(name: ApiKeyName) -> Self {
+        name.0
+    }
+}
+
+/// Hex encoded SHA-256 of a key, the key itself being shown only once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiKeyHash(String);
+
+impl ApiKeyHash {
+    pub fn of(key: &str) -> Self {
+        Self(hex::encode(Sha256::digest(key.as_bytes())))
+    }
+}
+
+impl From<ApiKeyHash> for String {
+    fn from(hash: ApiKeyHash) -> Self {
+        hash.0
+    }
+}
+
+impl TryFrom<String> for ApiKeyHash {
+    type Error = ();
+
+    fn try_from(hash: String) -> Result<Self, Self::Error> {
+        if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            Ok(Self(hash))
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ApiKey {
+    pub id: ApiKeyId,
+    pub name: ApiKeyName,
+    pub role: Role,
+    pub hash: ApiKeyHash,
+    pub created_at: u64,
+}
+
+/// A new key drawn from the randomness of the system.
+pub fn generate_key() -> Result<String, ()> {
+    let mut bytes = [0u8; KEY_BYTES];
+    match getrandom::getrandom(&mut bytes) {
+        Ok(()) => Ok(format!("{}{}", KEY_PREFIX, hex::encode(bytes))),
+        _ => Err(()),
+    }
+}
+
+#[cfg(test)]
+impl ApiKeyName {
+    pub fn ci() -> Self {
+        Self("ci".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_order_the_roles_by_what_they_allow() {
+        assert!(Role::Reader < Role::Editor);
+        assert!(Role::Editor < Role::Admin);
+        assert_eq!(Role::try_from("editor".to_string()), Ok(Role::Editor));
+        assert!(Role::try_from("owner".to_string()).is_err());
+    }
+
+    #[test]
+    fn it_should_hash_with_sha256() {
+        assert_eq!(
+            String::from(ApiKeyHash::of("abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn it_should_generate_different_prefixed_keys() {
+        let (first, second) = (generate_key().unwrap(), generate_key().unwrap());
+
+        assert!(first.starts_with(KEY_PREFIX));
+        assert_eq!(first.len(), KEY_PREFIX.len() + 2 * KEY_BYTES);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::domain::entities::{Pokemon, PokemonForm, PokemonNumber};
+use std::convert::TryFrom;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditAction {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+    Purged,
+}
+
+impl TryFrom<String> for AuditAction {
+    type Error = ();
+
+    fn try_from(action: String) -> Result<Self, Self::Error> {
+        match action.as_str() {
+            "created" => Ok(Self::Created),
+            "updated" => Ok(Self::Updated),
+            "deleted" => Ok(Self::Deleted),
+            "restored" => Ok(Self::Restored),
+            "purged" => Ok(Self::Purged),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<AuditAction> for String {
+    fn from(action: AuditAction) -> Self {
+        match action {
+            AuditAction::Created => "created".to_string(),
+            AuditAction::Updated => "updated".to_string(),
+            AuditAction::Deleted => "deleted".to_string(),
+            AuditAction::Restored => "restored".to_string(),
+            AuditAction::Purged => "purged".to_string(),
+        }
+    }
+}
+
+/// State of a Pokemon at the time of a change, kept as plain values so that
+/// old entries stay readable whatever the rules on names or types become.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PokemonSnapshot {
+    pub name: String,
+    /// Language and name of every translation.
+    pub names: Vec<(String, String)>,
+    pub types: Vec<String>,
+    pub stats: Vec<u16>,
+}
+
+impl From<&Pokemon> for PokemonSnapshot {
+    fn from(pokemon: &Pokemon) -> Self {
+        Self {
+            name: pokemon.name.to_string(),
+            names: pokemon
+                .translations
+                .iter()
+                .map(|(language, name)| (language.to_string(), name.to_string()))
+                .collect(),
+            types: pokemon.types.to_vec_string(),
+            stats: pokemon.stats.to_vec_u16(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    /// Unix timestamp, in seconds.
+    pub at: u64,
+    /// Who made the change, such as `cli:ash` or `api:127.0.0.1`.
+    pub actor: String,
+    pub action: AuditAction,
+    pub number: PokemonNumber,
+    pub form: PokemonForm,
+    pub before: Option<PokemonSnapshot>,
+    pub after: Option<PokemonSnapshot>,
+}
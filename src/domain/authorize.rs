@@ -0,0 +1,292 @@
+use crate::domain::api_key::{ApiKeyHash, Role};
+use crate::domain::clock::now;
+use crate::domain::jwt::{self, JwtError, JwtVerifier};
+use crate::repositories::api_key::{ApiKeyRepository, FetchOneError};
+use std::sync::Arc;
+
+pub struct Request {
+    /// The API key or the token sent by the client, if any.
+    pub key: Option<String>,
+    /// The least role allowed to make the request.
+    pub required: Role,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub name: String,
+    pub role: Role,
+    /// Tells the holders apart whatever their credentials: `key:` and the id
+    /// of an issued key, `config:` and the hash of a configured one, `jwt:`
+    /// and the subject of a token, or `token:` and its hash without one.
+    pub client: String,
+}
+
+/// What the API accepts besides the keys it issued.
+#[derive(Clone, Default)]
+pub struct Credentials {
+    /// The keys given in the configuration, allowed everything so that the
+    /// first keys can be issued through the API.
+    pub admin_keys: Vec<String>,
+    /// Checks the tokens, which are refused when not set.
+    pub jwt: Option<JwtVerifier>,
+}
+
+pub enum Error {
+    /// No key was given, or not a known one.
+    Unauthenticated,
+    /// A token was given but it is not valid.
+    InvalidToken(JwtError),
+    /// The key is known but its role is not enough.
+    Forbidden(Role),
+    Unknown,
+}
+
+/// A JWT names its holder with the `sub` claim, `jwt` when it has none.
+pub fn execute(
+    repo: Arc<dyn ApiKeyRepository>,
+    credentials: &Credentials,
+    req: Request,
+) -> Result<Response, Error> {
+    let key = match req.key.filter(|k| !k.is_empty()) {
+        Some(key) => key,
+        None => return Err(Error::Unauthenticated),
+    };
+
+    let hash = ApiKeyHash::of(&key);
+    let (name, role, client) = if jwt::looks_like_jwt(&key) {
+        let claims = match &credentials.jwt {
+            Some(verifier) => verifier.verify(&key, now()).map_err(Error::InvalidToken)?,
+            None => return Err(Error::Unauthenticated),
+        };
+        match claims.subject {
+            Some(subject) => (subject.clone(), claims.role, format!("jwt:{}", subject)),
+            None => (
+                "jwt".to_string(),
+                claims.role,
+                format!("token:{}", String::from(hash)),
+            ),
+        }
+    } else if credentials
+        .admin_keys
+        .iter()
+        .any(|k| ApiKeyHash::of(k) == hash)
+    {
+        (
+            "config".to_string(),
+            Role::Admin,
+            format!("config:{}", String::from(hash)),
+        )
+    } else {
+        match repo.fetch_by_hash(hash) {
+            Ok(found) => (
+                String::from(found.name),
+                found.role,
+                format!("key:{}", found.id.to_u32()),
+            ),
+            Err(FetchOneError::NotFound) => return Err(Error::Unauthenticated),
+            Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+        }
+    };
+
+    if role < req.required {
+        return Err(Error::Forbidden(role));
+    }
+    Ok(Response { name, role, client })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::api_key::ApiKeyName;
+    use crate::repositories::api_key::InMemoryApiKeyRepository;
+    use serde_json::json;
+
+    fn request(key: Option<&str>, required: Role) -> Request {
+        Request {
+            key: key.map(String::from),
+            required,
+        }
+    }
+
+    fn repo_with_reader() -> Arc<InMemoryApiKeyRepository> {
+        let repo = Arc::new(InMemoryApiKeyRepository::new());
+        repo.insert(ApiKeyName::ci(), Role::Reader, ApiKeyHash::of("reader"), 0)
+            .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_an_unauthenticated_error_when_no_key_is_given() {
+        match execute(
+            repo_with_reader(),
+            &Credentials::default(),
+            request(None, Role::Reader),
+        ) {
+            Err(Error::Unauthenticated) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unauthenticated_error_when_the_key_is_unknown() {
+        match execute(
+            repo_with_reader(),
+            &Credentials::default(),
+            request(Some("guess"), Role::Reader),
+        ) {
+            Err(Error::Unauthenticated) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_forbidden_error_when_the_role_is_not_enough() {
+        match execute(
+            repo_with_reader(),
+            &Credentials::default(),
+            request(Some("reader"), Role::Editor),
+        ) {
+            Err(Error::Forbidden(Role::Reader)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new().with_error());
+
+        match execute(
+            repo,
+            &Credentials::default(),
+            request(Some("reader"), Role::Reader),
+        ) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_allow_the_configured_keys_everything() {
+        let credentials = Credentials {
+            admin_keys: vec!["bootstrap".to_string()],
+            jwt: None,
+        };
+
+        match execute(
+            repo_with_reader(),
+            &credentials,
+            request(Some("bootstrap"), Role::Admin),
+        ) {
+            Ok(res) => assert_eq!(res.role, Role::Admin),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_invalid_token_error_when_the_token_is_not_valid() {
+        let credentials = Credentials {
+            admin_keys: vec![],
+            jwt: Some(JwtVerifier::test()),
+        };
+        let expired =
+            JwtVerifier::test().sign(json!({ "aud": "pokedex", "role": "admin", "exp": 1 }));
+
+        match execute(
+            repo_with_reader(),
+            &credentials,
+            request(Some(&expired), Role::Reader),
+        ) {
+            Err(Error::InvalidToken(JwtError::Expired)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unauthenticated_error_when_the_tokens_are_not_accepted() {
+        let token = JwtVerifier::test()
+            .sign(json!({ "aud": "pokedex", "role": "admin", "exp": now() + 60 }));
+
+        match execute(
+            repo_with_reader(),
+            &Credentials::default(),
+            request(Some(&token), Role::Reader),
+        ) {
+            Err(Error::Unauthenticated) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_take_the_role_from_the_token() {
+        let credentials = Credentials {
+            admin_keys: vec![],
+            jwt: Some(JwtVerifier::test()),
+        };
+        let token = JwtVerifier::test().sign(
+            json!({ "sub": "gateway", "aud": "pokedex", "role": "editor", "exp": now() + 60 }),
+        );
+
+        match execute(
+            repo_with_reader(),
+            &credentials,
+            request(Some(&token), Role::Admin),
+        ) {
+            Err(Error::Forbidden(Role::Editor)) => {}
+            _ => unreachable!(),
+        };
+        match execute(
+            repo_with_reader(),
+            &credentials,
+            request(Some(&token), Role::Editor),
+        ) {
+            Ok(res) => assert_eq!(res.name, "gateway"),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_tell_the_clients_apart_whatever_their_credentials() {
+        let credentials = Credentials {
+            admin_keys: vec![],
+            jwt: Some(JwtVerifier::test()),
+        };
+        let client = |key: &str| match execute(
+            repo_with_reader(),
+            &credentials,
+            request(Some(key), Role::Reader),
+        ) {
+            Ok(res) => res.client,
+            _ => unreachable!(),
+        };
+        let token = |claims| JwtVerifier::test().sign(claims);
+
+        let named = client(&token(
+            json!({ "sub": "ci", "aud": "pokedex", "role": "reader", "exp": now() + 60 }),
+        ));
+        let first = client(&token(
+            json!({ "aud": "pokedex", "role": "reader", "exp": now() + 60 }),
+        ));
+        let second = client(&token(
+            json!({ "aud": "pokedex", "role": "reader", "exp": now() + 120 }),
+        ));
+
+        assert_eq!(named, "jwt:ci");
+        assert_ne!(named, client("reader"));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn it_should_return_the_key_otherwise() {
+        match execute(
+            repo_with_reader(),
+            &Credentials::default(),
+            request(Some("reader"), Role::Reader),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.name, "ci");
+                assert_eq!(res.role, Role::Reader);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
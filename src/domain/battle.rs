@@ -0,0 +1,536 @@
+use crate::domain::entities::{Pokemon, PokemonType};
+use crate::domain::rng::Rng;
+use crate::domain::team::{MAX_LEVEL, MAX_MOVES};
+use std::convert::TryFrom;
+
+const MAX_TURNS: u16 = 500;
+
+#[derive(Clone, Debug)]
+pub struct Move {
+    name: String,
+    kind: PokemonType,
+    power: u16,
+    accuracy: u8,
+    priority: i8,
+}
+
+impl Move {
+    pub fn try_new(
+        name: String,
+        kind: String,
+        power: u16,
+        accuracy: u8,
+        priority: i8,
+    ) -> Result<Self, ()> {
+        let kind = PokemonType::try_from(kind)?;
+        if name.is_empty()
+            || power == 0
+            || power > 250
+            || accuracy == 0
+            || accuracy > 100
+            || !(-7..=5).contains(&priority)
+        {
+            return Err(());
+        }
+
+        Ok(Self {
+            name,
+            kind,
+            power,
+            accuracy,
+            priority,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Combatant {
+    pokemon: Pokemon,
+    level: u8,
+    moves: Vec<Move>,
+}
+
+impl Combatant {
+    pub fn try_new(pokemon: Pokemon, level: u8, moves: Vec<Move>) -> Result<Self, ()> {
+        if level == 0 || level > MAX_LEVEL || moves.is_empty() || moves.len() > MAX_MOVES {
+            return Err(());
+        }
+
+        Ok(Self {
+            pokemon,
+            level,
+            moves,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    First,
+    Second,
+}
+
+impl Side {
+    fn other(self) -> Self {
+        match self {
+            Side::First => Side::Second,
+            Side::Second => Side::First,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Side::First => 0,
+            Side::Second => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    SentOut {
+        side: Side,
+        pokemon: String,
+        hp: u16,
+    },
+    TurnStarted {
+        turn: u16,
+    },
+    MoveUsed {
+        side: Side,
+        pokemon: String,
+        move_name: String,
+    },
+    Missed {
+        side: Side,
+        pokemon: String,
+    },
+    Damaged {
+        side: Side,
+        pokemon: String,
+        damage: u16,
+        remaining_hp: u16,
+        effectiveness: f32,
+    },
+    Fainted {
+        side: Side,
+        pokemon: String,
+    },
+    BattleEnded {
+        winner: Option<Side>,
+    },
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub winner: Option<Side>,
+    pub turns: u16,
+    pub log: Vec<Event>,
+}
+
+struct Fighter {
+    combatant: Combatant,
+    name: String,
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    speed: u16,
+}
+
+impl Fighter {
+    fn new(combatant: Combatant) -> Self {
+        let level = u32::from(combatant.level);
+        let stats = &combatant.pokemon.stats;
+        let scale = |base: u16| (2 * u32::from(base) * level / 100) as u16;
+
+        Self {
+            name: combatant.pokemon.name.to_string(),
+            hp: scale(stats.hp()) + combatant.level as u16 + 10,
+            attack: scale(stats.attack()) + 5,
+            defense: scale(stats.defense()) + 5,
+            speed: scale(stats.speed()) + 5,
+            combatant,
+        }
+    }
+
+    fn is_fainted(&self) -> bool {
+        self.hp == 0
+    }
+
+    fn modifier(&self, m: &Move, target: &Fighter) -> f32 {
+        let stab = if self.combatant.pokemon.types.contains(m.kind) {
+            1.5
+        } else {
+            1.0
+        };
+        stab * target.combatant.pokemon.types.effectiveness_of(m.kind)
+    }
+
+    /// Picks the move with the highest expected damage against `target`.
+    fn choose_move(&self, target: &Fighter) -> usize {
+        let expected = |m: &Move| {
+            f32::from(m.power) * self.modifier(m, target) * f32::from(m.accuracy) / 100.0
+        };
+
+        let mut best = 0;
+        for (i, m) in self.combatant.moves.iter().enumerate() {
+            if expected(m) > expected(&self.combatant.moves[best]) {
+                best = i;
+            }
+        }
+        best
+    }
+}
+
+struct Team {
+    fighters: Vec<Fighter>,
+    active: usize,
+}
+
+impl Team {
+    fn new(combatants: Vec<Combatant>) -> Self {
+        Self {
+            fighters: combatants.into_iter().map(Fighter::new).collect(),
+            active: 0,
+        }
+    }
+
+    fn active(&self) -> &Fighter {
+        &self.fighters[self.active]
+    }
+
+    fn next_available(&self) -> Option<usize> {
+        self.fighters.iter().position(|f| !f.is_fainted())
+    }
+}
+
+/// Runs a full battle between two teams. The same teams and seed always
+/// produce the same outcome.
+pub fn simulate(first: Vec<Combatant>, second: Vec<Combatant>, seed: u64) -> Outcome {
+    let mut rng = Rng::new(seed);
+    let mut teams = [Team::new(first), Team::new(second)];
+    let mut log = vec![];
+
+    for side in [Side::First, Side::Second] {
+        match teams[side.index()].fighters.first() {
+            Some(fighter) => log.push(Event::SentOut {
+                side,
+                pokemon: fighter.name.clone(),
+                hp: fighter.hp,
+            }),
+            None => return end(Some(side.other()), 0, log),
+        }
+    }
+
+    let mut turn = 0;
+    while turn < MAX_TURNS {
+        turn += 1;
+        log.push(Event::TurnStarted { turn });
+
+        let choices = [
+            teams[0].active().choose_move(teams[1].active()),
+            teams[1].active().choose_move(teams[0].active()),
+        ];
+
+        for side in turn_order(&teams, choices, &mut rng) {
+            let (attacker, defender) = (side.index(), side.other().index());
+            if teams[attacker].active().is_fainted() || teams[defender].active().is_fainted() {
+                continue;
+            }
+
+            let m = teams[attacker].active().combatant.moves[choices[attacker]].clone();
+            log.push(Event::MoveUsed {
+                side,
+                pokemon: teams[attacker].active().name.clone(),
+                move_name: m.name.clone(),
+            });
+
+            if !rng.chance(m.accuracy) {
+                log.push(Event::Missed {
+                    side,
+                    pokemon: teams[attacker].active().name.clone(),
+                });
+                continue;
+            }
+
+            let (damage, effectiveness) = damage(
+                teams[attacker].active(),
+                teams[defender].active(),
+                &m,
+                &mut rng,
+            );
+
+            let target = &mut teams[defender].fighters[teams[defender].active];
+            target.hp = target.hp.saturating_sub(damage);
+            log.push(Event::Damaged {
+                side: side.other(),
+                pokemon: target.name.clone(),
+                damage,
+                remaining_hp: target.hp,
+                effectiveness,
+            });
+
+            if target.is_fainted() {
+                log.push(Event::Fainted {
+                    side: side.other(),
+                    pokemon: target.name.clone(),
+                });
+            }
+        }
+
+        for side in [Side::First, Side::Second] {
+            let team = &mut teams[side.index()];
+            if !team.active().is_fainted() {
+                continue;
+            }
+
+            match team.next_available() {
+                Some(index) => {
+                    team.active = index;
+                    log.push(Event::SentOut {
+                        side,
+                        pokemon: team.active().name.clone(),
+                        hp: team.active().hp,
+                    });
+                }
+                None => return end(Some(side.other()), turn, log),
+            }
+        }
+    }
+
+    end(None, turn, log)
+}
+
+fn end(winner: Option<Side>, turns: u16, mut log: Vec<Event>) -> Outcome {
+    log.push(Event::BattleEnded { winner });
+    Outcome { winner, turns, log }
+}
+
+/// Higher priority moves first, then the faster Pokemon; speed ties are random.
+fn turn_order(teams: &[Team; 2], choices: [usize; 2], rng: &mut Rng) -> [Side; 2] {
+    let key = |side: Side| {
+        let fighter = teams[side.index()].active();
+        (
+            fighter.combatant.moves[choices[side.index()]].priority,
+            fighter.speed,
+        )
+    };
+
+    let (first, second) = (key(Side::First), key(Side::Second));
+    if first > second || (first == second && rng.chance(50)) {
+        [Side::First, Side::Second]
+    } else {
+        [Side::Second, Side::First]
+    }
+}
+
+fn damage(attacker: &Fighter, defender: &Fighter, m: &Move, rng: &mut Rng) -> (u16, f32) {
+    let modifier = attacker.modifier(m, defender);
+    let effectiveness = defender.combatant.pokemon.types.effectiveness_of(m.kind);
+    if effectiveness == 0.0 {
+        return (0, effectiveness);
+    }
+
+    let level = f32::from(attacker.combatant.level);
+    let base = ((2.0 * level / 5.0 + 2.0) * f32::from(m.power) * f32::from(attacker.attack)
+        / f32::from(defender.defense))
+        / 50.0
+        + 2.0;
+    let roll = rng.range(85, 101) as f32 / 100.0;
+
+    (((base * modifier * roll) as u16).max(1), effectiveness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+
+    fn pikachu(moves: Vec<Move>) -> Combatant {
+        Combatant::try_new(
+            Pokemon::new(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            ),
+            50,
+            moves,
+        )
+        .ok()
+        .unwrap()
+    }
+
+    fn charmander(moves: Vec<Move>) -> Combatant {
+        Combatant::try_new(
+            Pokemon::new(
+                PokemonNumber::charmander(),
+                PokemonForm::default(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+                PokemonStats::charmander(),
+            ),
+            50,
+            moves,
+        )
+        .ok()
+        .unwrap()
+    }
+
+    fn thunderbolt() -> Move {
+        Move::try_new(
+            "Thunderbolt".to_string(),
+            "Electric".to_string(),
+            90,
+            100,
+            0,
+        )
+        .ok()
+        .unwrap()
+    }
+
+    fn ember() -> Move {
+        Move::try_new("Ember".to_string(), "Fire".to_string(), 40, 100, 0)
+            .ok()
+            .unwrap()
+    }
+
+    fn first_attacker(log: &[Event]) -> Option<Side> {
+        log.iter().find_map(|e| match e {
+            Event::MoveUsed { side, .. } => Some(*side),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn it_should_reject_invalid_moves_and_combatants() {
+        assert!(Move::try_new("Splash".to_string(), "Water".to_string(), 0, 100, 0).is_err());
+        assert!(Move::try_new("Tackle".to_string(), "Sound".to_string(), 40, 100, 0).is_err());
+        assert!(Combatant::try_new(
+            Pokemon::new(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            ),
+            50,
+            vec![],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn it_should_be_deterministic_for_a_given_seed() {
+        let a = simulate(
+            vec![pikachu(vec![thunderbolt()])],
+            vec![charmander(vec![ember()])],
+            1,
+        );
+        let b = simulate(
+            vec![pikachu(vec![thunderbolt()])],
+            vec![charmander(vec![ember()])],
+            1,
+        );
+
+        assert_eq!(a.log, b.log);
+        assert_eq!(a.winner, b.winner);
+    }
+
+    #[test]
+    fn it_should_let_the_faster_pokemon_move_first() {
+        let outcome = simulate(
+            vec![charmander(vec![ember()])],
+            vec![pikachu(vec![thunderbolt()])],
+            3,
+        );
+
+        assert_eq!(first_attacker(&outcome.log), Some(Side::Second));
+    }
+
+    #[test]
+    fn it_should_let_priority_moves_go_before_faster_pokemon() {
+        let quick_attack =
+            Move::try_new("Quick Attack".to_string(), "Normal".to_string(), 40, 100, 1)
+                .ok()
+                .unwrap();
+        let outcome = simulate(
+            vec![charmander(vec![quick_attack])],
+            vec![pikachu(vec![thunderbolt()])],
+            3,
+        );
+
+        assert_eq!(first_attacker(&outcome.log), Some(Side::First));
+    }
+
+    #[test]
+    fn it_should_apply_type_effectiveness() {
+        let water_gun = Move::try_new("Water Gun".to_string(), "Water".to_string(), 40, 100, 0)
+            .ok()
+            .unwrap();
+        let outcome = simulate(
+            vec![pikachu(vec![water_gun])],
+            vec![charmander(vec![ember()])],
+            5,
+        );
+
+        let effectiveness = outcome.log.iter().find_map(|e| match e {
+            Event::Damaged {
+                side: Side::Second,
+                effectiveness,
+                ..
+            } => Some(*effectiveness),
+            _ => None,
+        });
+        assert_eq!(effectiveness, Some(2.0));
+    }
+
+    #[test]
+    fn it_should_switch_in_the_next_pokemon_after_a_faint_and_end_with_a_winner() {
+        let outcome = simulate(
+            vec![charmander(vec![ember()]), charmander(vec![ember()])],
+            vec![pikachu(vec![thunderbolt()])],
+            9,
+        );
+
+        let sent_out = outcome
+            .log
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e,
+                    Event::SentOut {
+                        side: Side::First,
+                        ..
+                    }
+                )
+            })
+            .count();
+        let fainted = outcome
+            .log
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e,
+                    Event::Fainted {
+                        side: Side::First,
+                        ..
+                    }
+                )
+            })
+            .count();
+
+        assert_eq!(outcome.winner, Some(Side::Second));
+        assert_eq!(sent_out, 2);
+        assert_eq!(fainted, 2);
+        assert_eq!(
+            outcome.log.last(),
+            Some(&Event::BattleEnded {
+                winner: Some(Side::Second)
+            })
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use crate::repositories::pokemon::Repository;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Whether a dependency answered, and how long it took.
+pub struct Component {
+    pub name: &'static str,
+    pub healthy: bool,
+    pub latency: Duration,
+}
+
+pub struct Response {
+    pub components: Vec<Component>,
+}
+
+impl Response {
+    /// Requests can be served once every dependency is healthy.
+    pub fn is_ready(&self) -> bool {
+        self.components.iter().all(|c| c.healthy)
+    }
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Response {
+    let started = Instant::now();
+    let healthy = repo.ping().is_ok();
+
+    Response {
+        components: vec![Component {
+            name: "repository",
+            healthy,
+            latency: started.elapsed(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_not_be_ready_when_the_repository_fails() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo);
+
+        assert!(!res.is_ready());
+        assert!(!res.components[0].healthy);
+    }
+
+    #[test]
+    fn it_should_be_ready_when_every_component_is_healthy() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo);
+
+        assert!(res.is_ready());
+        assert_eq!(res.components[0].name, "repository");
+    }
+}
@@ -0,0 +1,65 @@
+use crate::repositories::pokemon::{CountError, Repository};
+use std::sync::Arc;
+
+pub enum Error {
+    Unknown,
+}
+
+pub struct Response {
+    /// Every form outside the trash.
+    pub count: usize,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Response, Error> {
+    match repo.count() {
+        Ok(count) => Ok(Response { count }),
+        Err(CountError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_leave_the_trash_out_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo.delete(PokemonNumber::charmander(), PokemonForm::default(), 0)
+            .ok();
+
+        match execute(repo) {
+            Ok(res) => assert_eq!(res.count, 1),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -1,5 +1,9 @@
-use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
-use crate::repositories::pokemon::{Insert, InsertError, Repository};
+use crate::domain::entities::{
+    PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+};
+use crate::domain::events::{Event, EventBus};
+use crate::domain::generation::Generations;
+use crate::repositories::pokemon::{InsertError, Repository};
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -8,15 +12,20 @@ use super::entities::Pokemon;
 #[derive(Debug, Clone)]
 pub struct Request {
     pub number: u16,
+    /// Defaults to the default form of the species.
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Vec<u16>,
 }
 
 #[derive(Debug)]
 pub struct Response {
     pub number: u16,
+    pub form: String,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Vec<u16>,
 }
 
 pub enum Error {
@@ -25,25 +34,48 @@ pub enum Error {
     Unknown,
 }
 
-pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+/// Only the numbers introduced by one of the `generations` are accepted.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    generations: &Generations,
+    req: Request,
+) -> Result<Response, Error> {
+    if generations.of(req.number).is_none() {
+        return Err(Error::BadRequest);
+    }
+
     match (
         PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form),
         PokemonName::try_from(req.name),
         PokemonTypes::try_from(req.types),
+        PokemonStats::try_from(req.stats),
     ) {
-        (Ok(number), Ok(name), Ok(types)) => match repo.insert(number, name, types) {
-            Ok(Pokemon {
-                number,
-                name,
-                types,
-            }) => Ok(Response {
-                number: number.to_u16(),
-                name: name.to_string(),
-                types: types.to_vec_string(),
-            }),
-            Err(InsertError::Conflict) => Err(Error::Conflict),
-            Err(InsertError::Unknown) => Err(Error::Unknown),
-        },
+        (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => {
+            match repo.insert(number, form, name, types, stats) {
+                Ok(pokemon) => {
+                    events.publish(Event::PokemonCreated(pokemon.clone()));
+                    let Pokemon {
+                        number,
+                        form,
+                        name,
+                        types,
+                        stats,
+                        ..
+                    } = pokemon;
+                    Ok(Response {
+                        number: number.to_u16(),
+                        form: form.to_string(),
+                        name: name.to_string(),
+                        types: types.to_vec_string(),
+                        stats: stats.to_vec_u16(),
+                    })
+                }
+                Err(InsertError::Conflict) => Err(Error::Conflict),
+                Err(InsertError::Unknown) => Err(Error::Unknown),
+            }
+        }
         _ => Err(Error::BadRequest),
     }
 }
@@ -52,14 +84,23 @@ pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Erro
 mod tests {
 
     use super::*;
+    use crate::domain::events::RecordingSubscriber;
+    use crate::domain::generation::Generation;
     use crate::repositories::pokemon::InMemoryRepository;
 
     impl Request {
-        fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+        fn new(
+            number: PokemonNumber,
+            name: PokemonName,
+            types: PokemonTypes,
+            stats: PokemonStats,
+        ) -> Self {
             Self {
                 number: u16::from(number),
+                form: None,
                 name: name.to_string(),
                 types: types.to_vec_string(),
+                stats: stats.to_vec_u16(),
             }
         }
     }
@@ -71,9 +112,62 @@ mod tests {
             PokemonNumber::pikachu(),
             PokemonName::bad(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        );
+
+        let res = execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        );
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_no_generation_introduced_the_number() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let kanto = Generation {
+            number: 1,
+            region: "Kanto".to_string(),
+            first: 1,
+            last: 151,
+        };
+        let generations = Generations::try_new(vec![kanto], vec![]).ok().unwrap();
+        let mut req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        );
+        req.number = 152;
+
+        match execute(repo, EventBus::without_subscribers(), &generations, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_stats_are_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::bad(),
         );
 
-        let res = execute(repo, req);
+        let res = execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        );
 
         match res {
             Err(Error::BadRequest) => {}
@@ -86,17 +180,26 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
-        );
+            PokemonStats::pikachu(),
+        )
+        .ok();
 
         let req = Request::new(
             PokemonNumber::pikachu(),
             PokemonName::charmander(),
             PokemonTypes::charmander(),
+            PokemonStats::charmander(),
         );
 
-        let res = execute(repo, req);
+        let res = execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        );
 
         match res {
             Err(Error::Conflict) => {}
@@ -104,6 +207,149 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_form_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            form: Some(PokemonForm::bad().to_string()),
+            ..Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            )
+        };
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        ) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_name_is_not_allowed() {
+        for name in ["   ", "Pika<chu>", "Pika\u{7}chu", &"a".repeat(41)] {
+            let repo = Arc::new(InMemoryRepository::new());
+            let req = Request {
+                name: name.to_string(),
+                ..Request::new(
+                    PokemonNumber::pikachu(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                    PokemonStats::pikachu(),
+                )
+            };
+
+            match execute(
+                repo,
+                EventBus::without_subscribers(),
+                &Generations::default(),
+                req,
+            ) {
+                Err(Error::BadRequest) => {}
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_trim_and_normalize_the_name() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            name: "  Nidoran\u{2640} Fl\u{65}\u{301}b\u{e9}b\u{e9}  ".to_string(),
+            ..Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            )
+        };
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        ) {
+            Ok(res) => assert_eq!(res.name, "Nidoran♀ Fl\u{e9}b\u{e9}b\u{e9}"),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_name_is_taken_in_another_case() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+
+        let req = Request {
+            name: "pIKACHU".to_string(),
+            ..Request::new(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+                PokemonStats::charmander(),
+            )
+        };
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        ) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_store_another_form_of_an_existing_species() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+
+        let req = Request {
+            form: Some("Alola".to_string()),
+            ..Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+                PokemonStats::charmander(),
+            )
+        };
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        ) {
+            Ok(res) => {
+                assert_eq!(res.form, PokemonForm::alola().to_string());
+                assert_eq!(res.types, PokemonTypes::charmander().to_vec_string());
+            }
+            _ => unreachable!(),
+        };
+    }
+
     #[test]
     fn it_should_return_an_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
@@ -111,9 +357,15 @@ mod tests {
             PokemonNumber::pikachu(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         );
 
-        let res = execute(repo, req);
+        let res = execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        );
 
         match res {
             Err(Error::Unknown) => {}
@@ -128,22 +380,56 @@ mod tests {
             PokemonNumber::pikachu(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         );
 
-        let res = execute(repo, req);
+        let res = execute(
+            repo,
+            EventBus::without_subscribers(),
+            &Generations::default(),
+            req,
+        );
 
         match res {
             Ok(Response {
                 number,
+                form,
                 name,
                 types,
+                stats,
             }) => {
                 assert_eq!(number, PokemonNumber::pikachu().to_u16());
+                assert_eq!(form, PokemonForm::default().to_string());
                 assert_eq!(name, PokemonName::pikachu().to_string());
                 assert_eq!(types, PokemonTypes::pikachu().to_vec_string());
+                assert_eq!(stats, PokemonStats::pikachu().to_vec_u16());
             }
             // Ok(res_number) => assert_eq!(res_number, number),
             _ => unreachable!(),
         };
     }
+
+    #[test]
+    fn it_should_publish_a_created_event_only_when_the_pokemon_is_stored() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let (events, subscriber) = RecordingSubscriber::bus();
+        let req = || {
+            Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            )
+        };
+
+        execute(repo.clone(), events.clone(), &Generations::default(), req()).ok();
+        execute(repo, events, &Generations::default(), req()).ok();
+
+        match subscriber.events().as_slice() {
+            [Event::PokemonCreated(pokemon)] => {
+                assert_eq!(pokemon.number, PokemonNumber::pikachu())
+            }
+            _ => unreachable!(),
+        };
+    }
 }
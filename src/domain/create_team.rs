@@ -0,0 +1,232 @@
+use crate::domain::entities::{PokemonForm, PokemonNumber};
+use crate::domain::team::{Team, TeamMember, TeamName, TeamRules};
+use crate::repositories::pokemon::{FetchOneError, Repository};
+use crate::repositories::team::{InsertError, TeamRepository};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub name: String,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub number: u16,
+    pub nickname: Option<String>,
+    pub level: u8,
+    pub moves: Vec<String>,
+    pub held_item: Option<String>,
+}
+
+impl From<TeamMember> for Member {
+    fn from(member: TeamMember) -> Self {
+        Self {
+            number: member.number.to_u16(),
+            nickname: member.nickname,
+            level: member.level,
+            moves: member.moves,
+            held_item: member.held_item,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    pub members: Vec<Member>,
+}
+
+impl From<Team> for Response {
+    fn from(team: Team) -> Self {
+        Self {
+            id: team.id.to_u32(),
+            name: String::from(team.name),
+            members: team.members.into_iter().map(Member::from).collect(),
+        }
+    }
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    RuleViolation(String),
+    Conflict,
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn TeamRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    rules: &TeamRules,
+    req: Request,
+) -> Result<Response, Error> {
+    let name = match TeamName::try_from(req.name) {
+        Ok(name) => name,
+        _ => return Err(Error::BadRequest),
+    };
+    let members = validate_members(pokemon_repo, rules, req.members)?;
+
+    match repo.insert(name, members) {
+        Ok(team) => Ok(Response::from(team)),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+/// Parses the members, checks each one references a stored Pokemon and
+/// applies the configured team rules.
+pub fn validate_members(
+    pokemon_repo: Arc<dyn Repository>,
+    rules: &TeamRules,
+    members: Vec<Member>,
+) -> Result<Vec<TeamMember>, Error> {
+    let mut team_members = vec![];
+    for member in members {
+        let number = match PokemonNumber::try_from(member.number) {
+            Ok(number) => number,
+            _ => return Err(Error::BadRequest),
+        };
+
+        match pokemon_repo.fetch_one(number.clone(), PokemonForm::default()) {
+            Ok(_) => {}
+            Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+            Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+        }
+
+        match TeamMember::try_new(
+            number,
+            member.nickname,
+            member.level,
+            member.moves,
+            member.held_item,
+        ) {
+            Ok(member) => team_members.push(member),
+            _ => return Err(Error::BadRequest),
+        }
+    }
+
+    match rules.validate(&team_members) {
+        Ok(()) => Ok(team_members),
+        Err(violation) => Err(Error::RuleViolation(violation)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonForm, PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+    use crate::repositories::team::InMemoryTeamRepository;
+
+    fn pokemon_repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    fn request(members: Vec<TeamMember>) -> Request {
+        Request {
+            name: String::from(TeamName::kanto()),
+            members: members.into_iter().map(Member::from).collect(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        let mut req = request(vec![TeamMember::pikachu()]);
+        req.members[0].level = 0;
+
+        let res = execute(repo, pokemon_repo(), &TeamRules::default(), req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_a_member_is_not_stored() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        let req = request(vec![TeamMember::charmander()]);
+
+        let res = execute(repo, pokemon_repo(), &TeamRules::default(), req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_rule_violation_error_when_the_rules_are_not_met() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        let req = request(vec![TeamMember::pikachu(), TeamMember::pikachu()]);
+        let rules = TeamRules {
+            species_clause: true,
+            ..TeamRules::default()
+        };
+
+        let res = execute(repo, pokemon_repo(), &rules, req);
+
+        match res {
+            Err(Error::RuleViolation(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_team_name_already_exists() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        repo.insert(TeamName::kanto(), vec![TeamMember::pikachu()])
+            .ok();
+        let req = request(vec![TeamMember::pikachu()]);
+
+        let res = execute(repo, pokemon_repo(), &TeamRules::default(), req);
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryTeamRepository::new().with_error());
+        let req = request(vec![TeamMember::pikachu()]);
+
+        let res = execute(repo, pokemon_repo(), &TeamRules::default(), req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_team_otherwise() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        let req = request(vec![TeamMember::pikachu()]);
+
+        let res = execute(repo, pokemon_repo(), &TeamRules::default(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.id, 1);
+                assert_eq!(res.name, String::from(TeamName::kanto()));
+                assert_eq!(res.members, vec![Member::from(TeamMember::pikachu())]);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,124 @@
+use crate::domain::webhook::{Webhook, WebhookSecret, WebhookUrl};
+use crate::repositories::webhook::{InsertError, WebhookRepository};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub struct Request {
+    pub url: String,
+    pub secret: String,
+}
+
+/// The secret is never given back.
+pub struct Response {
+    pub id: u32,
+    pub url: String,
+}
+
+impl From<Webhook> for Response {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id.to_u32(),
+            url: String::from(webhook.url),
+        }
+    }
+}
+
+pub enum Error {
+    BadRequest,
+    Conflict,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn WebhookRepository>, req: Request) -> Result<Response, Error> {
+    match (
+        WebhookUrl::try_from(req.url),
+        WebhookSecret::try_from(req.secret),
+    ) {
+        (Ok(url), Ok(secret)) => match repo.insert(url, secret) {
+            Ok(webhook) => Ok(Response::from(webhook)),
+            Err(InsertError::Conflict) => Err(Error::Conflict),
+            Err(InsertError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::webhook::InMemoryWebhookRepository;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_url_is_invalid() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let req = Request {
+            url: "ftp://localhost".to_string(),
+            secret: String::from(WebhookSecret::test()),
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_secret_is_empty() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let req = Request {
+            url: String::from(WebhookUrl::local()),
+            secret: " ".to_string(),
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_url_is_already_subscribed() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        repo.insert(WebhookUrl::local(), WebhookSecret::test()).ok();
+        let req = Request {
+            url: String::from(WebhookUrl::local()),
+            secret: String::from(WebhookSecret::test()),
+        };
+
+        match execute(repo, req) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryWebhookRepository::new().with_error());
+        let req = Request {
+            url: String::from(WebhookUrl::local()),
+            secret: String::from(WebhookSecret::test()),
+        };
+
+        match execute(repo, req) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_webhook_otherwise() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let req = Request {
+            url: String::from(WebhookUrl::local()),
+            secret: String::from(WebhookSecret::test()),
+        };
+
+        match execute(repo, req) {
+            Ok(res) => {
+                assert_eq!(res.id, 1);
+                assert_eq!(res.url, String::from(WebhookUrl::local()));
+            }
+            _ => unreachable!(),
+        };
+    }
+}
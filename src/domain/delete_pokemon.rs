@@ -1,10 +1,14 @@
-use crate::domain::entities::PokemonNumber;
+use crate::domain::clock::now;
+use crate::domain::entities::{PokemonForm, PokemonNumber};
+use crate::domain::events::{Event, EventBus};
 use crate::repositories::pokemon::{DeleteError, Repository};
 use std::convert::TryFrom;
 use std::sync::Arc;
 
 pub struct Request {
     pub number: u16,
+    /// Defaults to the default form of the species.
+    pub form: Option<String>,
 }
 
 pub enum Error {
@@ -13,10 +17,20 @@ pub enum Error {
     NotFound,
 }
 
-pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
-    match PokemonNumber::try_from(req.number) {
-        Ok(number) => match repo.delete(number) {
-            Ok(()) => Ok(()),
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    req: Request,
+) -> Result<(), Error> {
+    match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form),
+    ) {
+        (Ok(number), Ok(form)) => match repo.delete(number.clone(), form.clone(), now()) {
+            Ok(()) => {
+                events.publish(Event::PokemonDeleted { number, form });
+                Ok(())
+            }
             Err(DeleteError::NotFound) => Err(Error::NotFound),
             Err(DeleteError::Unknown) => Err(Error::Unknown),
         },
@@ -27,15 +41,16 @@ pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
-    use crate::repositories::pokemon::InMemoryRepository;
+    use crate::domain::entities::{PokemonName, PokemonStats, PokemonTypes};
+    use crate::domain::events::RecordingSubscriber;
+    use crate::repositories::pokemon::{FetchOneError, InMemoryRepository};
 
     #[test]
     fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
         let req = Request::new(PokemonNumber::pikachu());
 
-        let res = execute(repo, req);
+        let res = execute(repo, EventBus::without_subscribers(), req);
 
         match res {
             Err(Error::Unknown) => {}
@@ -48,7 +63,7 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         let req = Request::new(PokemonNumber::bad());
 
-        let res = execute(repo, req);
+        let res = execute(repo, EventBus::without_subscribers(), req);
 
         match res {
             Err(Error::BadRequest) => {}
@@ -61,7 +76,7 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         let req = Request::new(PokemonNumber::pikachu());
 
-        let res = execute(repo, req);
+        let res = execute(repo, EventBus::without_subscribers(), req);
 
         match res {
             Err(Error::NotFound) => {}
@@ -69,18 +84,59 @@ mod tests {
         };
     }
 
+    #[test]
+    fn it_should_move_the_pokemon_to_the_trash() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+
+        match execute(
+            repo.clone(),
+            EventBus::without_subscribers(),
+            Request::new(PokemonNumber::pikachu()),
+        ) {
+            Ok(()) => {}
+            _ => unreachable!(),
+        };
+
+        match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+            Err(FetchOneError::NotFound) => {}
+            _ => unreachable!(),
+        };
+        match repo.fetch_trash() {
+            Ok(trash) => assert_eq!(trash[0].pokemon.number, PokemonNumber::pikachu()),
+            _ => unreachable!(),
+        };
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            Request::new(PokemonNumber::pikachu()),
+        ) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
     #[test]
     fn it_should_return_ok_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         )
         .ok();
         let req = Request::new(PokemonNumber::pikachu());
 
-        let res = execute(repo, req);
+        let res = execute(repo, EventBus::without_subscribers(), req);
 
         match res {
             Ok(()) => {}
@@ -88,10 +144,35 @@ mod tests {
         };
     }
 
+    #[test]
+    fn it_should_publish_a_deleted_event() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        let (events, subscriber) = RecordingSubscriber::bus();
+
+        execute(repo, events, Request::new(PokemonNumber::pikachu())).ok();
+
+        match subscriber.events().as_slice() {
+            [Event::PokemonDeleted { number, form }] => {
+                assert_eq!(*number, PokemonNumber::pikachu());
+                assert_eq!(*form, PokemonForm::default());
+            }
+            _ => unreachable!(),
+        };
+    }
+
     impl Request {
         fn new(number: PokemonNumber) -> Self {
             Self {
                 number: u16::from(number),
+                form: None,
             }
         }
     }
@@ -0,0 +1,59 @@
+use crate::domain::webhook::WebhookId;
+use crate::repositories::webhook::{DeleteError, WebhookRepository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub id: u32,
+}
+
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn WebhookRepository>, req: Request) -> Result<(), Error> {
+    match repo.delete(WebhookId::from(req.id)) {
+        Ok(()) => Ok(()),
+        Err(DeleteError::NotFound) => Err(Error::NotFound),
+        Err(DeleteError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::webhook::{WebhookSecret, WebhookUrl};
+    use crate::repositories::webhook::InMemoryWebhookRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryWebhookRepository::new().with_error());
+
+        match execute(repo, Request { id: 1 }) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_webhook() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+
+        match execute(repo, Request { id: 1 }) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_ok_otherwise() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        repo.insert(WebhookUrl::local(), WebhookSecret::test()).ok();
+
+        match execute(repo.clone(), Request { id: 1 }) {
+            Ok(()) => {}
+            _ => unreachable!(),
+        };
+        assert!(repo.fetch_all().ok().unwrap().is_empty());
+    }
+}
@@ -1,11 +1,13 @@
 use std::cmp::PartialEq;
 use std::convert::TryFrom;
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
 
 #[derive(PartialEq, Clone, Debug, Eq, PartialOrd, Ord)]
 pub struct PokemonNumber(u16);
 
 impl PokemonNumber {
-    pub fn to_u16(self) -> u16 {
+    pub fn to_u16(&self) -> u16 {
         self.0
     }
 }
@@ -13,8 +15,10 @@ impl PokemonNumber {
 impl TryFrom<u16> for PokemonNumber {
     type Error = ();
 
+    /// The numbers a Pokemon can be created with depend on the generations
+    /// known, which `create_pokemon` checks.
     fn try_from(n: u16) -> Result<Self, Self::Error> {
-        if n > 0 && n < 899 {
+        if n > 0 {
             Ok(Self(n))
         } else {
             Err(())
@@ -31,17 +35,34 @@ impl From<PokemonNumber> for u16 {
 #[derive(Clone, Debug)]
 pub struct PokemonName(String);
 
+impl fmt::Display for PokemonName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+const MAX_NAME_LENGTH: usize = 40;
+
 impl PokemonName {
-    pub fn to_string(self) -> String {
-        self.0
+    /// Names are unique regardless of case: two names with the same key clash.
+    pub fn key(&self) -> String {
+        self.0.to_lowercase()
     }
 }
 
+/// Trims and NFC-normalizes the name, which may then only hold letters, digits,
+/// spaces, hyphens, apostrophes, periods and gender symbols.
 impl TryFrom<String> for PokemonName {
     type Error = ();
 
     fn try_from(n: String) -> Result<Self, Self::Error> {
-        if n.is_empty() {
+        let n = n.trim().nfc().collect::<String>();
+        if n.is_empty()
+            || n.chars().count() > MAX_NAME_LENGTH
+            || !n.chars().all(|c| {
+                c.is_alphanumeric() || matches!(c, ' ' | '-' | '\'' | '’' | '.' | '♀' | '♂')
+            })
+        {
             Err(())
         } else {
             Ok(Self(n))
@@ -49,17 +70,131 @@ impl TryFrom<String> for PokemonName {
     }
 }
 
+/// Primary language subtag of a BCP 47 tag, e.g. `fr` for `fr-CA`.
+#[derive(PartialEq, Clone, Debug, Eq, PartialOrd, Ord)]
+pub struct Language(String);
+
+const ENGLISH: &str = "en";
+
+impl Language {
+    /// Names fall back to English when no translation matches.
+    pub fn english() -> Self {
+        Self(ENGLISH.to_string())
+    }
+
+    pub fn is_english(&self) -> bool {
+        self.0 == ENGLISH
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for Language {
+    type Error = ();
+
+    fn try_from(l: String) -> Result<Self, Self::Error> {
+        let l = l
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if (2..=3).contains(&l.len()) && l.chars().all(|c| c.is_ascii_lowercase()) {
+            Ok(Self(l))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl From<Language> for String {
+    fn from(l: Language) -> Self {
+        l.0
+    }
+}
+
+/// Identifies one variant of a species, such as `alola` for Alolan Vulpix.
+/// Every species has a `default` form.
+#[derive(PartialEq, Clone, Debug, Eq, PartialOrd, Ord)]
+pub struct PokemonForm(String);
+
+const DEFAULT_FORM: &str = "default";
+const MAX_FORM_LENGTH: usize = 32;
+
+impl PokemonForm {
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_FORM
+    }
+}
+
+impl Default for PokemonForm {
+    fn default() -> Self {
+        Self(DEFAULT_FORM.to_string())
+    }
+}
+
+impl fmt::Display for PokemonForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for PokemonForm {
+    type Error = ();
+
+    fn try_from(f: String) -> Result<Self, Self::Error> {
+        let f = f.trim().to_lowercase();
+        if f.is_empty()
+            || f.len() > MAX_FORM_LENGTH
+            || !f.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            Err(())
+        } else {
+            Ok(Self(f))
+        }
+    }
+}
+
+/// A missing form means the default one.
+impl TryFrom<Option<String>> for PokemonForm {
+    type Error = ();
+
+    fn try_from(f: Option<String>) -> Result<Self, Self::Error> {
+        match f {
+            Some(f) => Self::try_from(f),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl From<PokemonForm> for String {
+    fn from(f: PokemonForm) -> Self {
+        f.0
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PokemonTypes(Vec<PokemonType>);
 
 impl PokemonTypes {
-    pub fn to_vec_string(self) -> Vec<String> {
-        let a: Vec<String> = self
-            .0
-            .into_iter()
-            .map(|t| -> String { String::from(t) })
-            .collect();
-        a
+    pub fn to_vec_string(&self) -> Vec<String> {
+        self.0.iter().map(|t| String::from(*t)).collect()
+    }
+
+    pub fn contains(&self, t: PokemonType) -> bool {
+        self.0.contains(&t)
+    }
+
+    /// Damage multiplier of an attack of type `attacker` against these types.
+    pub fn effectiveness_of(&self, attacker: PokemonType) -> f32 {
+        self.0
+            .iter()
+            .map(|defender| attacker.effectiveness_against(*defender))
+            .product()
     }
 }
 
@@ -84,17 +219,127 @@ impl TryFrom<Vec<String>> for PokemonTypes {
     }
 }
 
-#[derive(Clone, Debug)]
-enum PokemonType {
-    Electric,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PokemonType {
+    Normal,
     Fire,
+    Water,
+    Electric,
+    Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
 }
 
 impl PokemonType {
+    pub const ALL: [PokemonType; 18] = [
+        PokemonType::Normal,
+        PokemonType::Fire,
+        PokemonType::Water,
+        PokemonType::Electric,
+        PokemonType::Grass,
+        PokemonType::Ice,
+        PokemonType::Fighting,
+        PokemonType::Poison,
+        PokemonType::Ground,
+        PokemonType::Flying,
+        PokemonType::Psychic,
+        PokemonType::Bug,
+        PokemonType::Rock,
+        PokemonType::Ghost,
+        PokemonType::Dragon,
+        PokemonType::Dark,
+        PokemonType::Steel,
+        PokemonType::Fairy,
+    ];
+
     pub fn get_value(self) -> String {
         match self {
-            PokemonType::Electric => "Electric".to_string(),
-            PokemonType::Fire => "Fire".to_string(),
+            PokemonType::Normal => "Normal",
+            PokemonType::Fire => "Fire",
+            PokemonType::Water => "Water",
+            PokemonType::Electric => "Electric",
+            PokemonType::Grass => "Grass",
+            PokemonType::Ice => "Ice",
+            PokemonType::Fighting => "Fighting",
+            PokemonType::Poison => "Poison",
+            PokemonType::Ground => "Ground",
+            PokemonType::Flying => "Flying",
+            PokemonType::Psychic => "Psychic",
+            PokemonType::Bug => "Bug",
+            PokemonType::Rock => "Rock",
+            PokemonType::Ghost => "Ghost",
+            PokemonType::Dragon => "Dragon",
+            PokemonType::Dark => "Dark",
+            PokemonType::Steel => "Steel",
+            PokemonType::Fairy => "Fairy",
+        }
+        .to_string()
+    }
+
+    /// Damage multiplier of an attack of this type against a single defending type.
+    pub fn effectiveness_against(self, defender: PokemonType) -> f32 {
+        use PokemonType::*;
+
+        let (super_effective, not_very_effective, no_effect): (&[_], &[_], &[_]) = match self {
+            Normal => (&[], &[Rock, Steel], &[Ghost]),
+            Fire => (&[Grass, Ice, Bug, Steel], &[Fire, Water, Rock, Dragon], &[]),
+            Water => (&[Fire, Ground, Rock], &[Water, Grass, Dragon], &[]),
+            Electric => (&[Water, Flying], &[Electric, Grass, Dragon], &[Ground]),
+            Grass => (
+                &[Water, Ground, Rock],
+                &[Fire, Grass, Poison, Flying, Bug, Dragon, Steel],
+                &[],
+            ),
+            Ice => (
+                &[Grass, Ground, Flying, Dragon],
+                &[Fire, Water, Ice, Steel],
+                &[],
+            ),
+            Fighting => (
+                &[Normal, Ice, Rock, Dark, Steel],
+                &[Poison, Flying, Psychic, Bug, Fairy],
+                &[Ghost],
+            ),
+            Poison => (&[Grass, Fairy], &[Poison, Ground, Rock, Ghost], &[Steel]),
+            Ground => (
+                &[Fire, Electric, Poison, Rock, Steel],
+                &[Grass, Bug],
+                &[Flying],
+            ),
+            Flying => (&[Grass, Fighting, Bug], &[Electric, Rock, Steel], &[]),
+            Psychic => (&[Fighting, Poison], &[Psychic, Steel], &[Dark]),
+            Bug => (
+                &[Grass, Psychic, Dark],
+                &[Fire, Fighting, Poison, Flying, Ghost, Steel, Fairy],
+                &[],
+            ),
+            Rock => (&[Fire, Ice, Flying, Bug], &[Fighting, Ground, Steel], &[]),
+            Ghost => (&[Psychic, Ghost], &[Dark], &[Normal]),
+            Dragon => (&[Dragon], &[Steel], &[Fairy]),
+            Dark => (&[Psychic, Ghost], &[Fighting, Dark, Fairy], &[]),
+            Steel => (&[Ice, Rock, Fairy], &[Fire, Water, Electric, Steel], &[]),
+            Fairy => (&[Fighting, Dragon, Dark], &[Fire, Poison, Steel], &[]),
+        };
+
+        if no_effect.contains(&defender) {
+            0.0
+        } else if super_effective.contains(&defender) {
+            2.0
+        } else if not_very_effective.contains(&defender) {
+            0.5
+        } else {
+            1.0
         }
     }
 }
@@ -103,38 +348,146 @@ impl TryFrom<String> for PokemonType {
     type Error = ();
 
     fn try_from(t: String) -> Result<Self, Self::Error> {
-        match t.as_str() {
-            "Electric" => Ok(Self::Electric),
-            "Fire" => Ok(Self::Fire),
-            _ => Err(()),
+        match PokemonType::ALL.iter().find(|pt| pt.get_value() == t) {
+            Some(pt) => Ok(*pt),
+            None => Err(()),
         }
     }
 }
 
 impl From<PokemonType> for String {
     fn from(t: PokemonType) -> Self {
-        String::from(match t {
-            PokemonType::Electric => PokemonType::Electric.get_value(),
-            PokemonType::Fire => PokemonType::Fire.get_value(),
-        })
+        t.get_value()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PokemonStats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    speed: u16,
+}
+
+impl PokemonStats {
+    pub fn hp(&self) -> u16 {
+        self.hp
+    }
+
+    pub fn attack(&self) -> u16 {
+        self.attack
+    }
+
+    pub fn defense(&self) -> u16 {
+        self.defense
+    }
+
+    pub fn speed(&self) -> u16 {
+        self.speed
+    }
+
+    pub fn to_vec_u16(&self) -> Vec<u16> {
+        vec![self.hp, self.attack, self.defense, self.speed]
+    }
+}
+
+/// Given to a Pokemon created without stats, halfway through their range.
+impl Default for PokemonStats {
+    fn default() -> Self {
+        Self {
+            hp: 50,
+            attack: 50,
+            defense: 50,
+            speed: 50,
+        }
+    }
+}
+
+impl TryFrom<Vec<u16>> for PokemonStats {
+    type Error = ();
+
+    /// Expects the base stats in the order hp, attack, defense, speed.
+    fn try_from(stats: Vec<u16>) -> Result<Self, Self::Error> {
+        match stats.as_slice() {
+            [hp, attack, defense, speed] if stats.iter().all(|stat| *stat > 0 && *stat < 256) => {
+                Ok(Self {
+                    hp: *hp,
+                    attack: *attack,
+                    defense: *defense,
+                    speed: *speed,
+                })
+            }
+            _ => Err(()),
+        }
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct Pokemon {
     pub number: PokemonNumber,
+    pub form: PokemonForm,
+    /// English name.
     pub name: PokemonName,
     pub types: PokemonTypes,
+    pub stats: PokemonStats,
+    /// Names in languages other than English.
+    pub translations: Vec<(Language, PokemonName)>,
 }
 
 impl Pokemon {
-    pub fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+    pub fn new(
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Self {
         Self {
             number,
+            form,
             name,
             types,
+            stats,
+            translations: vec![],
+        }
+    }
+
+    pub fn with_translations(self, translations: Vec<(Language, PokemonName)>) -> Self {
+        Self {
+            translations,
+            ..self
         }
     }
+
+    /// Name in the first of `languages` with a translation, English otherwise.
+    pub fn localized_name(&self, languages: &[Language]) -> &PokemonName {
+        languages
+            .iter()
+            .take_while(|l| !l.is_english())
+            .find_map(|l| {
+                self.translations
+                    .iter()
+                    .find(|(language, _)| language == l)
+                    .map(|(_, name)| name)
+            })
+            .unwrap_or(&self.name)
+    }
+
+    /// Whether any of its names contains `query`, ignoring case.
+    pub fn name_contains(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        std::iter::once(&self.name)
+            .chain(self.translations.iter().map(|(_, name)| name))
+            .any(|name| name.to_string().to_lowercase().contains(&query))
+    }
+}
+
+/// A Pokemon moved to the trash, kept until it is restored or purged.
+#[derive(Clone, Debug)]
+pub struct DeletedPokemon {
+    pub pokemon: Pokemon,
+    /// Seconds since the Unix epoch.
+    pub deleted_at: u64,
 }
 
 #[cfg(test)]
@@ -165,6 +518,36 @@ impl PokemonName {
     pub fn bad() -> Self {
         Self("".to_string())
     }
+
+    pub fn charmander_in(language: &Language) -> Self {
+        match language.0.as_str() {
+            "fr" => Self("Salamèche".to_string()),
+            "ja" => Self("ヒトカゲ".to_string()),
+            _ => Self::charmander(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Language {
+    pub fn french() -> Self {
+        Self("fr".to_string())
+    }
+
+    pub fn japanese() -> Self {
+        Self("ja".to_string())
+    }
+}
+
+#[cfg(test)]
+impl PokemonForm {
+    pub fn alola() -> Self {
+        Self("alola".to_string())
+    }
+
+    pub fn bad() -> Self {
+        Self("".to_string())
+    }
 }
 
 #[cfg(test)]
@@ -177,3 +560,33 @@ impl PokemonTypes {
         Self(vec![PokemonType::Fire])
     }
 }
+
+#[cfg(test)]
+impl PokemonStats {
+    pub fn pikachu() -> Self {
+        Self {
+            hp: 35,
+            attack: 55,
+            defense: 40,
+            speed: 90,
+        }
+    }
+
+    pub fn charmander() -> Self {
+        Self {
+            hp: 39,
+            attack: 52,
+            defense: 43,
+            speed: 65,
+        }
+    }
+
+    pub fn bad() -> Self {
+        Self {
+            hp: 0,
+            attack: 0,
+            defense: 0,
+            speed: 0,
+        }
+    }
+}
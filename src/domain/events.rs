@@ -0,0 +1,247 @@
+use crate::domain::entities::{Pokemon, PokemonForm, PokemonNumber};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum Event {
+    /// Also published when a Pokemon is restored from the trash, since it
+    /// shows up again.
+    PokemonCreated(Pokemon),
+    PokemonUpdated(Pokemon),
+    PokemonDeleted {
+        number: PokemonNumber,
+        form: PokemonForm,
+    },
+}
+
+/// Side effect run for every event published by the use cases.
+pub trait Subscriber: Send + Sync {
+    fn handle(&self, event: &Event);
+
+    /// Waits until `deadline` for the work started in the background for the
+    /// events handled, and tells whether it all finished.
+    fn flush(&self, _deadline: Instant) -> bool {
+        true
+    }
+}
+
+enum Message {
+    Event(Event),
+    /// Answered once every event published before it is delivered.
+    Flush(Sender<()>),
+}
+
+/// Delivers the events to the subscribers registered at startup, either
+/// synchronously, before the use case returns, or from a queue drained by a
+/// background thread, in publication order.
+pub struct EventBus {
+    subscribers: Arc<Vec<Arc<dyn Subscriber>>>,
+    queue: Option<Sender<Message>>,
+}
+
+impl EventBus {
+    pub fn synchronous(subscribers: Vec<Arc<dyn Subscriber>>) -> Self {
+        Self {
+            subscribers: Arc::new(subscribers),
+            queue: None,
+        }
+    }
+
+    /// `failed` is told of every event a subscriber panicked on, the next
+    /// subscribers and events being delivered all the same.
+    pub fn queued(
+        subscribers: Vec<Arc<dyn Subscriber>>,
+        failed: impl Fn(&Event) + Send + 'static,
+    ) -> Self {
+        let subscribers = Arc::new(subscribers);
+        let (sender, receiver) = mpsc::channel::<Message>();
+
+        let worker_subscribers = subscribers.clone();
+        thread::spawn(move || {
+            for message in receiver {
+                match message {
+                    Message::Event(event) => worker_subscribers.iter().for_each(|s| {
+                        if panic::catch_unwind(AssertUnwindSafe(|| s.handle(&event))).is_err() {
+                            failed(&event);
+                        }
+                    }),
+                    Message::Flush(done) => {
+                        done.send(()).ok();
+                    }
+                }
+            }
+        });
+
+        Self {
+            subscribers,
+            queue: Some(sender),
+        }
+    }
+
+    pub fn publish(&self, event: Event) {
+        match &self.queue {
+            Some(queue) => {
+                queue.send(Message::Event(event)).ok();
+            }
+            None => self.subscribers.iter().for_each(|s| s.handle(&event)),
+        }
+    }
+
+    /// Waits at most `timeout` for the events already published to be
+    /// delivered, and for the subscribers to finish what they do with them in
+    /// the background, and tells whether they all were.
+    pub fn flush(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+
+        if let Some(queue) = &self.queue {
+            let (done, delivered) = mpsc::channel();
+            if queue.send(Message::Flush(done)).is_err() {
+                return false;
+            }
+            if delivered.recv_timeout(timeout).is_err() {
+                return false;
+            }
+        }
+
+        // Every subscriber is flushed, even after one failed.
+        let flushed = self
+            .subscribers
+            .iter()
+            .map(|s| s.flush(deadline))
+            .collect::<Vec<bool>>();
+        flushed.into_iter().all(|f| f)
+    }
+}
+
+#[cfg(test)]
+impl EventBus {
+    pub fn without_subscribers() -> Arc<Self> {
+        Arc::new(Self::synchronous(vec![]))
+    }
+}
+
+/// Keeps every event it receives, for the tests to inspect.
+#[cfg(test)]
+#[derive(Default)]
+pub struct RecordingSubscriber {
+    events: std::sync::Mutex<Vec<Event>>,
+}
+
+#[cfg(test)]
+impl RecordingSubscriber {
+    pub fn events(&self) -> Vec<Event> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// A synchronous bus delivering to a new recording subscriber.
+    pub fn bus() -> (Arc<EventBus>, Arc<RecordingSubscriber>) {
+        let subscriber = Arc::new(RecordingSubscriber::default());
+        let bus = EventBus::synchronous(vec![subscriber.clone()]);
+        (Arc::new(bus), subscriber)
+    }
+}
+
+#[cfg(test)]
+impl Subscriber for RecordingSubscriber {
+    fn handle(&self, event: &Event) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn deleted(number: PokemonNumber) -> Event {
+        Event::PokemonDeleted {
+            number,
+            form: PokemonForm::default(),
+        }
+    }
+
+    #[test]
+    fn it_should_deliver_to_every_subscriber_before_returning_when_synchronous() {
+        let (first, second) = (
+            Arc::new(RecordingSubscriber::default()),
+            Arc::new(RecordingSubscriber::default()),
+        );
+        let bus = EventBus::synchronous(vec![first.clone(), second.clone()]);
+
+        bus.publish(deleted(PokemonNumber::pikachu()));
+
+        assert_eq!(first.events().len(), 1);
+        assert_eq!(second.events().len(), 1);
+    }
+
+    #[test]
+    fn it_should_deliver_in_publication_order_when_queued() {
+        let subscriber = Arc::new(RecordingSubscriber::default());
+        let bus = EventBus::queued(vec![subscriber.clone()], |_| {});
+
+        bus.publish(deleted(PokemonNumber::pikachu()));
+        bus.publish(deleted(PokemonNumber::charmander()));
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while subscriber.events().len() < 2 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let numbers = subscriber
+            .events()
+            .into_iter()
+            .map(|e| match e {
+                Event::PokemonDeleted { number, .. } => number,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<PokemonNumber>>();
+        assert_eq!(
+            numbers,
+            vec![PokemonNumber::pikachu(), PokemonNumber::charmander()]
+        );
+    }
+
+    #[test]
+    fn it_should_deliver_the_queued_events_before_returning_from_a_flush() {
+        let subscriber = Arc::new(RecordingSubscriber::default());
+        let bus = EventBus::queued(vec![subscriber.clone()], |_| {});
+
+        bus.publish(deleted(PokemonNumber::pikachu()));
+        bus.publish(deleted(PokemonNumber::charmander()));
+
+        assert!(bus.flush(Duration::from_secs(1)));
+        assert_eq!(subscriber.events().len(), 2);
+    }
+
+    struct PanickingSubscriber;
+
+    impl Subscriber for PanickingSubscriber {
+        fn handle(&self, _: &Event) {
+            panic!("the subscriber failed");
+        }
+    }
+
+    #[test]
+    fn it_should_keep_delivering_when_queued_after_a_subscriber_panicked() {
+        let subscriber = Arc::new(RecordingSubscriber::default());
+        let failures = Arc::new(AtomicUsize::new(0));
+        let counter = failures.clone();
+        let bus = EventBus::queued(
+            vec![Arc::new(PanickingSubscriber), subscriber.clone()],
+            move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        bus.publish(deleted(PokemonNumber::pikachu()));
+        bus.publish(deleted(PokemonNumber::charmander()));
+
+        assert!(bus.flush(Duration::from_secs(1)));
+        assert_eq!(subscriber.events().len(), 2);
+        assert_eq!(failures.load(Ordering::SeqCst), 2);
+    }
+}
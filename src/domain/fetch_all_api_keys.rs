@@ -0,0 +1,62 @@
+use crate::repositories::api_key::{ApiKeyRepository, FetchAllError};
+use std::sync::Arc;
+
+pub enum Error {
+    Unknown,
+}
+
+/// The keys themselves cannot be given back.
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    pub role: String,
+    pub created_at: u64,
+}
+
+pub fn execute(repo: Arc<dyn ApiKeyRepository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(keys) => Ok(keys
+            .into_iter()
+            .map(|key| Response {
+                id: key.id.to_u32(),
+                name: String::from(key.name),
+                role: key.role.to_string(),
+                created_at: key.created_at,
+            })
+            .collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::api_key::{ApiKeyHash, ApiKeyName, Role};
+    use crate::repositories::api_key::InMemoryApiKeyRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_all_the_keys_otherwise() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new());
+        repo.insert(ApiKeyName::ci(), Role::Reader, ApiKeyHash::of("key"), 0)
+            .ok();
+
+        match execute(repo) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].name, "ci");
+                assert_eq!(res[0].role, "reader");
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -1,26 +1,61 @@
 use std::sync::Arc;
 
+use crate::domain::fetch_pokemon::parse_languages;
+use crate::domain::generation::Generations;
 use crate::repositories::pokemon::{FetchAllError, Repository};
 
+pub struct Request {
+    /// Only keep the Pokemons introduced by this generation.
+    pub generation: Option<u8>,
+    /// Only keep the Pokemons with a name containing this, in any language.
+    pub name: Option<String>,
+    /// Preferred languages for the names, most preferred first.
+    pub languages: Vec<String>,
+}
+
 pub enum Error {
+    BadRequest,
     Unknown,
 }
 
 #[derive(Debug)]
 pub struct Response {
     pub number: u16,
+    pub form: String,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Vec<u16>,
 }
 
-pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    generations: &Generations,
+    req: Request,
+) -> Result<Vec<Response>, Error> {
+    let generation = match req.generation {
+        Some(number) => match generations.find(number) {
+            Some(generation) => Some(generation),
+            None => return Err(Error::BadRequest),
+        },
+        None => None,
+    };
+
+    let languages = parse_languages(req.languages);
+    let query = req.name.filter(|n| !n.trim().is_empty());
+
     match repo.fetch_all() {
         Ok(pokemons) => Ok(pokemons
             .into_iter()
+            .filter(|p| {
+                generation.is_none_or(|g| g.number == generations.generation_of(p.number.to_u16()))
+            })
+            .filter(|p| query.as_ref().is_none_or(|q| p.name_contains(q.trim())))
             .map(|p| Response {
                 number: p.number.to_u16(),
-                name: p.name.to_string(),
+                form: p.form.to_string(),
+                name: p.localized_name(&languages).to_string(),
                 types: p.types.to_vec_string(),
+                stats: p.stats.to_vec_u16(),
             })
             .collect::<Vec<Response>>()),
         Err(FetchAllError::Unknown) => Err(Error::Unknown),
@@ -33,14 +68,24 @@ mod tests {
     use std::sync::Arc;
 
     use crate::{
-        domain::entities::{PokemonName, PokemonNumber, PokemonTypes},
+        domain::entities::{
+            Language, PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+        },
         repositories::pokemon::InMemoryRepository,
     };
 
     #[test]
     fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
-        let res = execute(repo);
+        let res = execute(
+            repo,
+            &Generations::default(),
+            Request {
+                generation: None,
+                name: None,
+                languages: vec![],
+            },
+        );
 
         match res {
             Err(Error::Unknown) => {}
@@ -48,33 +93,150 @@ mod tests {
         };
     }
 
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_generation_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let res = execute(
+            repo,
+            &Generations::default(),
+            Request {
+                generation: Some(42),
+                name: None,
+                languages: vec![],
+            },
+        );
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_only_return_the_pokemons_of_the_requested_generation() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+
+        match execute(
+            repo.clone(),
+            &Generations::default(),
+            Request {
+                generation: Some(1),
+                name: None,
+                languages: vec![],
+            },
+        ) {
+            Ok(res) => assert_eq!(res.len(), 1),
+            _ => unreachable!(),
+        };
+        match execute(
+            repo,
+            &Generations::default(),
+            Request {
+                generation: Some(2),
+                name: None,
+                languages: vec![],
+            },
+        ) {
+            Ok(res) => assert!(res.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_match_the_name_in_any_language() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo.save_name(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            Language::japanese(),
+            PokemonName::charmander_in(&Language::japanese()),
+        )
+        .ok();
+
+        let req = Request {
+            generation: None,
+            name: Some("ヒトカ".to_string()),
+            languages: vec!["ja".to_string()],
+        };
+        match execute(repo.clone(), &Generations::default(), req) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(
+                    res[0].name,
+                    PokemonName::charmander_in(&Language::japanese()).to_string()
+                );
+            }
+            _ => unreachable!(),
+        };
+
+        let req = Request {
+            generation: None,
+            name: Some("pika".to_string()),
+            languages: vec![],
+        };
+        match execute(repo, &Generations::default(), req) {
+            Ok(res) => assert!(res.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+
     #[test]
     fn it_should_return_all_the_pokemons_ordered_by_increasing_number_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         )
         .ok();
 
         repo.insert(
             PokemonNumber::charmander(),
+            PokemonForm::default(),
             PokemonName::charmander(),
             PokemonTypes::charmander(),
+            PokemonStats::charmander(),
         )
         .ok();
 
-        let res = execute(repo);
+        let res = execute(
+            repo,
+            &Generations::default(),
+            Request {
+                generation: None,
+                name: None,
+                languages: vec![],
+            },
+        );
 
         match res {
             Ok(res) => {
                 assert_eq!(res[0].number, PokemonNumber::charmander().to_u16());
                 assert_eq!(res[0].name, PokemonName::charmander().to_string());
                 assert_eq!(res[0].types, PokemonTypes::charmander().to_vec_string());
+                assert_eq!(res[0].stats, PokemonStats::charmander().to_vec_u16());
                 assert_eq!(res[1].number, PokemonNumber::pikachu().to_u16());
                 assert_eq!(res[1].name, PokemonName::pikachu().to_string());
                 assert_eq!(res[1].types, PokemonTypes::pikachu().to_vec_string());
+                assert_eq!(res[1].stats, PokemonStats::pikachu().to_vec_u16());
             }
             _ => unreachable!(),
         };
@@ -0,0 +1,54 @@
+use crate::domain::create_team::Response;
+use crate::repositories::team::{FetchAllError, TeamRepository};
+use std::sync::Arc;
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn TeamRepository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(teams) => Ok(teams.into_iter().map(Response::from).collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::team::{TeamMember, TeamName};
+    use crate::repositories::team::InMemoryTeamRepository;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryTeamRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_all_the_teams_ordered_by_id_otherwise() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        repo.insert(TeamName::kanto(), vec![TeamMember::pikachu()])
+            .ok();
+        repo.insert(
+            TeamName::try_from("Fire".to_string()).ok().unwrap(),
+            vec![TeamMember::charmander()],
+        )
+        .ok();
+
+        match execute(repo) {
+            Ok(res) => {
+                assert_eq!(res.len(), 2);
+                assert_eq!(res[0].id, 1);
+                assert_eq!(res[1].id, 2);
+                assert_eq!(res[1].name, "Fire".to_string());
+            }
+            _ => unreachable!(),
+        };
+    }
+}
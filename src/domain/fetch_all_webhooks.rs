@@ -0,0 +1,45 @@
+use crate::domain::create_webhook::Response;
+use crate::repositories::webhook::{FetchAllError, WebhookRepository};
+use std::sync::Arc;
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn WebhookRepository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(webhooks) => Ok(webhooks.into_iter().map(Response::from).collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::webhook::{WebhookSecret, WebhookUrl};
+    use crate::repositories::webhook::InMemoryWebhookRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryWebhookRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_all_the_webhooks_otherwise() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        repo.insert(WebhookUrl::local(), WebhookSecret::test()).ok();
+
+        match execute(repo) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].url, String::from(WebhookUrl::local()));
+            }
+            _ => unreachable!(),
+        };
+    }
+}
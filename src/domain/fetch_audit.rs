@@ -0,0 +1,180 @@
+use crate::domain::audit::PokemonSnapshot;
+use crate::domain::entities::PokemonNumber;
+use crate::repositories::audit::{AuditRepository, FetchError};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub struct Request {
+    /// Only keep the changes made to this Pokemon.
+    pub number: Option<u16>,
+    /// Only keep the changes made at or after this Unix timestamp.
+    pub since: Option<u64>,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub at: u64,
+    pub actor: String,
+    pub action: String,
+    pub number: u16,
+    pub form: String,
+    pub before: Option<PokemonSnapshot>,
+    pub after: Option<PokemonSnapshot>,
+}
+
+pub fn execute(audit: Arc<dyn AuditRepository>, req: Request) -> Result<Vec<Response>, Error> {
+    let number = match req.number.map(PokemonNumber::try_from) {
+        Some(Ok(number)) => Some(number),
+        Some(Err(_)) => return Err(Error::BadRequest),
+        None => None,
+    };
+
+    match audit.fetch(number, req.since) {
+        Ok(entries) => Ok(entries
+            .into_iter()
+            .map(|e| Response {
+                at: e.at,
+                actor: e.actor,
+                action: String::from(e.action),
+                number: e.number.to_u16(),
+                form: e.form.to_string(),
+                before: e.before,
+                after: e.after,
+            })
+            .collect()),
+        Err(FetchError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::clock::now;
+    use crate::domain::entities::{Language, PokemonForm, PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::audit::{AuditedRepository, InMemoryAuditRepository};
+    use crate::repositories::pokemon::{InMemoryRepository, Repository};
+
+    fn audited_repo() -> (AuditedRepository, Arc<InMemoryAuditRepository>) {
+        let audit = Arc::new(InMemoryAuditRepository::new());
+        let repo = AuditedRepository::new(
+            Arc::new(InMemoryRepository::new()),
+            audit.clone(),
+            "cli:ash".to_string(),
+        );
+        (repo, audit)
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_number_is_invalid() {
+        let audit = Arc::new(InMemoryAuditRepository::new());
+        let req = Request {
+            number: Some(PokemonNumber::bad().to_u16()),
+            since: None,
+        };
+
+        match execute(audit, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let audit = Arc::new(InMemoryAuditRepository::new().with_error());
+        let req = Request {
+            number: None,
+            since: None,
+        };
+
+        match execute(audit, req) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_not_record_failed_changes() {
+        let (repo, audit) = audited_repo();
+        repo.delete(PokemonNumber::pikachu(), PokemonForm::default(), now())
+            .ok();
+
+        let req = Request {
+            number: None,
+            since: None,
+        };
+        match execute(audit, req) {
+            Ok(res) => assert!(res.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_every_change_with_its_snapshots_otherwise() {
+        let (repo, audit) = audited_repo();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo.save_name(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            Language::french(),
+            PokemonName::charmander_in(&Language::french()),
+        )
+        .ok();
+        repo.delete(PokemonNumber::charmander(), PokemonForm::default(), now())
+            .ok();
+        repo.restore(PokemonNumber::charmander(), PokemonForm::default())
+            .ok();
+
+        let req = Request {
+            number: Some(PokemonNumber::charmander().to_u16()),
+            since: None,
+        };
+        match execute(audit.clone(), req) {
+            Ok(res) => {
+                let actions = res.iter().map(|e| e.action.as_str()).collect::<Vec<&str>>();
+                assert_eq!(actions, vec!["created", "updated", "deleted", "restored"]);
+                assert!(res.iter().all(|e| e.actor == "cli:ash"));
+
+                let updated = &res[1];
+                assert_eq!(updated.before.as_ref().map(|s| s.names.len()), Some(0));
+                assert_eq!(
+                    updated.after.as_ref().map(|s| s.names.clone()),
+                    Some(vec![(
+                        "fr".to_string(),
+                        PokemonName::charmander_in(&Language::french()).to_string()
+                    )])
+                );
+                assert!(res[2].before.is_some() && res[2].after.is_none());
+            }
+            _ => unreachable!(),
+        };
+
+        let req = Request {
+            number: None,
+            since: Some(now() + 60),
+        };
+        match execute(audit, req) {
+            Ok(res) => assert!(res.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,74 @@
+use crate::domain::webhook::DeadLetter;
+use crate::repositories::webhook::{FetchAllError, WebhookRepository};
+use std::sync::Arc;
+
+pub struct Response {
+    pub webhook: u32,
+    pub event: String,
+    pub payload: String,
+    pub attempts: u32,
+    pub failed_at: u64,
+}
+
+impl From<DeadLetter> for Response {
+    fn from(dead_letter: DeadLetter) -> Self {
+        Self {
+            webhook: dead_letter.webhook.to_u32(),
+            event: dead_letter.event,
+            payload: dead_letter.payload,
+            attempts: dead_letter.attempts,
+            failed_at: dead_letter.failed_at,
+        }
+    }
+}
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn WebhookRepository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_dead_letters() {
+        Ok(dead_letters) => Ok(dead_letters.into_iter().map(Response::from).collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::webhook::{WebhookId, MAX_ATTEMPTS};
+    use crate::repositories::webhook::InMemoryWebhookRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryWebhookRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_undelivered_events_otherwise() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        repo.record_dead_letter(DeadLetter {
+            webhook: WebhookId::from(1),
+            event: "pokemon.deleted".to_string(),
+            payload: "{}".to_string(),
+            attempts: MAX_ATTEMPTS,
+            failed_at: 42,
+        })
+        .ok();
+
+        match execute(repo) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].webhook, 1);
+                assert_eq!(res[0].event, "pokemon.deleted".to_string());
+                assert_eq!(res[0].attempts, MAX_ATTEMPTS);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
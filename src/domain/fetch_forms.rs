@@ -0,0 +1,85 @@
+use crate::domain::entities::PokemonNumber;
+use crate::domain::fetch_pokemon::{parse_languages, Response};
+use crate::repositories::pokemon::{FetchOneError, Repository};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+    pub languages: Vec<String>,
+}
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        _ => return Err(Error::BadRequest),
+    };
+
+    let languages = parse_languages(req.languages);
+    match repo.fetch_forms(number) {
+        Ok(pokemons) => Ok(pokemons
+            .into_iter()
+            .map(|p| Response::localized(p, &languages))
+            .collect()),
+        Err(FetchOneError::NotFound) => Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonForm, PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_species_is_not_stored() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: PokemonNumber::pikachu().to_u16(),
+            languages: vec![],
+        };
+
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_default_form_first_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (form, name) in [
+            (PokemonForm::alola(), "Alolan Pikachu"),
+            (PokemonForm::default(), "Pikachu"),
+        ] {
+            repo.insert(
+                PokemonNumber::pikachu(),
+                form,
+                PokemonName::try_from(name.to_string()).ok().unwrap(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            )
+            .ok();
+        }
+        let req = Request {
+            number: PokemonNumber::pikachu().to_u16(),
+            languages: vec![],
+        };
+
+        match execute(repo, req) {
+            Ok(res) => {
+                assert_eq!(res.len(), 2);
+                assert_eq!(res[0].form, PokemonForm::default().to_string());
+                assert_eq!(res[1].form, PokemonForm::alola().to_string());
+            }
+            _ => unreachable!(),
+        };
+    }
+}
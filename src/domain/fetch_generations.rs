@@ -0,0 +1,37 @@
+use crate::domain::generation::Generations;
+
+#[derive(Debug)]
+pub struct Response {
+    pub number: u8,
+    pub region: String,
+    pub first: u16,
+    pub last: u16,
+}
+
+pub fn execute(generations: &Generations) -> Vec<Response> {
+    generations
+        .all()
+        .iter()
+        .map(|g| Response {
+            number: g.number,
+            region: g.region.clone(),
+            first: g.first,
+            last: g.last,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_return_the_generations_in_release_order() {
+        let res = execute(&Generations::default());
+
+        assert_eq!(res[0].number, 1);
+        assert_eq!(res[0].region, "Kanto");
+        assert_eq!(res[0].first, 1);
+        assert!(res.windows(2).all(|w| w[0].last + 1 == w[1].first));
+    }
+}
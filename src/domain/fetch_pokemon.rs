@@ -1,11 +1,34 @@
 use crate::{
-    domain::entities::{Pokemon, PokemonNumber},
+    domain::entities::{Language, Pokemon, PokemonForm, PokemonName, PokemonNumber},
     repositories::pokemon::{FetchOneError, Repository},
 };
 use std::{convert::TryFrom, sync::Arc};
 
+/// A Pokemon is either looked up by number and form, or by its English name,
+/// regardless of case.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Identifier {
+    Number(u16),
+    Name(String),
+}
+
+/// Input that parses as a number is a number, anything else a name.
+impl From<String> for Identifier {
+    fn from(input: String) -> Self {
+        match input.trim().parse::<u16>() {
+            Ok(number) => Self::Number(number),
+            _ => Self::Name(input),
+        }
+    }
+}
+
 pub struct Request {
-    pub number: u16,
+    pub identifier: Identifier,
+    /// Defaults to the default form of the species. A name already designates
+    /// a single form, so it cannot be combined with one.
+    pub form: Option<String>,
+    /// Preferred languages for the name, most preferred first.
+    pub languages: Vec<String>,
 }
 
 pub enum Error {
@@ -16,27 +39,61 @@ pub enum Error {
 
 pub struct Response {
     pub number: u16,
+    pub form: String,
+    /// Name in the preferred language, English otherwise.
     pub name: String,
+    /// Every known name as `(language, name)`, English first.
+    pub names: Vec<(String, String)>,
     pub types: Vec<String>,
+    pub stats: Vec<u16>,
+}
+
+impl Response {
+    pub fn localized(pokemon: Pokemon, languages: &[Language]) -> Self {
+        Self {
+            number: pokemon.number.to_u16(),
+            form: pokemon.form.to_string(),
+            name: pokemon.localized_name(languages).to_string(),
+            names: std::iter::once((Language::english(), pokemon.name))
+                .chain(pokemon.translations)
+                .map(|(language, name)| (String::from(language), name.to_string()))
+                .collect(),
+            types: pokemon.types.to_vec_string(),
+            stats: pokemon.stats.to_vec_u16(),
+        }
+    }
+}
+
+/// Keeps the valid languages, in order; unknown ones fall back to English anyway.
+pub fn parse_languages(languages: Vec<String>) -> Vec<Language> {
+    languages
+        .into_iter()
+        .filter_map(|l| Language::try_from(l).ok())
+        .collect()
 }
 
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
-    match PokemonNumber::try_from(req.number) {
-        Ok(number) => match repo.fetch_one(number) {
-            Ok(Pokemon {
-                number,
-                name,
-                types,
-            }) => Ok(Response {
-                number: number.to_u16(),
-                name: name.to_string(),
-                types: types.to_vec_string(),
-            }),
-
-            Err(FetchOneError::NotFound) => Err(Error::NotFound),
-            Err(FetchOneError::Unknown) => Err(Error::Unknown),
+    let pokemon = match (req.identifier, req.form) {
+        (Identifier::Number(number), form) => {
+            match (PokemonNumber::try_from(number), PokemonForm::try_from(form)) {
+                (Ok(number), Ok(form)) => repo.fetch_one(number, form),
+                _ => return Err(Error::BadRequest),
+            }
+        }
+        (Identifier::Name(name), None) => match PokemonName::try_from(name) {
+            Ok(name) => repo.fetch_by_name(name),
+            _ => return Err(Error::BadRequest),
         },
-        _ => Err(Error::BadRequest),
+        (Identifier::Name(_), Some(_)) => return Err(Error::BadRequest),
+    };
+
+    match pokemon {
+        Ok(pokemon) => Ok(Response::localized(
+            pokemon,
+            &parse_languages(req.languages),
+        )),
+        Err(FetchOneError::NotFound) => Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => Err(Error::Unknown),
     }
 }
 
@@ -44,7 +101,7 @@ pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Erro
 mod tests {
 
     use crate::{
-        domain::entities::{PokemonName, PokemonTypes},
+        domain::entities::{PokemonStats, PokemonTypes},
         repositories::pokemon::InMemoryRepository,
     };
 
@@ -87,13 +144,140 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_form_is_not_stored() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+
+        let req = Request {
+            form: Some(PokemonForm::alola().to_string()),
+            ..Request::new(PokemonNumber::pikachu())
+        };
+
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_name_in_the_preferred_language_with_an_english_fallback() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo.save_name(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            Language::french(),
+            PokemonName::charmander_in(&Language::french()),
+        )
+        .ok();
+
+        let req = Request {
+            languages: vec!["de".to_string(), "fr-CA".to_string()],
+            ..Request::new(PokemonNumber::charmander())
+        };
+        match execute(repo.clone(), req) {
+            Ok(res) => {
+                assert_eq!(
+                    res.name,
+                    PokemonName::charmander_in(&Language::french()).to_string()
+                );
+                assert_eq!(res.names.len(), 2);
+                assert_eq!(res.names[0].0, "en");
+            }
+            _ => unreachable!(),
+        }
+
+        let req = Request {
+            languages: vec!["ja".to_string()],
+            ..Request::new(PokemonNumber::charmander())
+        };
+        match execute(repo, req) {
+            Ok(res) => assert_eq!(res.name, PokemonName::charmander().to_string()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_parse_numbers_and_names() {
+        assert_eq!(Identifier::from(" 25 ".to_string()), Identifier::Number(25));
+        assert_eq!(
+            Identifier::from("Mr. Mime".to_string()),
+            Identifier::Name("Mr. Mime".to_string())
+        );
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_name_comes_with_a_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            identifier: Identifier::Name(PokemonName::pikachu().to_string()),
+            form: Some(PokemonForm::alola().to_string()),
+            languages: vec![],
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_pokemon_by_name_regardless_of_case() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+
+        let req = Request {
+            identifier: Identifier::Name(" pIKAchu".to_string()),
+            form: None,
+            languages: vec![],
+        };
+        match execute(repo.clone(), req) {
+            Ok(res) => assert_eq!(res.number, PokemonNumber::pikachu().to_u16()),
+            _ => unreachable!(),
+        }
+
+        let req = Request {
+            identifier: Identifier::Name(PokemonName::charmander().to_string()),
+            form: None,
+            languages: vec![],
+        };
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn it_should_return_the_pokemon_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         )
         .ok();
 
@@ -104,8 +288,10 @@ mod tests {
         match res {
             Ok(res) => {
                 assert_eq!(res.number, PokemonNumber::pikachu().to_u16());
+                assert_eq!(res.form, PokemonForm::default().to_string());
                 assert_eq!(res.name, PokemonName::pikachu().to_string());
                 assert_eq!(res.types, PokemonTypes::pikachu().to_vec_string());
+                assert_eq!(res.stats, PokemonStats::pikachu().to_vec_u16());
             }
             _ => unreachable!(),
         }
@@ -114,7 +300,9 @@ mod tests {
     impl Request {
         fn new(number: PokemonNumber) -> Self {
             Self {
-                number: number.to_u16(),
+                identifier: Identifier::Number(number.to_u16()),
+                form: None,
+                languages: vec![],
             }
         }
     }
@@ -0,0 +1,236 @@
+use crate::domain::entities::{Pokemon, PokemonType};
+use crate::domain::generation::Generations;
+use crate::domain::mark_entry;
+use crate::domain::progress::{DexEntry, EntryStatus, ProfileName};
+use crate::repositories::pokemon::{self, Repository};
+use crate::repositories::progress::{FetchAllError, ProgressRepository};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub struct Request {
+    pub profile: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    pub seen: usize,
+    pub caught: usize,
+    pub total: usize,
+    pub seen_percent: f32,
+    pub caught_percent: f32,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub profile: String,
+    pub overall: Completion,
+    pub by_type: Vec<(String, Completion)>,
+    pub by_generation: Vec<(u8, Completion)>,
+    pub entries: Vec<mark_entry::Response>,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn ProgressRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    generations: &Generations,
+    req: Request,
+) -> Result<Response, Error> {
+    let profile = match ProfileName::try_from(req.profile) {
+        Ok(profile) => profile,
+        _ => return Err(Error::BadRequest),
+    };
+
+    // Entries are tracked per species, so only the first form of each counts.
+    let catalogue = match pokemon_repo.fetch_all() {
+        Ok(mut pokemons) => {
+            pokemons.dedup_by(|a, b| a.number == b.number);
+            pokemons
+        }
+        Err(pokemon::FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let entries = match repo.fetch_entries(profile.clone()) {
+        Ok(entries) => entries,
+        Err(FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let by_type = PokemonType::ALL
+        .iter()
+        .map(|t| {
+            (
+                t.get_value(),
+                completion(&catalogue, &entries, |p| p.types.contains(*t)),
+            )
+        })
+        .filter(|(_, c)| c.total > 0)
+        .collect();
+
+    let generation_of = |p: &Pokemon| generations.generation_of(p.number.to_u16());
+    let mut released = catalogue.iter().map(generation_of).collect::<Vec<u8>>();
+    released.sort_unstable();
+    released.dedup();
+
+    let by_generation = released
+        .into_iter()
+        .map(|g| {
+            (
+                g,
+                completion(&catalogue, &entries, |p| generation_of(p) == g),
+            )
+        })
+        .collect();
+
+    Ok(Response {
+        profile: String::from(profile),
+        overall: completion(&catalogue, &entries, |_| true),
+        by_type,
+        by_generation,
+        entries: entries
+            .into_iter()
+            .map(mark_entry::Response::from)
+            .collect(),
+    })
+}
+
+/// Completion of the catalogue entries matching `filter`. Entries for
+/// Pokemon no longer in the catalogue are ignored.
+fn completion<F>(catalogue: &[Pokemon], entries: &[DexEntry], filter: F) -> Completion
+where
+    F: Fn(&Pokemon) -> bool,
+{
+    let pokemons = catalogue
+        .iter()
+        .filter(|p| filter(p))
+        .collect::<Vec<&Pokemon>>();
+    let status = |p: &Pokemon| {
+        entries
+            .iter()
+            .find(|e| e.number == p.number)
+            .map(|e| e.status)
+    };
+
+    let total = pokemons.len();
+    let seen = pokemons.iter().filter(|p| status(p).is_some()).count();
+    let caught = pokemons
+        .iter()
+        .filter(|p| status(p) == Some(EntryStatus::Caught))
+        .count();
+    let percent = |count: usize| {
+        if total == 0 {
+            0.0
+        } else {
+            (count as f32 * 1000.0 / total as f32).round() / 10.0
+        }
+    };
+
+    Completion {
+        seen,
+        caught,
+        total,
+        seen_percent: percent(seen),
+        caught_percent: percent(caught),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+    use crate::repositories::progress::InMemoryProgressRepository;
+
+    fn pokemon_repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo
+    }
+
+    fn request() -> Request {
+        Request {
+            profile: String::from(ProfileName::ash()),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_profile_is_blank() {
+        let repo = Arc::new(InMemoryProgressRepository::new());
+
+        match execute(
+            repo,
+            pokemon_repo(),
+            &Generations::default(),
+            Request {
+                profile: " ".to_string(),
+            },
+        ) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryProgressRepository::new().with_error());
+
+        match execute(repo, pokemon_repo(), &Generations::default(), request()) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_completion_percentages_otherwise() {
+        let repo = Arc::new(InMemoryProgressRepository::new());
+        repo.save(
+            ProfileName::ash(),
+            DexEntry::new(
+                PokemonNumber::pikachu(),
+                EntryStatus::Caught,
+                false,
+                None,
+                1,
+            ),
+        )
+        .ok();
+
+        match execute(repo, pokemon_repo(), &Generations::default(), request()) {
+            Ok(res) => {
+                assert_eq!(res.overall.total, 2);
+                assert_eq!(res.overall.caught, 1);
+                assert_eq!(res.overall.caught_percent, 50.0);
+
+                let electric = res.by_type.iter().find(|(t, _)| t == "Electric").unwrap();
+                assert_eq!(electric.1.caught_percent, 100.0);
+                let fire = res.by_type.iter().find(|(t, _)| t == "Fire").unwrap();
+                assert_eq!(fire.1.seen_percent, 0.0);
+
+                assert_eq!(res.by_generation.len(), 1);
+                assert_eq!(res.by_generation[0].0, 1);
+                assert_eq!(res.entries.len(), 1);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,104 @@
+use crate::domain::fetch_pokemon::{self, Identifier, Response};
+use crate::domain::generation::Generations;
+use crate::repositories::pokemon::Repository;
+use std::sync::Arc;
+
+pub struct Request {
+    pub region: String,
+    /// Number of the Pokemon in the regional Pokedex.
+    pub number: u16,
+    pub languages: Vec<String>,
+}
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    generations: &Generations,
+    req: Request,
+) -> Result<Response, Error> {
+    let number = match generations.to_national(&req.region, req.number) {
+        Some(number) => number,
+        None => return Err(Error::NotFound),
+    };
+
+    match fetch_pokemon::execute(
+        repo,
+        fetch_pokemon::Request {
+            identifier: Identifier::Number(number),
+            form: None,
+            languages: req.languages,
+        },
+    ) {
+        Ok(res) => Ok(res),
+        Err(fetch_pokemon::Error::BadRequest) => Err(Error::BadRequest),
+        Err(fetch_pokemon::Error::NotFound) => Err(Error::NotFound),
+        Err(fetch_pokemon::Error::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_region_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            region: "Orre".to_string(),
+            number: 1,
+            languages: vec![],
+        };
+
+        match execute(repo, &Generations::default(), req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_number_is_outside_the_regional_dex() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            region: "Kanto".to_string(),
+            number: 152,
+            languages: vec![],
+        };
+
+        match execute(repo, &Generations::default(), req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemon_of_the_regional_number_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        let req = Request {
+            region: "kanto".to_string(),
+            number: 25,
+            languages: vec![],
+        };
+
+        match execute(repo, &Generations::default(), req) {
+            Ok(res) => assert_eq!(res.name, PokemonName::pikachu().to_string()),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,64 @@
+use crate::domain::create_team::Response;
+use crate::domain::team::TeamId;
+use crate::repositories::team::{FetchOneError, TeamRepository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub id: u32,
+}
+
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn TeamRepository>, req: Request) -> Result<Response, Error> {
+    match repo.fetch_one(TeamId::from(req.id)) {
+        Ok(team) => Ok(Response::from(team)),
+        Err(FetchOneError::NotFound) => Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::create_team::Member;
+    use crate::domain::team::{TeamMember, TeamName};
+    use crate::repositories::team::InMemoryTeamRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryTeamRepository::new().with_error());
+
+        match execute(repo, Request { id: 1 }) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_team() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+
+        match execute(repo, Request { id: 1 }) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_team_otherwise() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        repo.insert(TeamName::kanto(), vec![TeamMember::pikachu()])
+            .ok();
+
+        match execute(repo, Request { id: 1 }) {
+            Ok(res) => {
+                assert_eq!(res.name, String::from(TeamName::kanto()));
+                assert_eq!(res.members, vec![Member::from(TeamMember::pikachu())]);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,92 @@
+use crate::repositories::pokemon::{FetchAllError, Repository};
+use std::sync::Arc;
+
+pub enum Error {
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub number: u16,
+    pub form: String,
+    pub name: String,
+    pub types: Vec<String>,
+    pub deleted_at: u64,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_trash() {
+        Ok(deleted) => Ok(deleted
+            .into_iter()
+            .map(|d| Response {
+                number: d.pokemon.number.to_u16(),
+                form: d.pokemon.form.to_string(),
+                name: d.pokemon.name.to_string(),
+                types: d.pokemon.types.to_vec_string(),
+                deleted_at: d.deleted_at,
+            })
+            .collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_deleted_pokemons_most_recent_first_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo.delete(PokemonNumber::pikachu(), PokemonForm::default(), 20)
+            .ok();
+
+        match execute(repo.clone()) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].name, PokemonName::pikachu().to_string());
+                assert_eq!(res[0].deleted_at, 20);
+            }
+            _ => unreachable!(),
+        };
+
+        repo.delete(PokemonNumber::charmander(), PokemonForm::default(), 30)
+            .ok();
+        match execute(repo) {
+            Ok(res) => {
+                assert_eq!(res[0].number, PokemonNumber::charmander().to_u16());
+                assert_eq!(res[1].number, PokemonNumber::pikachu().to_u16());
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,111 @@
+use crate::domain::webhook::{Delivery, DeliveryStatus, WebhookId};
+use crate::repositories::webhook::{FetchOneError, WebhookRepository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub id: u32,
+}
+
+pub struct Response {
+    pub event: String,
+    pub attempt: u32,
+    pub at: u64,
+    pub delivered: bool,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl From<Delivery> for Response {
+    fn from(delivery: Delivery) -> Self {
+        let delivered = delivery.status.is_success();
+        let (status_code, error) = match delivery.status {
+            DeliveryStatus::Responded(code) => (Some(code), None),
+            DeliveryStatus::Unreachable(error) => (None, Some(error)),
+        };
+
+        Self {
+            event: delivery.event,
+            attempt: delivery.attempt,
+            at: delivery.at,
+            delivered,
+            status_code,
+            error,
+        }
+    }
+}
+
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn WebhookRepository>, req: Request) -> Result<Vec<Response>, Error> {
+    match repo.fetch_deliveries(WebhookId::from(req.id)) {
+        Ok(deliveries) => Ok(deliveries.into_iter().map(Response::from).collect()),
+        Err(FetchOneError::NotFound) => Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::webhook::{WebhookSecret, WebhookUrl};
+    use crate::repositories::webhook::InMemoryWebhookRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryWebhookRepository::new().with_error());
+
+        match execute(repo, Request { id: 1 }) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_webhook() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+
+        match execute(repo, Request { id: 1 }) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_attempts_oldest_first_otherwise() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let webhook = repo
+            .insert(WebhookUrl::local(), WebhookSecret::test())
+            .ok()
+            .unwrap();
+        for (attempt, status) in vec![
+            DeliveryStatus::Unreachable("connection refused".to_string()),
+            DeliveryStatus::Responded(204),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            repo.record_delivery(Delivery {
+                webhook: webhook.id,
+                event: "pokemon.created".to_string(),
+                attempt: attempt as u32 + 1,
+                at: 0,
+                status,
+            })
+            .ok();
+        }
+
+        match execute(repo, Request { id: 1 }) {
+            Ok(res) => {
+                assert_eq!(res.len(), 2);
+                assert!(!res[0].delivered);
+                assert_eq!(res[0].error, Some("connection refused".to_string()));
+                assert!(res[1].delivered);
+                assert_eq!(res[1].status_code, Some(204));
+            }
+            _ => unreachable!(),
+        };
+    }
+}
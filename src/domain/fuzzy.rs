@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+/// Trigrams of the lowercased text, padded so that short words and word
+/// starts still produce some.
+pub fn trigrams(text: &str) -> HashSet<String> {
+    let padded = format!("  {} ", text.trim().to_lowercase())
+        .chars()
+        .collect::<Vec<char>>();
+    padded
+        .windows(3)
+        .map(|w| w.iter().collect::<String>())
+        .collect()
+}
+
+/// Jaccard index of the trigrams of both texts, between 0 and 1.
+pub fn trigram_similarity(a: &str, b: &str) -> f32 {
+    let (a, b) = (trigrams(a), trigrams(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+/// Levenshtein distance between the lowercased texts, counted in characters.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_lowercase().chars().collect::<Vec<char>>();
+    let b = b.to_lowercase().chars().collect::<Vec<char>>();
+
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// How close `query` is to `name`, between 0 and 1. Names starting with the
+/// query score high so that partial input works as autocomplete.
+pub fn similarity(query: &str, name: &str) -> f32 {
+    let (query, name) = (query.trim().to_lowercase(), name.trim().to_lowercase());
+    let longest = query.chars().count().max(name.chars().count());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    let edit = 1.0 - edit_distance(&query, &name) as f32 / longest as f32;
+    let prefix = if !query.is_empty() && name.starts_with(&query) {
+        0.9
+    } else {
+        0.0
+    };
+
+    edit.max(trigram_similarity(&query, &name)).max(prefix)
+}
+
+/// Whether `answer` spells `name` closely enough to be accepted, allowing
+/// one typo for every five characters of the name.
+pub fn is_close_enough(answer: &str, name: &str) -> bool {
+    let (answer, name) = (answer.trim(), name.trim());
+    !answer.is_empty() && edit_distance(answer, name) <= name.chars().count() / 5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_compute_the_edit_distance() {
+        assert_eq!(edit_distance("Pikachuu", "pikachu"), 1);
+        assert_eq!(edit_distance("charmandr", "Charmander"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn it_should_rank_close_names_above_distant_ones() {
+        assert!(similarity("pikachuu", "Pikachu") > similarity("pikachuu", "Raichu"));
+        assert!(similarity("char", "Charmander") >= 0.9);
+        assert!(similarity("bulbasaur", "Pikachu") < 0.3);
+        assert_eq!(similarity("Pikachu", "pikachu"), 1.0);
+    }
+
+    #[test]
+    fn it_should_accept_answers_with_few_typos() {
+        assert!(is_close_enough(" pikachu ", "Pikachu"));
+        assert!(is_close_enough("Pikachuu", "Pikachu"));
+        assert!(is_close_enough("Charmandr", "Charmander"));
+        assert!(!is_close_enough("Pika", "Pikachu"));
+        assert!(!is_close_enough("Mew", "Mewtwo"));
+        assert!(!is_close_enough("Muk", "Mew"));
+        assert!(!is_close_enough("", "Mew"));
+    }
+}
@@ -0,0 +1,242 @@
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generation {
+    /// Position in the release order, starting at 1.
+    pub number: u8,
+    pub region: String,
+    /// First and last national numbers introduced by this generation.
+    pub first: u16,
+    pub last: u16,
+}
+
+/// Maps the numbering of a regional Pokedex onto national numbers: the
+/// entry at index `i` is regional number `i + 1`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionalDex {
+    pub region: String,
+    pub numbers: Vec<u16>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Generations {
+    generations: Vec<Generation>,
+    regional_dexes: Vec<RegionalDex>,
+}
+
+impl Generations {
+    /// Generations must be contiguous, start at national number 1 and be
+    /// listed in release order. Regional dexes must reference known regions
+    /// and national numbers.
+    pub fn try_new(
+        mut generations: Vec<Generation>,
+        regional_dexes: Vec<RegionalDex>,
+    ) -> Result<Self, ()> {
+        generations.sort_by_key(|g| g.number);
+
+        let mut next = 1;
+        for (i, generation) in generations.iter().enumerate() {
+            if usize::from(generation.number) != i + 1
+                || generation.first != next
+                || generation.last < generation.first
+                || generation.region.trim().is_empty()
+            {
+                return Err(());
+            }
+            next = match generation.last.checked_add(1) {
+                Some(next) => next,
+                None => return Err(()),
+            };
+        }
+
+        let generations = Self {
+            generations,
+            regional_dexes: vec![],
+        };
+        let max = match generations.max_number() {
+            0 => return Err(()),
+            max => max,
+        };
+
+        for dex in regional_dexes.iter() {
+            if generations.find_region(&dex.region).is_none()
+                || dex.numbers.iter().any(|n| *n == 0 || *n > max)
+            {
+                return Err(());
+            }
+        }
+
+        Ok(Self {
+            regional_dexes,
+            ..generations
+        })
+    }
+
+    pub fn all(&self) -> &[Generation] {
+        &self.generations
+    }
+
+    pub fn max_number(&self) -> u16 {
+        self.generations.last().map_or(0, |g| g.last)
+    }
+
+    pub fn of(&self, number: u16) -> Option<&Generation> {
+        self.generations
+            .iter()
+            .find(|g| g.first <= number && number <= g.last)
+    }
+
+    /// Release order of the generation that introduced a national number,
+    /// `0` when none did.
+    pub fn generation_of(&self, number: u16) -> u8 {
+        self.of(number).map_or(0, |g| g.number)
+    }
+
+    pub fn find(&self, number: u8) -> Option<&Generation> {
+        self.generations.iter().find(|g| g.number == number)
+    }
+
+    fn find_region(&self, region: &str) -> Option<&Generation> {
+        self.generations
+            .iter()
+            .find(|g| g.region.eq_ignore_ascii_case(region))
+    }
+
+    /// National number of the `regional`-th entry of a region's Pokedex.
+    /// Regions without an explicit dex use the range of their generation.
+    pub fn to_national(&self, region: &str, regional: u16) -> Option<u16> {
+        if regional == 0 {
+            return None;
+        }
+
+        match self
+            .regional_dexes
+            .iter()
+            .find(|d| d.region.eq_ignore_ascii_case(region))
+        {
+            Some(dex) => dex.numbers.get(usize::from(regional) - 1).copied(),
+            None => self
+                .find_region(region)
+                .and_then(|g| g.first.checked_add(regional - 1).filter(|n| *n <= g.last)),
+        }
+    }
+}
+
+impl Default for Generations {
+    fn default() -> Self {
+        let generation = |number: u8, region: &str, first: u16, last: u16| Generation {
+            number,
+            region: region.to_string(),
+            first,
+            last,
+        };
+
+        Self {
+            generations: vec![
+                generation(1, "Kanto", 1, 151),
+                generation(2, "Johto", 152, 251),
+                generation(3, "Hoenn", 252, 386),
+                generation(4, "Sinnoh", 387, 493),
+                generation(5, "Unova", 494, 649),
+                generation(6, "Kalos", 650, 721),
+                generation(7, "Alola", 722, 809),
+                generation(8, "Galar", 810, 905),
+                generation(9, "Paldea", 906, 1025),
+            ],
+            regional_dexes: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generation(number: u8, region: &str, first: u16, last: u16) -> Generation {
+        Generation {
+            number,
+            region: region.to_string(),
+            first,
+            last,
+        }
+    }
+
+    #[test]
+    fn it_should_find_the_generation_of_a_national_number() {
+        let generations = Generations::default();
+
+        assert_eq!(generations.of(25).map(|g| g.number), Some(1));
+        assert_eq!(
+            generations.of(152).map(|g| g.region.as_str()),
+            Some("Johto")
+        );
+        assert_eq!(generations.of(1025).map(|g| g.number), Some(9));
+        assert_eq!(generations.of(1026), None);
+        assert_eq!(generations.max_number(), 1025);
+    }
+
+    #[test]
+    fn it_should_reject_gaps_and_overlaps_between_generations() {
+        assert!(Generations::try_new(
+            vec![
+                generation(1, "Kanto", 1, 151),
+                generation(2, "Johto", 160, 251)
+            ],
+            vec![],
+        )
+        .is_err());
+        assert!(Generations::try_new(
+            vec![
+                generation(1, "Kanto", 1, 151),
+                generation(2, "Johto", 100, 251)
+            ],
+            vec![],
+        )
+        .is_err());
+        assert!(Generations::try_new(vec![], vec![]).is_err());
+    }
+
+    #[test]
+    fn it_should_reject_a_generation_ending_past_the_highest_number() {
+        assert!(Generations::try_new(
+            vec![
+                generation(1, "Kanto", 1, 151),
+                generation(2, "Johto", 152, 65535)
+            ],
+            vec![],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn it_should_reject_regional_dexes_of_unknown_regions() {
+        assert!(Generations::try_new(
+            vec![generation(1, "Kanto", 1, 151)],
+            vec![RegionalDex {
+                region: "Johto".to_string(),
+                numbers: vec![152],
+            }],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn it_should_map_regional_numbers_onto_national_numbers() {
+        let generations = Generations::try_new(
+            vec![
+                generation(1, "Kanto", 1, 151),
+                generation(2, "Johto", 152, 251),
+            ],
+            vec![RegionalDex {
+                region: "Johto".to_string(),
+                numbers: vec![152, 153, 154, 16, 17],
+            }],
+        )
+        .ok()
+        .unwrap();
+
+        assert_eq!(generations.to_national("johto", 4), Some(16));
+        assert_eq!(generations.to_national("Johto", 6), None);
+        assert_eq!(generations.to_national("Kanto", 25), Some(25));
+        assert_eq!(generations.to_national("Kanto", 152), None);
+        assert_eq!(generations.to_national("Hoenn", 1), None);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::domain::api_key::{generate_key, ApiKeyHash, ApiKeyName, Role};
+use crate::domain::clock::now;
+use crate::repositories::api_key::{ApiKeyRepository, InsertError};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub struct Request {
+    pub name: String,
+    pub role: String,
+}
+
+/// The key is only given back here, its hash being all that is stored.
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    pub role: String,
+    pub key: String,
+}
+
+pub enum Error {
+    BadRequest,
+    Conflict,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn ApiKeyRepository>, req: Request) -> Result<Response, Error> {
+    let (name, role) = match (ApiKeyName::try_from(req.name), Role::try_from(req.role)) {
+        (Ok(name), Ok(role)) => (name, role),
+        _ => return Err(Error::BadRequest),
+    };
+
+    let key = match generate_key() {
+        Ok(key) => key,
+        _ => return Err(Error::Unknown),
+    };
+
+    match repo.insert(name, role, ApiKeyHash::of(&key), now()) {
+        Ok(issued) => Ok(Response {
+            id: issued.id.to_u32(),
+            name: String::from(issued.name),
+            role: issued.role.to_string(),
+            key,
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::api_key::InMemoryApiKeyRepository;
+
+    fn request(name: &str, role: &str) -> Request {
+        Request {
+            name: name.to_string(),
+            role: role.to_string(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_role_is_unknown() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new());
+
+        match execute(repo, request("ci", "owner")) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_name_is_taken() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new());
+        execute(repo.clone(), request("ci", "reader")).ok();
+
+        match execute(repo, request("ci", "admin")) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new().with_error());
+
+        match execute(repo, request("ci", "reader")) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_only_store_the_hash_of_the_key_otherwise() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new());
+
+        match execute(repo.clone(), request("ci", "editor")) {
+            Ok(res) => {
+                assert_eq!(res.id, 1);
+                assert_eq!(res.role, "editor");
+                let stored = repo.fetch_by_hash(ApiKeyHash::of(&res.key)).ok().unwrap();
+                assert_ne!(String::from(stored.hash), res.key);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
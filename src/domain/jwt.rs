@@ -0,0 +1,261 @@
+use crate::domain::api_key::Role;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use std::convert::TryFrom;
+
+/// Seconds the clocks of the issuer and of the API may drift apart.
+const LEEWAY: u64 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JwtError {
+    Malformed,
+    /// Only HS256 is accepted, `none` above all.
+    UnsupportedAlgorithm,
+    BadSignature,
+    /// Tokens must expire.
+    MissingExpiration,
+    Expired,
+    NotYetValid,
+    WrongAudience,
+    /// The role claim is missing or names no role.
+    MissingRole,
+}
+
+/// Who the token was issued to, and what they may do.
+#[derive(Debug)]
+pub struct Claims {
+    pub subject: Option<String>,
+    pub role: Role,
+}
+
+/// Checks the tokens signed with HS256 by a trusted issuer.
+#[derive(Clone)]
+pub struct JwtVerifier {
+    secret: Vec<u8>,
+    /// When set, the `aud` claim must hold it.
+    audience: Option<String>,
+    /// Claim holding `reader`, `editor` or `admin`.
+    role_claim: String,
+}
+
+impl JwtVerifier {
+    pub fn new(secret: &str, audience: Option<String>, role_claim: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+            audience,
+            role_claim: role_claim.to_string(),
+        }
+    }
+
+    /// `now` is in seconds since the Unix epoch.
+    pub fn verify(&self, token: &str, now: u64) -> Result<Claims, JwtError> {
+        let parts = token.split('.').collect::<Vec<&str>>();
+        let (header, payload, signature) = match parts.as_slice() {
+            [header, payload, signature] => (*header, *payload, *signature),
+            _ => return Err(JwtError::Malformed),
+        };
+
+        let header = decode_json(header)?;
+        if header.get("alg").and_then(Value::as_str) != Some("HS256") {
+            return Err(JwtError::UnsupportedAlgorithm);
+        }
+
+        let signature = match URL_SAFE_NO_PAD.decode(signature) {
+            Ok(signature) => signature,
+            _ => return Err(JwtError::Malformed),
+        };
+        let signed = &token[..token.len() - parts[2].len() - 1];
+        if self
+            .mac(signed.as_bytes())
+            .verify_slice(&signature)
+            .is_err()
+        {
+            return Err(JwtError::BadSignature);
+        }
+
+        // The claims are only looked at once they are known to be genuine.
+        let claims = decode_json(payload)?;
+        match claims.get("exp").map(Value::as_u64) {
+            None => return Err(JwtError::MissingExpiration),
+            Some(None) => return Err(JwtError::Malformed),
+            Some(Some(exp)) if exp.saturating_add(LEEWAY) <= now => return Err(JwtError::Expired),
+            _ => {}
+        }
+        match claims.get("nbf").map(Value::as_u64) {
+            Some(None) => return Err(JwtError::Malformed),
+            Some(Some(nbf)) if nbf > now + LEEWAY => return Err(JwtError::NotYetValid),
+            _ => {}
+        }
+        if let Some(audience) = &self.audience {
+            let matches = match claims.get("aud") {
+                Some(Value::String(aud)) => aud == audience,
+                Some(Value::Array(auds)) => auds.iter().any(|aud| aud.as_str() == Some(audience)),
+                _ => false,
+            };
+            if !matches {
+                return Err(JwtError::WrongAudience);
+            }
+        }
+
+        let role = match claims.get(&self.role_claim).and_then(Value::as_str) {
+            Some(role) => Role::try_from(role.to_string()).map_err(|_| JwtError::MissingRole)?,
+            None => return Err(JwtError::MissingRole),
+        };
+
+        Ok(Claims {
+            subject: claims.get("sub").and_then(Value::as_str).map(String::from),
+            role,
+        })
+    }
+
+    fn mac(&self, data: &[u8]) -> Hmac<Sha256> {
+        let mut mac = match Hmac::<Sha256>::new_from_slice(&self.secret) {
+            Ok(mac) => mac,
+            _ => unreachable!("HMAC accepts keys of any length"),
+        };
+        mac.update(data);
+        mac
+    }
+}
+
+/// A token has three parts separated by dots, unlike an API key.
+pub fn looks_like_jwt(token: &str) -> bool {
+    token.split('.').count() == 3
+}
+
+fn decode_json(part: &str) -> Result<Value, JwtError> {
+    match URL_SAFE_NO_PAD
+        .decode(part)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+    {
+        Some(value @ Value::Object(_)) => Ok(value),
+        _ => Err(JwtError::Malformed),
+    }
+}
+
+#[cfg(test)]
+impl JwtVerifier {
+    /// Signs `claims` the way the gateway does.
+    pub fn sign(&self, claims: Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let signed = format!("{}.{}", header, payload);
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(signed.as_bytes()).finalize().into_bytes());
+        format!("{}.{}", signed, signature)
+    }
+
+    pub fn test() -> Self {
+        Self::new("gateway secret", Some("pokedex".to_string()), "role")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn it_should_accept_a_valid_token() {
+        let verifier = JwtVerifier::test();
+        let token = verifier.sign(json!({
+            "sub": "gateway", "aud": ["other", "pokedex"], "role": "editor",
+            "exp": NOW + 60, "nbf": NOW - 60,
+        }));
+
+        let claims = verifier.verify(&token, NOW).unwrap();
+
+        assert_eq!(claims.role, Role::Editor);
+        assert_eq!(claims.subject, Some("gateway".to_string()));
+    }
+
+    #[test]
+    fn it_should_reject_a_token_signed_with_another_secret() {
+        let other = JwtVerifier::new("another secret", None, "role");
+        let token = other.sign(json!({ "aud": "pokedex", "role": "admin", "exp": NOW + 60 }));
+
+        assert_eq!(
+            JwtVerifier::test().verify(&token, NOW).err(),
+            Some(JwtError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn it_should_reject_a_tampered_token() {
+        let verifier = JwtVerifier::test();
+        let token = verifier.sign(json!({ "aud": "pokedex", "role": "reader", "exp": NOW + 60 }));
+        let forged = URL_SAFE_NO_PAD
+            .encode(json!({ "aud": "pokedex", "role": "admin", "exp": NOW + 60 }).to_string());
+        let parts = token.split('.').collect::<Vec<&str>>();
+        let tampered = format!("{}.{}.{}", parts[0], forged, parts[2]);
+
+        assert_eq!(
+            verifier.verify(&tampered, NOW).err(),
+            Some(JwtError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn it_should_reject_the_unsigned_tokens() {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#);
+        let payload =
+            URL_SAFE_NO_PAD.encode(json!({ "role": "admin", "exp": NOW + 60 }).to_string());
+        let token = format!("{}.{}.", header, payload);
+
+        assert_eq!(
+            JwtVerifier::test().verify(&token, NOW).err(),
+            Some(JwtError::UnsupportedAlgorithm)
+        );
+    }
+
+    #[test]
+    fn it_should_check_the_validity_period() {
+        let verifier = JwtVerifier::test();
+        let verify = |claims: Value| verifier.verify(&verifier.sign(claims), NOW).err();
+
+        assert_eq!(
+            verify(json!({ "aud": "pokedex", "role": "reader", "exp": NOW - LEEWAY })),
+            Some(JwtError::Expired)
+        );
+        assert_eq!(
+            verify(
+                json!({ "aud": "pokedex", "role": "reader", "exp": NOW + 60, "nbf": NOW + LEEWAY + 1 })
+            ),
+            Some(JwtError::NotYetValid)
+        );
+        assert_eq!(
+            verify(json!({ "aud": "pokedex", "role": "reader" })),
+            Some(JwtError::MissingExpiration)
+        );
+    }
+
+    #[test]
+    fn it_should_check_the_audience_and_the_role() {
+        let verifier = JwtVerifier::test();
+        let verify = |claims: Value| verifier.verify(&verifier.sign(claims), NOW).err();
+
+        assert_eq!(
+            verify(json!({ "aud": "billing", "role": "reader", "exp": NOW + 60 })),
+            Some(JwtError::WrongAudience)
+        );
+        assert_eq!(
+            verify(json!({ "aud": "pokedex", "role": "owner", "exp": NOW + 60 })),
+            Some(JwtError::MissingRole)
+        );
+        assert_eq!(
+            verify(json!({ "aud": "pokedex", "exp": NOW + 60 })),
+            Some(JwtError::MissingRole)
+        );
+    }
+
+    #[test]
+    fn it_should_tell_tokens_from_api_keys() {
+        assert!(looks_like_jwt(&JwtVerifier::test().sign(json!({}))));
+        assert!(!looks_like_jwt("pkdx_0123"));
+    }
+}
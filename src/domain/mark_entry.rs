@@ -0,0 +1,158 @@
+use crate::domain::clock::now;
+use crate::domain::entities::PokemonNumber;
+use crate::domain::progress::{DexEntry, EntryStatus, ProfileName};
+use crate::repositories::pokemon::{self, Repository};
+use crate::repositories::progress::{FetchOneError, ProgressRepository, SaveError};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub profile: String,
+    pub number: u16,
+    pub status: String,
+    pub shiny: bool,
+    pub form: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub number: u16,
+    pub status: String,
+    pub shiny: bool,
+    pub form: Option<String>,
+    pub seen_at: u64,
+    pub caught_at: Option<u64>,
+}
+
+impl From<DexEntry> for Response {
+    fn from(entry: DexEntry) -> Self {
+        Self {
+            number: entry.number.to_u16(),
+            status: String::from(entry.status),
+            shiny: entry.shiny,
+            form: entry.form,
+            seen_at: entry.seen_at,
+            caught_at: entry.caught_at,
+        }
+    }
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn ProgressRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let (profile, number, status) = match (
+        ProfileName::try_from(req.profile),
+        PokemonNumber::try_from(req.number),
+        EntryStatus::try_from(req.status),
+    ) {
+        (Ok(profile), Ok(number), Ok(status)) => (profile, number, status),
+        _ => return Err(Error::BadRequest),
+    };
+
+    match pokemon_repo.fetch_forms(number.clone()) {
+        Ok(_) => {}
+        Err(pokemon::FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(pokemon::FetchOneError::Unknown) => return Err(Error::Unknown),
+    }
+
+    let form = req.form.filter(|f| !f.trim().is_empty());
+    let entry = DexEntry::new(number.clone(), status, req.shiny, form, now());
+
+    let entry = match repo.fetch_entry(profile.clone(), number) {
+        Ok(existing) => existing.merge(entry),
+        Err(FetchOneError::NotFound) => entry,
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    match repo.save(profile, entry) {
+        Ok(entry) => Ok(Response::from(entry)),
+        Err(SaveError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonForm, PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+    use crate::repositories::progress::InMemoryProgressRepository;
+
+    fn pokemon_repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    fn request(status: &str) -> Request {
+        Request {
+            profile: String::from(ProfileName::ash()),
+            number: PokemonNumber::pikachu().to_u16(),
+            status: status.to_string(),
+            shiny: false,
+            form: None,
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryProgressRepository::new());
+
+        match execute(repo, pokemon_repo(), request("owned")) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_is_not_in_the_catalogue() {
+        let repo = Arc::new(InMemoryProgressRepository::new());
+        let mut req = request("seen");
+        req.number = PokemonNumber::charmander().to_u16();
+
+        match execute(repo, pokemon_repo(), req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryProgressRepository::new().with_error());
+
+        match execute(repo, pokemon_repo(), request("seen")) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_keep_an_entry_caught_once_it_has_been_caught() {
+        let repo = Arc::new(InMemoryProgressRepository::new());
+        execute(repo.clone(), pokemon_repo(), request("caught")).ok();
+
+        match execute(repo, pokemon_repo(), request("seen")) {
+            Ok(res) => {
+                assert_eq!(res.number, PokemonNumber::pikachu().to_u16());
+                assert_eq!(res.status, "caught".to_string());
+                assert!(res.caught_at.is_some());
+            }
+            _ => unreachable!(),
+        };
+    }
+}
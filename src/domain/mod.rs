@@ -1,5 +1,47 @@
+pub mod analyze_team;
+pub mod api_key;
+pub mod audit;
+pub mod authorize;
+pub mod battle;
+pub mod check_readiness;
+pub mod clock;
+pub mod count_pokemons;
 pub mod create_pokemon;
+pub mod create_team;
+pub mod create_webhook;
 pub mod delete_pokemon;
+pub mod delete_team;
+pub mod delete_webhook;
 pub mod entities;
+pub mod events;
+pub mod fetch_all_api_keys;
 pub mod fetch_all_pokemons;
+pub mod fetch_all_teams;
+pub mod fetch_all_webhooks;
+pub mod fetch_audit;
+pub mod fetch_dead_letters;
+pub mod fetch_forms;
+pub mod fetch_generations;
 pub mod fetch_pokemon;
+pub mod fetch_progress;
+pub mod fetch_regional_pokemon;
+pub mod fetch_team;
+pub mod fetch_trash;
+pub mod fetch_webhook_deliveries;
+pub mod fuzzy;
+pub mod generation;
+pub mod issue_api_key;
+pub mod jwt;
+pub mod mark_entry;
+pub mod progress;
+pub mod purge_trash;
+pub mod random_pokemon;
+pub mod restore_pokemon;
+pub mod revoke_api_key;
+pub mod rng;
+pub mod save_pokemon_name;
+pub mod search_pokemon;
+pub mod simulate_battle;
+pub mod team;
+pub mod update_team;
+pub mod webhook;
@@ -0,0 +1,152 @@
+use crate::domain::entities::PokemonNumber;
+use std::convert::TryFrom;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileName(String);
+
+impl TryFrom<String> for ProfileName {
+    type Error = ();
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            Err(())
+        } else {
+            Ok(Self(name))
+        }
+    }
+}
+
+impl From<ProfileName> for String {
+    fn from(name: ProfileName) -> Self {
+        name.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryStatus {
+    Seen,
+    Caught,
+}
+
+impl TryFrom<String> for EntryStatus {
+    type Error = ();
+
+    fn try_from(status: String) -> Result<Self, Self::Error> {
+        match status.as_str() {
+            "seen" => Ok(Self::Seen),
+            "caught" => Ok(Self::Caught),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<EntryStatus> for String {
+    fn from(status: EntryStatus) -> Self {
+        match status {
+            EntryStatus::Seen => "seen".to_string(),
+            EntryStatus::Caught => "caught".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DexEntry {
+    pub number: PokemonNumber,
+    pub status: EntryStatus,
+    pub shiny: bool,
+    pub form: Option<String>,
+    /// Unix timestamps, in seconds.
+    pub seen_at: u64,
+    pub caught_at: Option<u64>,
+}
+
+impl DexEntry {
+    pub fn new(
+        number: PokemonNumber,
+        status: EntryStatus,
+        shiny: bool,
+        form: Option<String>,
+        now: u64,
+    ) -> Self {
+        Self {
+            number,
+            status,
+            shiny,
+            form,
+            seen_at: now,
+            caught_at: match status {
+                EntryStatus::Caught => Some(now),
+                EntryStatus::Seen => None,
+            },
+        }
+    }
+
+    /// Records a new sighting or capture on top of this entry. A caught
+    /// entry never goes back to seen and the first timestamps are kept.
+    pub fn merge(self, other: DexEntry) -> Self {
+        let status = match (self.status, other.status) {
+            (EntryStatus::Caught, _) | (_, EntryStatus::Caught) => EntryStatus::Caught,
+            _ => EntryStatus::Seen,
+        };
+
+        Self {
+            number: self.number,
+            status,
+            shiny: self.shiny || other.shiny,
+            form: other.form.or(self.form),
+            seen_at: self.seen_at.min(other.seen_at),
+            caught_at: self.caught_at.or(other.caught_at),
+        }
+    }
+}
+
+#[cfg(test)]
+impl ProfileName {
+    pub fn ash() -> Self {
+        Self("ash".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_never_downgrade_a_caught_entry() {
+        let caught = DexEntry::new(
+            PokemonNumber::pikachu(),
+            EntryStatus::Caught,
+            false,
+            None,
+            10,
+        );
+        let seen = DexEntry::new(PokemonNumber::pikachu(), EntryStatus::Seen, true, None, 20);
+
+        let merged = caught.merge(seen);
+
+        assert_eq!(merged.status, EntryStatus::Caught);
+        assert!(merged.shiny);
+        assert_eq!(merged.seen_at, 10);
+        assert_eq!(merged.caught_at, Some(10));
+    }
+
+    #[test]
+    fn it_should_record_the_first_capture_time() {
+        let seen = DexEntry::new(PokemonNumber::pikachu(), EntryStatus::Seen, false, None, 10);
+        let caught = DexEntry::new(
+            PokemonNumber::pikachu(),
+            EntryStatus::Caught,
+            false,
+            Some("Partner".to_string()),
+            20,
+        );
+
+        let merged = seen.merge(caught);
+
+        assert_eq!(merged.status, EntryStatus::Caught);
+        assert_eq!(merged.seen_at, 10);
+        assert_eq!(merged.caught_at, Some(20));
+        assert_eq!(merged.form, Some("Partner".to_string()));
+    }
+}
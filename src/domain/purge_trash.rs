@@ -0,0 +1,93 @@
+use crate::domain::clock::now;
+use crate::repositories::pokemon::{PurgeError, Repository};
+use std::sync::Arc;
+
+pub const DEFAULT_RETENTION_DAYS: u64 = 30;
+
+pub struct Request {
+    /// How long deleted Pokemons stay in the trash, in seconds.
+    pub retention: u64,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub purged: usize,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match repo.purge(now().saturating_sub(req.retention)) {
+        Ok(purged) => Ok(Response { purged }),
+        Err(PurgeError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, Request { retention: 0 }) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_only_purge_the_pokemons_deleted_before_the_retention_period() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo.delete(PokemonNumber::pikachu(), PokemonForm::default(), 0)
+            .ok();
+        repo.delete(PokemonNumber::charmander(), PokemonForm::default(), now())
+            .ok();
+
+        match execute(repo.clone(), Request { retention: 3600 }) {
+            Ok(res) => assert_eq!(res.purged, 1),
+            _ => unreachable!(),
+        };
+        match repo.fetch_trash() {
+            Ok(trash) => {
+                assert_eq!(trash.len(), 1);
+                assert_eq!(trash[0].pokemon.number, PokemonNumber::charmander());
+            }
+            _ => unreachable!(),
+        };
+
+        // A purged Pokemon frees its number and name.
+        assert!(repo
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            )
+            .is_ok());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::domain::entities::PokemonType;
+use crate::domain::fetch_pokemon::{parse_languages, Response};
+use crate::domain::generation::Generations;
+use crate::domain::rng::Rng;
+use crate::repositories::pokemon::{FetchAllError, Repository};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub struct Request {
+    /// The same seed over the same catalogue picks the same Pokemon.
+    pub seed: u64,
+    /// Only pick among the Pokemons of this type.
+    pub kind: Option<String>,
+    /// Only pick among the Pokemons introduced by this generation.
+    pub generation: Option<u8>,
+    pub languages: Vec<String>,
+}
+
+pub enum Error {
+    BadRequest,
+    /// No Pokemon matches the filters.
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    generations: &Generations,
+    req: Request,
+) -> Result<Response, Error> {
+    let kind = match req.kind.map(PokemonType::try_from) {
+        Some(Ok(kind)) => Some(kind),
+        Some(Err(_)) => return Err(Error::BadRequest),
+        None => None,
+    };
+
+    let generation = match req.generation {
+        Some(number) => match generations.find(number) {
+            Some(generation) => Some(generation.number),
+            None => return Err(Error::BadRequest),
+        },
+        None => None,
+    };
+
+    let mut candidates = match repo.fetch_all() {
+        Ok(pokemons) => pokemons
+            .into_iter()
+            .filter(|p| kind.is_none_or(|k| p.types.contains(k)))
+            .filter(|p| {
+                generation.is_none_or(|g| g == generations.generation_of(p.number.to_u16()))
+            })
+            .collect::<Vec<_>>(),
+        Err(FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    if candidates.is_empty() {
+        return Err(Error::NotFound);
+    }
+
+    let index = Rng::new(req.seed).range(0, candidates.len() as u64) as usize;
+    Ok(Response::localized(
+        candidates.swap_remove(index),
+        &parse_languages(req.languages),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repo_with_pokemons() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo
+    }
+
+    fn request(seed: u64, kind: Option<&str>, generation: Option<u8>) -> Request {
+        Request {
+            seed,
+            kind: kind.map(|k| k.to_string()),
+            generation,
+            languages: vec![],
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_filter_is_invalid() {
+        let repo = repo_with_pokemons();
+
+        match execute(
+            repo.clone(),
+            &Generations::default(),
+            request(1, Some("Sound"), None),
+        ) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+        match execute(repo, &Generations::default(), request(1, None, Some(42))) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_no_pokemon_matches() {
+        let repo = repo_with_pokemons();
+
+        match execute(
+            repo,
+            &Generations::default(),
+            request(1, Some("Water"), None),
+        ) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, &Generations::default(), request(1, None, None)) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_only_pick_among_the_pokemons_matching_the_filters() {
+        let repo = repo_with_pokemons();
+
+        for seed in 0..10 {
+            match execute(
+                repo.clone(),
+                &Generations::default(),
+                request(seed, Some("Fire"), Some(1)),
+            ) {
+                Ok(res) => assert_eq!(res.number, PokemonNumber::charmander().to_u16()),
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_pick_the_same_pokemon_for_the_same_seed_otherwise() {
+        let repo = repo_with_pokemons();
+
+        let picks = (0..10)
+            .map(|seed| {
+                match execute(
+                    repo.clone(),
+                    &Generations::default(),
+                    request(seed, None, None),
+                ) {
+                    Ok(res) => res.number,
+                    _ => unreachable!(),
+                }
+            })
+            .collect::<Vec<u16>>();
+
+        for (seed, number) in picks.iter().enumerate() {
+            match execute(
+                repo.clone(),
+                &Generations::default(),
+                request(seed as u64, None, None),
+            ) {
+                Ok(res) => assert_eq!(res.number, *number),
+                _ => unreachable!(),
+            };
+        }
+        assert!(picks.contains(&PokemonNumber::pikachu().to_u16()));
+        assert!(picks.contains(&PokemonNumber::charmander().to_u16()));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::domain::entities::{PokemonForm, PokemonNumber};
+use crate::domain::events::{Event, EventBus};
+use crate::domain::fetch_pokemon::{parse_languages, Response};
+use crate::repositories::pokemon::{Repository, RestoreError};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+    /// Defaults to the default form of the species.
+    pub form: Option<String>,
+    pub languages: Vec<String>,
+}
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    /// The Pokemon is not in the trash.
+    NotFound,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    req: Request,
+) -> Result<Response, Error> {
+    match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form),
+    ) {
+        (Ok(number), Ok(form)) => match repo.restore(number, form) {
+            Ok(pokemon) => {
+                events.publish(Event::PokemonCreated(pokemon.clone()));
+                Ok(Response::localized(
+                    pokemon,
+                    &parse_languages(req.languages),
+                ))
+            }
+            Err(RestoreError::NotFound) => Err(Error::NotFound),
+            Err(RestoreError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn request(number: PokemonNumber) -> Request {
+        Request {
+            number: number.to_u16(),
+            form: None,
+            languages: vec![],
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            request(PokemonNumber::bad()),
+        ) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_is_not_in_the_trash() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            request(PokemonNumber::pikachu()),
+        ) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            request(PokemonNumber::pikachu()),
+        ) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_bring_the_pokemon_back_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.delete(PokemonNumber::pikachu(), PokemonForm::default(), 10)
+            .ok();
+
+        match execute(
+            repo.clone(),
+            EventBus::without_subscribers(),
+            request(PokemonNumber::pikachu()),
+        ) {
+            Ok(res) => assert_eq!(res.name, PokemonName::pikachu().to_string()),
+            _ => unreachable!(),
+        };
+
+        assert!(repo
+            .fetch_one(PokemonNumber::pikachu(), PokemonForm::default())
+            .is_ok());
+        match repo.fetch_trash() {
+            Ok(trash) => assert!(trash.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,60 @@
+use crate::domain::api_key::ApiKeyId;
+use crate::repositories::api_key::{ApiKeyRepository, DeleteError};
+use std::sync::Arc;
+
+pub struct Request {
+    pub id: u32,
+}
+
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn ApiKeyRepository>, req: Request) -> Result<(), Error> {
+    match repo.delete(ApiKeyId::from(req.id)) {
+        Ok(()) => Ok(()),
+        Err(DeleteError::NotFound) => Err(Error::NotFound),
+        Err(DeleteError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::api_key::{ApiKeyHash, ApiKeyName, Role};
+    use crate::repositories::api_key::InMemoryApiKeyRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new().with_error());
+
+        match execute(repo, Request { id: 1 }) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_key() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new());
+
+        match execute(repo, Request { id: 1 }) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_no_longer_find_the_key_otherwise() {
+        let repo = Arc::new(InMemoryApiKeyRepository::new());
+        repo.insert(ApiKeyName::ci(), Role::Reader, ApiKeyHash::of("key"), 0)
+            .ok();
+
+        match execute(repo.clone(), Request { id: 1 }) {
+            Ok(()) => {}
+            _ => unreachable!(),
+        };
+        assert!(repo.fetch_by_hash(ApiKeyHash::of("key")).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+/// Small seedable pseudo-random generator (SplitMix64) so that use cases
+/// relying on chance stay reproducible and testable.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `low..high`, or `low` when the range is empty.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        if high <= low {
+            return low;
+        }
+        low + self.next_u64() % (high - low)
+    }
+
+    pub fn chance(&mut self, percent: u8) -> bool {
+        self.range(0, 100) < u64::from(percent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_produce_the_same_sequence_for_the_same_seed() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn it_should_stay_within_the_requested_range() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..100 {
+            let value = rng.range(85, 101);
+            assert!((85..101).contains(&value));
+        }
+        assert_eq!(rng.range(3, 3), 3);
+    }
+}
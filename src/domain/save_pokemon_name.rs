@@ -0,0 +1,177 @@
+use crate::domain::entities::{Language, PokemonForm, PokemonName, PokemonNumber};
+use crate::domain::events::{Event, EventBus};
+use crate::domain::fetch_pokemon::Response;
+use crate::repositories::pokemon::{Repository, SaveNameError};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+    /// Defaults to the default form of the species.
+    pub form: Option<String>,
+    pub language: String,
+    pub name: String,
+}
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+    Conflict,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    req: Request,
+) -> Result<Response, Error> {
+    let (number, form, language, name) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form),
+        Language::try_from(req.language),
+        PokemonName::try_from(req.name),
+    ) {
+        (Ok(number), Ok(form), Ok(language), Ok(name)) => (number, form, language, name),
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.save_name(number, form, language.clone(), name) {
+        Ok(pokemon) => {
+            events.publish(Event::PokemonUpdated(pokemon.clone()));
+            Ok(Response::localized(pokemon, &[language]))
+        }
+        Err(SaveNameError::NotFound) => Err(Error::NotFound),
+        Err(SaveNameError::Conflict) => Err(Error::Conflict),
+        Err(SaveNameError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonStats, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(language: &str, name: &str) -> Self {
+            Self {
+                number: PokemonNumber::charmander().to_u16(),
+                form: None,
+                language: language.to_string(),
+                name: name.to_string(),
+            }
+        }
+    }
+
+    fn repo_with_charmander() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_language_is_invalid() {
+        let repo = repo_with_charmander();
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            Request::new("french!", "Salamèche"),
+        ) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_is_not_stored() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            Request::new("fr", "Salamèche"),
+        ) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            Request::new("fr", "Salamèche"),
+        ) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_another_pokemon_has_the_name() {
+        let repo = repo_with_charmander();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            Request::new("en", "PIKACHU"),
+        ) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_add_the_translation_otherwise() {
+        let repo = repo_with_charmander();
+
+        match execute(
+            repo.clone(),
+            EventBus::without_subscribers(),
+            Request::new("FR", "Salamèche"),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.name, "Salamèche");
+                assert_eq!(
+                    res.names,
+                    vec![
+                        ("en".to_string(), "Charmander".to_string()),
+                        ("fr".to_string(), "Salamèche".to_string())
+                    ]
+                );
+            }
+            _ => unreachable!(),
+        };
+
+        match execute(
+            repo,
+            EventBus::without_subscribers(),
+            Request::new("en", "Hitokage"),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.name, "Hitokage");
+                assert_eq!(res.names.len(), 2);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,212 @@
+use crate::domain::entities::PokemonType;
+use crate::domain::fetch_pokemon::parse_languages;
+use crate::domain::fuzzy::similarity;
+use crate::repositories::pokemon::{FetchAllError, Repository};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub const DEFAULT_LIMIT: usize = 10;
+/// Below this similarity a name is too far from the query to be suggested.
+const MIN_SCORE: f32 = 0.3;
+
+pub struct Request {
+    pub query: String,
+    /// Only keep the Pokemons of this type.
+    pub kind: Option<String>,
+    pub limit: Option<usize>,
+    /// Preferred languages for the names, most preferred first.
+    pub languages: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub number: u16,
+    pub form: String,
+    /// Name in the preferred language, English otherwise.
+    pub name: String,
+    /// The name, in whatever language, closest to the query.
+    pub matched_name: String,
+    pub types: Vec<String>,
+    pub score: f32,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+/// Best matches first; ties are ordered by number.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let query = req.query.trim().to_string();
+    if query.is_empty() || req.limit == Some(0) {
+        return Err(Error::BadRequest);
+    }
+
+    let kind = match req.kind.map(PokemonType::try_from) {
+        Some(Ok(kind)) => Some(kind),
+        Some(Err(_)) => return Err(Error::BadRequest),
+        None => None,
+    };
+
+    let candidates = match repo.search(&query) {
+        Ok(candidates) => candidates,
+        Err(FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let languages = parse_languages(req.languages);
+    let mut matches = candidates
+        .into_iter()
+        .filter(|p| kind.is_none_or(|k| p.types.contains(k)))
+        .filter_map(|p| {
+            let (matched_name, score) = std::iter::once(&p.name)
+                .chain(p.translations.iter().map(|(_, name)| name))
+                .map(|name| (name.to_string(), similarity(&query, &name.to_string())))
+                .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+            if score < MIN_SCORE {
+                return None;
+            }
+
+            Some(Response {
+                number: p.number.to_u16(),
+                form: p.form.to_string(),
+                name: p.localized_name(&languages).to_string(),
+                matched_name,
+                types: p.types.to_vec_string(),
+                score,
+            })
+        })
+        .collect::<Vec<Response>>();
+
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.number.cmp(&b.number)));
+    matches.truncate(req.limit.unwrap_or(DEFAULT_LIMIT));
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Language, PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repo_with_pokemons() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo.save_name(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            Language::french(),
+            PokemonName::charmander_in(&Language::french()),
+        )
+        .ok();
+        repo
+    }
+
+    fn request(query: &str) -> Request {
+        Request {
+            query: query.to_string(),
+            kind: None,
+            limit: None,
+            languages: vec![],
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_query_is_blank() {
+        let repo = repo_with_pokemons();
+
+        match execute(repo, request("  ")) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_type_is_invalid() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            kind: Some("Sound".to_string()),
+            ..request("pika")
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, request("pika")) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_find_mistyped_names() {
+        let repo = repo_with_pokemons();
+
+        match execute(repo.clone(), request("Pikachuu")) {
+            Ok(res) => assert_eq!(res[0].number, PokemonNumber::pikachu().to_u16()),
+            _ => unreachable!(),
+        };
+        match execute(repo, request("charmandr")) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].number, PokemonNumber::charmander().to_u16());
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_match_names_in_other_languages() {
+        let repo = repo_with_pokemons();
+
+        match execute(repo, request("salameche")) {
+            Ok(res) => {
+                assert_eq!(res[0].number, PokemonNumber::charmander().to_u16());
+                assert_eq!(res[0].name, PokemonName::charmander().to_string());
+                assert_eq!(
+                    res[0].matched_name,
+                    PokemonName::charmander_in(&Language::french()).to_string()
+                );
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_only_keep_the_requested_type() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            kind: Some("Fire".to_string()),
+            ..request("pikachu")
+        };
+
+        match execute(repo, req) {
+            Ok(res) => assert!(res.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+}
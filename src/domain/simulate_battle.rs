@@ -0,0 +1,217 @@
+use crate::domain::battle::{self, Combatant, Event, Move, Side};
+use crate::domain::entities::{PokemonForm, PokemonNumber};
+use crate::domain::team::MAX_TEAM_SIZE;
+use crate::repositories::pokemon::{FetchOneError, Repository};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub seed: u64,
+    pub first: Vec<TeamMember>,
+    pub second: Vec<TeamMember>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamMember {
+    pub number: u16,
+    pub level: u8,
+    pub moves: Vec<MoveRequest>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveRequest {
+    pub name: String,
+    pub kind: String,
+    pub power: u16,
+    pub accuracy: u8,
+    pub priority: i8,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub winner: Option<Side>,
+    pub turns: u16,
+    pub log: Vec<Event>,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let first = build_team(&repo, req.first)?;
+    let second = build_team(&repo, req.second)?;
+
+    let outcome = battle::simulate(first, second, req.seed);
+
+    Ok(Response {
+        winner: outcome.winner,
+        turns: outcome.turns,
+        log: outcome.log,
+    })
+}
+
+fn build_team(
+    repo: &Arc<dyn Repository>,
+    members: Vec<TeamMember>,
+) -> Result<Vec<Combatant>, Error> {
+    if members.is_empty() || members.len() > MAX_TEAM_SIZE {
+        return Err(Error::BadRequest);
+    }
+
+    let mut team = vec![];
+    for member in members {
+        let number = match PokemonNumber::try_from(member.number) {
+            Ok(number) => number,
+            _ => return Err(Error::BadRequest),
+        };
+
+        let mut moves = vec![];
+        for m in member.moves {
+            match Move::try_new(m.name, m.kind, m.power, m.accuracy, m.priority) {
+                Ok(m) => moves.push(m),
+                _ => return Err(Error::BadRequest),
+            }
+        }
+
+        let pokemon = match repo.fetch_one(number, PokemonForm::default()) {
+            Ok(pokemon) => pokemon,
+            Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+            Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+        };
+
+        match Combatant::try_new(pokemon, member.level, moves) {
+            Ok(combatant) => team.push(combatant),
+            _ => return Err(Error::BadRequest),
+        }
+    }
+
+    Ok(team)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonForm, PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn member(number: PokemonNumber, kind: &str) -> TeamMember {
+        TeamMember {
+            number: number.to_u16(),
+            level: 50,
+            moves: vec![MoveRequest {
+                name: "Tackle".to_string(),
+                kind: kind.to_string(),
+                power: 40,
+                accuracy: 100,
+                priority: 0,
+            }],
+        }
+    }
+
+    fn repo_with_pokemons() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_team_is_empty() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            seed: 0,
+            first: vec![],
+            second: vec![member(PokemonNumber::pikachu(), "Electric")],
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_move_is_invalid() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            seed: 0,
+            first: vec![member(PokemonNumber::pikachu(), "Sound")],
+            second: vec![member(PokemonNumber::charmander(), "Fire")],
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_a_pokemon_is_not_stored() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            seed: 0,
+            first: vec![member(PokemonNumber::pikachu(), "Electric")],
+            second: vec![member(PokemonNumber::charmander(), "Fire")],
+        };
+
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            seed: 0,
+            first: vec![member(PokemonNumber::pikachu(), "Electric")],
+            second: vec![member(PokemonNumber::charmander(), "Fire")],
+        };
+
+        match execute(repo, req) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_battle_log_otherwise() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            seed: 42,
+            first: vec![member(PokemonNumber::pikachu(), "Electric")],
+            second: vec![member(PokemonNumber::charmander(), "Fire")],
+        };
+
+        match execute(repo, req) {
+            Ok(res) => {
+                assert!(res.winner.is_some());
+                assert!(res.turns > 0);
+                assert_eq!(
+                    res.log.last(),
+                    Some(&Event::BattleEnded { winner: res.winner })
+                );
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,251 @@
+use crate::domain::entities::PokemonNumber;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+pub const MAX_TEAM_SIZE: usize = 6;
+pub const MAX_MOVES: usize = 4;
+pub const MAX_LEVEL: u8 = 100;
+const MAX_NICKNAME_LENGTH: usize = 12;
+
+#[derive(PartialEq, Clone, Copy, Debug, Eq, PartialOrd, Ord)]
+pub struct TeamId(u32);
+
+impl TeamId {
+    pub fn to_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for TeamId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TeamName(String);
+
+impl TryFrom<String> for TeamName {
+    type Error = ();
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            Err(())
+        } else {
+            Ok(Self(name))
+        }
+    }
+}
+
+impl From<TeamName> for String {
+    fn from(name: TeamName) -> Self {
+        name.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TeamMember {
+    pub number: PokemonNumber,
+    pub nickname: Option<String>,
+    pub level: u8,
+    pub moves: Vec<String>,
+    pub held_item: Option<String>,
+}
+
+impl TeamMember {
+    pub fn try_new(
+        number: PokemonNumber,
+        nickname: Option<String>,
+        level: u8,
+        moves: Vec<String>,
+        held_item: Option<String>,
+    ) -> Result<Self, ()> {
+        let nickname = non_blank(nickname);
+        let held_item = non_blank(held_item);
+        let moves = moves
+            .into_iter()
+            .map(|m| m.trim().to_string())
+            .collect::<Vec<String>>();
+
+        let distinct_moves = moves.iter().collect::<HashSet<_>>().len();
+        if level == 0
+            || level > MAX_LEVEL
+            || moves.is_empty()
+            || moves.len() > MAX_MOVES
+            || moves.iter().any(|m| m.is_empty())
+            || distinct_moves != moves.len()
+            || nickname
+                .as_ref()
+                .is_some_and(|n| n.chars().count() > MAX_NICKNAME_LENGTH)
+        {
+            return Err(());
+        }
+
+        Ok(Self {
+            number,
+            nickname,
+            level,
+            moves,
+            held_item,
+        })
+    }
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[derive(Clone, Debug)]
+pub struct Team {
+    pub id: TeamId,
+    pub name: TeamName,
+    pub members: Vec<TeamMember>,
+}
+
+impl Team {
+    pub fn new(id: TeamId, name: TeamName, members: Vec<TeamMember>) -> Self {
+        Self { id, name, members }
+    }
+}
+
+/// Rules a party has to satisfy on top of the hard limit of six members.
+#[derive(Clone, Debug)]
+pub struct TeamRules {
+    /// At most one member of each species.
+    pub species_clause: bool,
+    /// Highest level allowed for any member.
+    pub level_cap: u8,
+}
+
+impl Default for TeamRules {
+    fn default() -> Self {
+        Self {
+            species_clause: false,
+            level_cap: MAX_LEVEL,
+        }
+    }
+}
+
+impl TeamRules {
+    pub fn validate(&self, members: &[TeamMember]) -> Result<(), String> {
+        if members.is_empty() || members.len() > MAX_TEAM_SIZE {
+            return Err(format!(
+                "A team must have between 1 and {} members",
+                MAX_TEAM_SIZE
+            ));
+        }
+
+        if let Some(member) = members.iter().find(|m| m.level > self.level_cap) {
+            return Err(format!(
+                "Pokemon #{} is above the level cap of {}",
+                member.number.to_u16(),
+                self.level_cap
+            ));
+        }
+
+        if self.species_clause {
+            let mut seen = HashSet::new();
+            if let Some(member) = members.iter().find(|m| !seen.insert(m.number.to_u16())) {
+                return Err(format!(
+                    "Pokemon #{} appears more than once (species clause)",
+                    member.number.to_u16()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl TeamMember {
+    pub fn pikachu() -> Self {
+        Self {
+            number: PokemonNumber::pikachu(),
+            nickname: Some("Sparky".to_string()),
+            level: 50,
+            moves: vec!["Thunderbolt".to_string(), "Quick Attack".to_string()],
+            held_item: Some("Light Ball".to_string()),
+        }
+    }
+
+    pub fn charmander() -> Self {
+        Self {
+            number: PokemonNumber::charmander(),
+            nickname: None,
+            level: 50,
+            moves: vec!["Ember".to_string()],
+            held_item: None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl TeamName {
+    pub fn kanto() -> Self {
+        Self("Kanto starters".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_reject_invalid_members() {
+        let number = PokemonNumber::pikachu;
+        assert!(TeamMember::try_new(number(), None, 0, vec!["Tackle".to_string()], None).is_err());
+        assert!(TeamMember::try_new(number(), None, 5, vec![], None).is_err());
+        assert!(TeamMember::try_new(
+            number(),
+            None,
+            5,
+            vec!["Tackle".to_string(), "Tackle".to_string()],
+            None
+        )
+        .is_err());
+        assert!(TeamMember::try_new(
+            number(),
+            Some("A very long nickname".to_string()),
+            5,
+            vec!["Tackle".to_string()],
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn it_should_enforce_the_team_size() {
+        let rules = TeamRules::default();
+
+        assert!(rules.validate(&[]).is_err());
+        assert!(rules.validate(&vec![TeamMember::pikachu(); 7]).is_err());
+        assert!(rules.validate(&vec![TeamMember::pikachu(); 6]).is_ok());
+    }
+
+    #[test]
+    fn it_should_enforce_the_level_cap() {
+        let rules = TeamRules {
+            level_cap: 30,
+            ..TeamRules::default()
+        };
+
+        assert!(rules.validate(&[TeamMember::pikachu()]).is_err());
+    }
+
+    #[test]
+    fn it_should_enforce_the_species_clause_when_enabled() {
+        let members = vec![TeamMember::pikachu(), TeamMember::pikachu()];
+
+        assert!(TeamRules::default().validate(&members).is_ok());
+        assert!(TeamRules {
+            species_clause: true,
+            ..TeamRules::default()
+        }
+        .validate(&members)
+        .is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::domain::create_team::{self, Member, Response};
+use crate::domain::team::{Team, TeamId, TeamName, TeamRules};
+use crate::repositories::pokemon::Repository;
+use crate::repositories::team::{TeamRepository, UpdateError};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub id: u32,
+    pub name: String,
+    pub members: Vec<Member>,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    RuleViolation(String),
+    Conflict,
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn TeamRepository>,
+    pokemon_repo: Arc<dyn Repository>,
+    rules: &TeamRules,
+    req: Request,
+) -> Result<Response, Error> {
+    let name = match TeamName::try_from(req.name) {
+        Ok(name) => name,
+        _ => return Err(Error::BadRequest),
+    };
+    let members = match create_team::validate_members(pokemon_repo, rules, req.members) {
+        Ok(members) => members,
+        Err(create_team::Error::BadRequest) => return Err(Error::BadRequest),
+        Err(create_team::Error::NotFound) => return Err(Error::BadRequest),
+        Err(create_team::Error::RuleViolation(violation)) => {
+            return Err(Error::RuleViolation(violation))
+        }
+        Err(_) => return Err(Error::Unknown),
+    };
+
+    match repo.update(Team::new(TeamId::from(req.id), name, members)) {
+        Ok(team) => Ok(Response::from(team)),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Conflict) => Err(Error::Conflict),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::domain::team::TeamMember;
+    use crate::repositories::pokemon::InMemoryRepository;
+    use crate::repositories::team::InMemoryTeamRepository;
+
+    fn pokemon_repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    fn request(id: u32, name: &str) -> Request {
+        Request {
+            id,
+            name: name.to_string(),
+            members: vec![Member::from(TeamMember::pikachu())],
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+
+        match execute(repo, pokemon_repo(), &TeamRules::default(), request(1, " ")) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_team() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+
+        match execute(
+            repo,
+            pokemon_repo(),
+            &TeamRules::default(),
+            request(1, "Team"),
+        ) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_another_team_has_the_name() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        repo.insert(TeamName::kanto(), vec![TeamMember::pikachu()])
+            .ok();
+        repo.insert(
+            TeamName::try_from("Other".to_string()).ok().unwrap(),
+            vec![TeamMember::pikachu()],
+        )
+        .ok();
+
+        match execute(
+            repo,
+            pokemon_repo(),
+            &TeamRules::default(),
+            request(2, &String::from(TeamName::kanto())),
+        ) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_updated_team_otherwise() {
+        let repo = Arc::new(InMemoryTeamRepository::new());
+        repo.insert(TeamName::kanto(), vec![TeamMember::pikachu()])
+            .ok();
+
+        match execute(
+            repo,
+            pokemon_repo(),
+            &TeamRules::default(),
+            request(1, "Renamed"),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.id, 1);
+                assert_eq!(res.name, "Renamed".to_string());
+            }
+            _ => unreachable!(),
+        };
+    }
+}
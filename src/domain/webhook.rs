@@ -0,0 +1,184 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// Deliveries of an event to a webhook, the first one included, before it
+/// goes to the dead letters.
+pub const MAX_ATTEMPTS: u32 = 5;
+
+#[derive(PartialEq, Clone, Copy, Debug, Eq, PartialOrd, Ord)]
+pub struct WebhookId(u32);
+
+impl WebhookId {
+    pub fn to_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for WebhookId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+/// Only `http` and `https` endpoints can be called.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookUrl(String);
+
+impl TryFrom<String> for WebhookUrl {
+    type Error = ();
+
+    fn try_from(url: String) -> Result<Self, Self::Error> {
+        let url = url.trim().to_string();
+        match url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+        {
+            Some(rest) if !rest.is_empty() && !rest.starts_with('/') => Ok(Self(url)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<WebhookUrl> for String {
+    fn from(url: WebhookUrl) -> Self {
+        url.0
+    }
+}
+
+impl WebhookUrl {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Shared with the receiver to check that a delivery comes from us.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookSecret(String);
+
+impl TryFrom<String> for WebhookSecret {
+    type Error = ();
+
+    fn try_from(secret: String) -> Result<Self, Self::Error> {
+        if secret.trim().is_empty() {
+            Err(())
+        } else {
+            Ok(Self(secret))
+        }
+    }
+}
+
+impl From<WebhookSecret> for String {
+    fn from(secret: WebhookSecret) -> Self {
+        secret.0
+    }
+}
+
+impl WebhookSecret {
+    /// Hex encoded HMAC-SHA256 of `body`.
+    pub fn sign(&self, body: &[u8]) -> String {
+        let mut mac = match Hmac::<Sha256>::new_from_slice(self.0.as_bytes()) {
+            Ok(mac) => mac,
+            _ => unreachable!("HMAC accepts keys of any length"),
+        };
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub url: WebhookUrl,
+    pub secret: WebhookSecret,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeliveryStatus {
+    /// The status code the receiver answered with.
+    Responded(u16),
+    /// The receiver could not be reached.
+    Unreachable(String),
+}
+
+impl DeliveryStatus {
+    pub fn is_success(&self) -> bool {
+        matches!(self, DeliveryStatus::Responded(code) if (200..300).contains(code))
+    }
+}
+
+/// One attempt at delivering an event to a webhook.
+#[derive(Clone, Debug)]
+pub struct Delivery {
+    pub webhook: WebhookId,
+    pub event: String,
+    pub attempt: u32,
+    pub at: u64,
+    pub status: DeliveryStatus,
+}
+
+/// An event that could not be delivered after `MAX_ATTEMPTS` attempts, or
+/// fewer when the deliveries waiting were too many or the API shut down.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    pub webhook: WebhookId,
+    pub event: String,
+    pub payload: String,
+    pub attempts: u32,
+    pub failed_at: u64,
+}
+
+/// Wait before the attempt following `attempt`, doubling every time.
+pub fn retry_delay(base: Duration, attempt: u32) -> Duration {
+    base * 2u32.pow(attempt.saturating_sub(1))
+}
+
+#[cfg(test)]
+impl WebhookUrl {
+    pub fn local() -> Self {
+        Self("http://localhost:9000/hooks".to_string())
+    }
+}
+
+#[cfg(test)]
+impl WebhookSecret {
+    pub fn test() -> Self {
+        Self("It's a secret to everybody".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_only_accept_http_and_https_urls() {
+        assert!(WebhookUrl::try_from("http://localhost:9000/hooks".to_string()).is_ok());
+        assert!(WebhookUrl::try_from(" http://example.com ".to_string()).is_ok());
+        assert!(WebhookUrl::try_from("https://example.com/hooks".to_string()).is_ok());
+        assert!(WebhookUrl::try_from("http://".to_string()).is_err());
+        assert!(WebhookUrl::try_from("https:///hooks".to_string()).is_err());
+        assert!(WebhookUrl::try_from("ftp://example.com".to_string()).is_err());
+        assert!(WebhookUrl::try_from("example.com".to_string()).is_err());
+    }
+
+    #[test]
+    fn it_should_sign_with_hmac_sha256() {
+        let secret = WebhookSecret::try_from("key".to_string()).ok().unwrap();
+
+        assert_eq!(
+            secret.sign(b"The quick brown fox jumps over the lazy dog"),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn it_should_double_the_delay_between_attempts() {
+        let base = Duration::from_secs(1);
+
+        assert_eq!(retry_delay(base, 1), Duration::from_secs(1));
+        assert_eq!(retry_delay(base, 2), Duration::from_secs(2));
+        assert_eq!(retry_delay(base, 4), Duration::from_secs(8));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::config::{LogFormat, LogLevel};
+use crate::domain::rng::Rng;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Longest `X-Request-Id` adopted from a client.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+static REQUEST_COUNT: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Id of the request the thread is serving, added to every line it logs.
+    static REQUEST_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+struct Logger {
+    level: LogLevel,
+    format: LogFormat,
+}
+
+/// Nothing is logged until the logger is set up, which the tests never do.
+pub fn init(level: LogLevel, format: LogFormat) {
+    LOGGER.set(Logger { level, format }).ok();
+}
+
+/// Writes a line to stderr with the time, the level, the message, the
+/// request being served, if any, and the fields.
+pub fn log(level: LogLevel, message: &str, fields: &[(&str, Value)]) {
+    let logger = match LOGGER.get() {
+        Some(logger) if level <= logger.level => logger,
+        _ => return,
+    };
+
+    let at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default();
+    let request_id = REQUEST_ID.with(|id| id.borrow().clone());
+
+    eprintln!(
+        "{}",
+        render(logger.format, at, level, message, request_id, fields)
+    );
+}
+
+fn render(
+    format: LogFormat,
+    at: f64,
+    level: LogLevel,
+    message: &str,
+    request_id: Option<String>,
+    fields: &[(&str, Value)],
+) -> String {
+    let mut line = vec![
+        ("ts", Value::from((at * 1000.0).round() / 1000.0)),
+        ("level", Value::from(level.to_string())),
+        ("msg", Value::from(message)),
+    ];
+    if let Some(id) = request_id {
+        line.push(("request_id", Value::from(id)));
+    }
+    line.extend(fields.iter().cloned());
+
+    match format {
+        // Written by hand so that the keys keep their order.
+        LogFormat::Json => format!(
+            "{{{}}}",
+            line.into_iter()
+                .map(|(k, v)| format!("{}:{}", Value::from(k), v))
+                .collect::<Vec<String>>()
+                .join(",")
+        ),
+        LogFormat::Logfmt => line
+            .into_iter()
+            .map(|(k, v)| format!("{}={}", k, logfmt_value(v)))
+            .collect::<Vec<String>>()
+            .join(" "),
+    }
+}
+
+/// Strings are quoted when they contain spaces, quotes or `=`.
+fn logfmt_value(value: Value) -> String {
+    match value {
+        Value::String(s) if s.is_empty() || s.contains([' ', '"', '=']) => {
+            Value::String(s).to_string()
+        }
+        Value::String(s) => s,
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// The id the client gave in `X-Request-Id`, when it is printable and not
+/// too long, or a new one.
+pub fn request_id(header: Option<&str>) -> String {
+    match header.map(str::trim) {
+        Some(id)
+            if !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id.chars().all(|c| c.is_ascii_graphic()) =>
+        {
+            id.to_string()
+        }
+        _ => {
+            let seed = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default();
+            let count = REQUEST_COUNT.fetch_add(1, Ordering::Relaxed);
+            format!("{:016x}", Rng::new(seed ^ count).next_u64())
+        }
+    }
+}
+
+/// Tags what the thread logs with a request id until it is dropped.
+pub struct RequestScope {
+    previous: Option<String>,
+}
+
+impl RequestScope {
+    pub fn enter(id: &str) -> Self {
+        let previous = REQUEST_ID.with(|current| current.replace(Some(id.to_string())));
+        Self { previous }
+    }
+}
+
+impl Drop for RequestScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        REQUEST_ID.with(|current| {
+            current.replace(previous);
+        });
+    }
+}
+
+/// Logs at the debug level how long it lived, so that the work done for a
+/// request can be followed from the API down to the repository.
+pub struct Span {
+    name: &'static str,
+    started: Instant,
+}
+
+impl Span {
+    pub fn enter(name: &'static str) -> Self {
+        Self {
+            name,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        log(
+            LogLevel::Debug,
+            "span",
+            &[
+                ("span", Value::from(self.name)),
+                ("latency_ms", latency_ms(self.started)),
+            ],
+        );
+    }
+}
+
+/// Time elapsed since `started`, in milliseconds with a microsecond precision.
+pub fn latency_ms(started: Instant) -> Value {
+    Value::from((started.elapsed().as_secs_f64() * 1_000_000.0).round() / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_render_logfmt_quoting_the_values_with_spaces() {
+        let line = render(
+            LogFormat::Logfmt,
+            1.5,
+            LogLevel::Info,
+            "request",
+            Some("abc".to_string()),
+            &[
+                ("path", Value::from("/by-name/Mr Mime")),
+                ("status", Value::from(200)),
+            ],
+        );
+
+        assert_eq!(
+            line,
+            "ts=1.5 level=info msg=request request_id=abc path=\"/by-name/Mr Mime\" status=200"
+        );
+    }
+
+    #[test]
+    fn it_should_render_json() {
+        let line = render(
+            LogFormat::Json,
+            1.5,
+            LogLevel::Error,
+            "request",
+            None,
+            &[("status", Value::from(500))],
+        );
+
+        assert!(line.starts_with("{\"ts\":1.5,\"level\":\"error\",\"msg\":\"request\""));
+        let json = serde_json::from_str::<Value>(&line).unwrap();
+        assert_eq!(json["level"], "error");
+        assert_eq!(json["status"], 500);
+        assert!(json.get("request_id").is_none());
+    }
+
+    #[test]
+    fn it_should_only_adopt_the_printable_request_ids() {
+        assert_eq!(request_id(Some("trace-42")), "trace-42");
+        assert_eq!(request_id(None).len(), 16);
+        assert_eq!(request_id(Some("two words")).len(), 16);
+        assert_eq!(request_id(Some(&"x".repeat(200))).len(), 16);
+        assert_ne!(request_id(None), request_id(None));
+    }
+}
@@ -1,18 +1,51 @@
 mod api;
 mod cli;
+mod config;
 mod domain;
+mod logger;
+mod metrics;
+mod notifiers;
+mod rate_limit;
 mod repositories;
 
+use std::io::Write;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
+use api::ServerConfig;
+use config::{Config, Flags, LogLevel};
+use domain::authorize::Credentials;
+use domain::events::{Event, EventBus, Subscriber};
+use domain::generation::{Generation, Generations, RegionalDex};
+use domain::jwt::JwtVerifier;
+use domain::purge_trash;
+use domain::team::TeamRules;
+use logger::Span;
+use notifiers::webhook::WebhookDispatcher;
+use repositories::api_key::{ApiKeyRepository, InMemoryApiKeyRepository, SqliteApiKeyRepository};
+use repositories::audit::{
+    AuditRepository, AuditedRepository, InMemoryAuditRepository, SqliteAuditRepository,
+};
+use repositories::cached::CachedRepository;
 use repositories::pokemon::{InMemoryRepository, Repository};
+use repositories::progress::{
+    InMemoryProgressRepository, ProgressRepository, SqliteProgressRepository,
+};
+use repositories::team::{InMemoryTeamRepository, SqliteTeamRepository, TeamRepository};
+use repositories::webhook::{
+    InMemoryWebhookRepository, SqliteWebhookRepository, WebhookRepository,
+};
 
 #[macro_use]
 extern crate rouille;
 extern crate clap;
 extern crate serde;
 
-use clap::{crate_authors, crate_name, crate_version, App, Arg, SubCommand};
+use serde::Deserialize;
+use serde_json::Value;
+
+use clap::{crate_authors, crate_name, crate_version, App, Arg, ArgMatches, SubCommand};
 
 use crate::repositories::pokemon::SqliteRepository;
 
@@ -21,27 +54,401 @@ fn main() {
         .version(crate_version!())
         .author(crate_authors!())
         .subcommand(SubCommand::with_name("cli").about("Use cli <name>!"))
-        .subcommand(SubCommand::with_name("api").about("Use api <name>!"))
-        .arg(Arg::with_name("sqlite").long("sqlite").value_name("PATH"))
+        .subcommand(
+            SubCommand::with_name("api")
+                .about("Use api <name>!")
+                .arg(
+                    Arg::with_name("host")
+                        .long("host")
+                        .value_name("HOST")
+                        .help("Address to listen on, localhost by default"),
+                )
+                .arg(
+                    Arg::with_name("port")
+                        .long("port")
+                        .value_name("PORT")
+                        .help("Port to listen on, 8080 by default, or any free one with 0"),
+                )
+                .arg(
+                    Arg::with_name("threads")
+                        .long("threads")
+                        .value_name("COUNT")
+                        .help("Number of requests handled at once, one thread per request by default"),
+                )
+                .arg(
+                    Arg::with_name("shutdown-timeout")
+                        .long("shutdown-timeout")
+                        .value_name("SECONDS")
+                        .help("How long the requests in flight are given to finish on SIGINT or SIGTERM, 30 by default"),
+                )
+                .arg(
+                    Arg::with_name("rate-limit-read")
+                        .long("rate-limit-read")
+                        .value_name("PER_MINUTE")
+                        .help("Requests a client can make per minute to read the Pokedex, 0 for no limit, 600 by default"),
+                )
+                .arg(
+                    Arg::with_name("rate-limit-write")
+                        .long("rate-limit-write")
+                        .value_name("PER_MINUTE")
+                        .help("Requests a client can make per minute to change the Pokedex, 0 for no limit, 60 by default"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("config")
+                .about("Inspect the configuration")
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about("Print the effective settings and where each comes from"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("keys")
+                .about("Manage the API keys, stored in the database")
+                .subcommand(
+                    SubCommand::with_name("issue")
+                        .about("Issue a key and print it")
+                        .arg(
+                            Arg::with_name("name")
+                                .long("name")
+                                .value_name("NAME")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("role")
+                                .long("role")
+                                .value_name("ROLE")
+                                .possible_values(&["reader", "editor", "admin"])
+                                .required(true),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("revoke")
+                        .about("Revoke a key")
+                        .arg(Arg::with_name("id").value_name("ID").required(true)),
+                )
+                .subcommand(SubCommand::with_name("list").about("List the keys issued")),
+        )
+        .subcommand(
+            SubCommand::with_name("purge")
+                .about("Purge the Pokemons deleted longer ago than the trash retention"),
+        )
+        .subcommand(
+            SubCommand::with_name("history")
+                .about("Print every change made to a Pokemon")
+                .arg(Arg::with_name("number").value_name("NUMBER").required(true)),
+        )
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("PATH")
+                .help("TOML file with the settings, overridden by the POKEMON_* environment variables and the flags"),
+        )
+        .arg(
+            Arg::with_name("backend")
+                .long("backend")
+                .value_name("BACKEND")
+                .possible_values(&["memory", "sqlite"])
+                .help("Where the Pokemon are stored, sqlite when a database is given"),
+        )
+        .arg(
+            Arg::with_name("sqlite")
+                .long("sqlite")
+                .value_name("PATH")
+                .help("SQLite database"),
+        )
+        .arg(
+            Arg::with_name("log-level")
+                .long("log-level")
+                .value_name("LEVEL")
+                .possible_values(&["error", "warn", "info", "debug"])
+                .help("Least severe messages logged, info by default"),
+        )
+        .arg(
+            Arg::with_name("log-format")
+                .long("log-format")
+                .value_name("FORMAT")
+                .possible_values(&["logfmt", "json"])
+                .help("How the logs are written to stderr, logfmt by default"),
+        )
+        .arg(
+            Arg::with_name("cache-ttl")
+                .long("cache-ttl")
+                .value_name("SECONDS")
+                .help("How long the reads of the Pokedex stay cached, no caching with 0, the default"),
+        )
+        .arg(
+            Arg::with_name("cache-capacity")
+                .long("cache-capacity")
+                .value_name("COUNT")
+                .help("Most reads kept in the cache, 1000 by default"),
+        )
+        .arg(
+            Arg::with_name("api-key")
+                .long("api-key")
+                .value_name("KEY")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Key allowed everything by the API, besides the issued ones, can be repeated"),
+        )
+        .arg(
+            Arg::with_name("jwt-secret")
+                .long("jwt-secret")
+                .value_name("SECRET")
+                .help("Secret of the HS256 tokens accepted by the API, none being accepted when not set"),
+        )
+        .arg(
+            Arg::with_name("jwt-audience")
+                .long("jwt-audience")
+                .value_name("AUDIENCE")
+                .help("Audience the tokens must be issued for"),
+        )
+        .arg(
+            Arg::with_name("jwt-role-claim")
+                .long("jwt-role-claim")
+                .value_name("CLAIM")
+                .help("Claim of the tokens holding the role [default: role]"),
+        )
+        .arg(
+            Arg::with_name("level-cap")
+                .long("level-cap")
+                .value_name("LEVEL")
+                .help("Highest level allowed in a team"),
+        )
+        .arg(
+            Arg::with_name("species-clause")
+                .long("species-clause")
+                .help("Forbid a team from having two Pokemon of the same species"),
+        )
+        .arg(
+            Arg::with_name("lang")
+                .long("lang")
+                .value_name("LANGUAGE")
+                .help("Language of the Pokemon names in the cli, English by default"),
+        )
+        .arg(
+            Arg::with_name("generations")
+                .long("generations")
+                .value_name("PATH")
+                .help("JSON file describing the generations and regional Pokedexes"),
+        )
+        .arg(
+            Arg::with_name("trash-retention")
+                .long("trash-retention")
+                .value_name("DAYS")
+                .help("How long deleted Pokemon stay in the trash before being purged, 30 days by default"),
+        )
         .get_matches();
 
-    let repo = build_repo(matches.value_of("sqlite"));
+    let config = build_config(&matches);
+    logger::init(config.log_level.value, config.log_format.value);
+    if let ("config", Some(_)) = matches.subcommand() {
+        cli::show_config::run(&config);
+        return;
+    }
+
+    let store = build_repo(config.sqlite_path());
+    let repo = cached(
+        store.clone(),
+        config.cache_ttl.value,
+        config.cache_capacity.value,
+    );
+    let audit_repo = build_audit_repo(config.sqlite_path());
+    let team_repo = build_team_repo(config.sqlite_path());
+    let progress_repo = build_progress_repo(config.sqlite_path());
+    let rules = TeamRules {
+        species_clause: config.species_clause.value,
+        level_cap: config.level_cap.value,
+    };
+    let generations = match config.generations.value.as_deref() {
+        Some(path) => build_generations(path),
+        None => Generations::default(),
+    };
+    // Retention of the trash, in seconds.
+    let retention = config.trash_retention.value.saturating_mul(24 * 60 * 60);
 
     match matches.subcommand() {
         ("cli", Some(_)) => {
-            run_cli(repo);
+            let languages = config.language.value.clone().into_iter().collect();
+            let actor = format!("cli:{}", std::env::var("USER").unwrap_or_default());
+            let repo = audited(&repo, &store, &audit_repo, &actor);
+            let webhook_repo = build_webhook_repo(config.sqlite_path());
+            let events = Arc::new(EventBus::synchronous(build_subscribers(&webhook_repo)));
+            run_cli(
+                repo,
+                events.clone(),
+                team_repo,
+                progress_repo,
+                rules,
+                &generations,
+                languages,
+            );
+
+            // The webhooks of the last changes are delivered before exiting.
+            if !events.flush(Duration::from_secs(config.shutdown_timeout.value)) {
+                logger::log(LogLevel::Warn, "events not delivered before exiting", &[]);
+            }
         }
         ("api", Some(_)) => {
-            run_api(repo);
+            let server = ServerConfig {
+                host: config.host.value.clone(),
+                port: config.port.value,
+                threads: config.threads.value,
+                shutdown_timeout: Duration::from_secs(config.shutdown_timeout.value),
+                rate_limits: (config.rate_limit_read.value, config.rate_limit_write.value),
+                credentials: Credentials {
+                    admin_keys: config.api_keys.value.clone(),
+                    jwt: config.jwt_secret.value.as_deref().map(|secret| {
+                        JwtVerifier::new(
+                            secret,
+                            config.jwt_audience.value.clone(),
+                            &config.jwt_role_claim.value,
+                        )
+                    }),
+                },
+            };
+            // The server runs for a long time, so the trash is purged on start and
+            // hourly.
+            purge_hourly(audited(&repo, &store, &audit_repo, "system"), retention);
+            let webhook_repo = build_webhook_repo(config.sqlite_path());
+            let api_key_repo = build_api_key_repo(config.sqlite_path());
+            run_api(
+                &server,
+                repo.clone(),
+                store.clone(),
+                audit_repo.clone(),
+                webhook_repo.clone(),
+                api_key_repo.clone(),
+                team_repo.clone(),
+                progress_repo.clone(),
+                rules,
+                generations,
+            );
+
+            std::io::stdout().flush().ok();
+            let closed = [
+                ("pokemon", repo.close().is_ok()),
+                ("audit", audit_repo.close().is_ok()),
+                ("webhook", webhook_repo.close().is_ok()),
+                ("API key", api_key_repo.close().is_ok()),
+                ("team", team_repo.close().is_ok()),
+                ("progress", progress_repo.close().is_ok()),
+            ];
+            for (name, _) in closed.iter().filter(|(_, ok)| !ok) {
+                logger::log(
+                    LogLevel::Error,
+                    "repository not closed",
+                    &[("repository", Value::from(*name))],
+                );
+            }
+        }
+        ("keys", Some(keys)) => {
+            // Keys issued in memory would be lost as soon as the command exits.
+            if config.sqlite_path().is_none() {
+                println!("API keys can only be managed in a SQLite database");
+                return;
+            }
+            let api_key_repo = build_api_key_repo(config.sqlite_path());
+            match keys.subcommand() {
+                ("issue", Some(issue)) => cli::api_keys::issue(
+                    api_key_repo,
+                    issue.value_of("name").unwrap_or_default(),
+                    issue.value_of("role").unwrap_or_default(),
+                ),
+                ("revoke", Some(revoke)) => {
+                    cli::api_keys::revoke(api_key_repo, revoke.value_of("id").unwrap_or_default())
+                }
+                _ => cli::api_keys::list(api_key_repo),
+            }
+        }
+        ("history", Some(history)) => {
+            cli::history::run(audit_repo, history.value_of("number").unwrap_or_default());
+        }
+        ("purge", Some(_)) => {
+            cli::purge_trash::run(audited(&repo, &store, &audit_repo, "system"), retention);
         }
         _ => unreachable!(),
     };
 }
 
+/// The settings of the `--config` file, or of the one named by `POKEMON_CONFIG`,
+/// overridden by the environment, then by the flags.
+fn build_config(matches: &ArgMatches) -> Config {
+    let path = matches
+        .value_of("config")
+        .map(String::from)
+        .or_else(|| std::env::var("POKEMON_CONFIG").ok());
+    let content = match path.as_deref().map(std::fs::read_to_string) {
+        Some(Ok(content)) => Some(content),
+        Some(Err(_)) => panic!("Error while reading the config file"),
+        None => None,
+    };
+
+    let api = matches.subcommand_matches("api");
+    let flags = Flags {
+        backend: matches.value_of("backend"),
+        sqlite: matches.value_of("sqlite"),
+        host: api.and_then(|a| a.value_of("host")),
+        port: api.and_then(|a| a.value_of("port")),
+        threads: api.and_then(|a| a.value_of("threads")),
+        shutdown_timeout: api.and_then(|a| a.value_of("shutdown-timeout")),
+        rate_limit_read: api.and_then(|a| a.value_of("rate-limit-read")),
+        rate_limit_write: api.and_then(|a| a.value_of("rate-limit-write")),
+        log_level: matches.value_of("log-level"),
+        log_format: matches.value_of("log-format"),
+        cache_ttl: matches.value_of("cache-ttl"),
+        cache_capacity: matches.value_of("cache-capacity"),
+        api_keys: matches.values_of("api-key").map(Iterator::collect),
+        jwt_secret: matches.value_of("jwt-secret"),
+        jwt_audience: matches.value_of("jwt-audience"),
+        jwt_role_claim: matches.value_of("jwt-role-claim"),
+        generations: matches.value_of("generations"),
+        level_cap: matches.value_of("level-cap"),
+        species_clause: Some("true").filter(|_| matches.is_present("species-clause")),
+        trash_retention: matches.value_of("trash-retention"),
+        language: matches.value_of("lang"),
+    };
+
+    let file = match (&path, &content) {
+        (Some(path), Some(content)) => Some((path.as_str(), content.as_str())),
+        _ => None,
+    };
+    match Config::load(file, &|name| std::env::var(name).ok(), flags) {
+        Ok(config) => config,
+        Err(message) => panic!("{}", message),
+    }
+}
+
+/// The reads are cached unless `ttl` is `0`.
+fn cached(repo: Arc<dyn Repository>, ttl: u64, capacity: usize) -> Arc<dyn Repository> {
+    match ttl {
+        0 => repo,
+        ttl => Arc::new(CachedRepository::new(
+            repo,
+            Duration::from_secs(ttl),
+            capacity,
+        )),
+    }
+}
+
 fn build_repo(sqlite_value: Option<&str>) -> Arc<dyn Repository> {
     if let Some(path) = sqlite_value {
+        // Rows written before the current validation rules are left out of the
+        // reads rather than failing them.
         match SqliteRepository::try_new(path) {
-            Ok(repo) => return Arc::new(repo),
+            Ok(repo) => {
+                return Arc::new(repo.on_invalid_row(|row, number, form| {
+                    logger::log(
+                        LogLevel::Warn,
+                        "invalid row skipped",
+                        &[
+                            ("row", Value::from(row)),
+                            ("number", Value::from(number)),
+                            ("form", Value::from(form)),
+                        ],
+                    )
+                }))
+            }
             Err(_) => panic!("Error while creating sqlite repo"),
         }
     }
@@ -49,13 +456,240 @@ fn build_repo(sqlite_value: Option<&str>) -> Arc<dyn Repository> {
     Arc::new(InMemoryRepository::new())
 }
 
-fn run_api(repo: Arc<dyn Repository>) {
-    let url = "localhost:8080";
-    println!("http://{}", &url);
+fn build_audit_repo(sqlite_value: Option<&str>) -> Arc<dyn AuditRepository> {
+    if let Some(path) = sqlite_value {
+        match SqliteAuditRepository::try_new(path) {
+            Ok(repo) => return Arc::new(repo),
+            Err(_) => panic!("Error while creating sqlite audit repo"),
+        }
+    }
+
+    Arc::new(InMemoryAuditRepository::new())
+}
+
+/// Records the changes made through the returned repository on behalf of `actor`,
+/// reading the state before them from `store`, under the cache.
+fn audited(
+    repo: &Arc<dyn Repository>,
+    store: &Arc<dyn Repository>,
+    audit_repo: &Arc<dyn AuditRepository>,
+    actor: &str,
+) -> Arc<dyn Repository> {
+    Arc::new(
+        AuditedRepository::new(repo.clone(), audit_repo.clone(), actor.to_string())
+            .with_snapshots(store.clone()),
+    )
+}
+
+fn build_team_repo(sqlite_value: Option<&str>) -> Arc<dyn TeamRepository> {
+    if let Some(path) = sqlite_value {
+        match SqliteTeamRepository::try_new(path) {
+            Ok(repo) => return Arc::new(repo),
+            Err(_) => panic!("Error while creating sqlite team repo"),
+        }
+    }
+
+    Arc::new(InMemoryTeamRepository::new())
+}
+
+fn build_api_key_repo(sqlite_value: Option<&str>) -> Arc<dyn ApiKeyRepository> {
+    if let Some(path) = sqlite_value {
+        match SqliteApiKeyRepository::try_new(path) {
+            Ok(repo) => return Arc::new(repo),
+            Err(_) => panic!("Error while creating sqlite API key repo"),
+        }
+    }
+
+    Arc::new(InMemoryApiKeyRepository::new())
+}
+
+fn build_webhook_repo(sqlite_value: Option<&str>) -> Arc<dyn WebhookRepository> {
+    if let Some(path) = sqlite_value {
+        match SqliteWebhookRepository::try_new(path) {
+            Ok(repo) => return Arc::new(repo),
+            Err(_) => panic!("Error while creating sqlite webhook repo"),
+        }
+    }
+
+    Arc::new(InMemoryWebhookRepository::new())
+}
+
+fn build_progress_repo(sqlite_value: Option<&str>) -> Arc<dyn ProgressRepository> {
+    if let Some(path) = sqlite_value {
+        match SqliteProgressRepository::try_new(path) {
+            Ok(repo) => return Arc::new(repo),
+            Err(_) => panic!("Error while creating sqlite progress repo"),
+        }
+    }
+
+    Arc::new(InMemoryProgressRepository::new())
+}
+
+#[derive(Deserialize)]
+struct GenerationsFile {
+    generations: Vec<GenerationEntry>,
+    #[serde(default)]
+    regional_dexes: Vec<RegionalDexEntry>,
+}
+
+#[derive(Deserialize)]
+struct GenerationEntry {
+    number: u8,
+    region: String,
+    first: u16,
+    last: u16,
+}
+
+#[derive(Deserialize)]
+struct RegionalDexEntry {
+    region: String,
+    numbers: Vec<u16>,
+}
+
+fn build_generations(path: &str) -> Generations {
+    let file = match std::fs::read_to_string(path) {
+        Ok(file) => file,
+        Err(_) => panic!("Error while reading the generations file"),
+    };
+
+    let file: GenerationsFile = match serde_json::from_str(&file) {
+        Ok(file) => file,
+        Err(_) => panic!("The generations file is not valid JSON"),
+    };
+
+    let generations = file
+        .generations
+        .into_iter()
+        .map(|g| Generation {
+            number: g.number,
+            region: g.region,
+            first: g.first,
+            last: g.last,
+        })
+        .collect();
+    let regional_dexes = file
+        .regional_dexes
+        .into_iter()
+        .map(|d| RegionalDex {
+            region: d.region,
+            numbers: d.numbers,
+        })
+        .collect();
+
+    match Generations::try_new(generations, regional_dexes) {
+        Ok(generations) => generations,
+        Err(_) => panic!("The generations must be contiguous and start at number 1"),
+    }
+}
+
+/// Logs a line for every change made through the API or the cli.
+struct EventLogger;
+
+impl Subscriber for EventLogger {
+    fn handle(&self, event: &Event) {
+        let (message, number, form, name) = match event {
+            Event::PokemonCreated(pokemon) => (
+                "pokemon created",
+                pokemon.number.to_u16(),
+                &pokemon.form,
+                Some(&pokemon.name),
+            ),
+            Event::PokemonUpdated(pokemon) => (
+                "pokemon updated",
+                pokemon.number.to_u16(),
+                &pokemon.form,
+                Some(&pokemon.name),
+            ),
+            Event::PokemonDeleted { number, form } => {
+                ("pokemon deleted", number.to_u16(), form, None)
+            }
+        };
+
+        let mut fields = vec![
+            ("number", Value::from(number)),
+            ("form", Value::from(form.to_string())),
+        ];
+        if let Some(name) = name {
+            fields.push(("name", Value::from(name.to_string())));
+        }
+        logger::log(LogLevel::Info, message, &fields);
+    }
+}
+
+fn purge(repo: Arc<dyn Repository>, retention: u64) {
+    let _span = Span::enter("purge_trash");
+    match purge_trash::execute(repo, purge_trash::Request { retention }) {
+        Ok(res) if res.purged > 0 => logger::log(
+            LogLevel::Info,
+            "trash purged",
+            &[("purged", Value::from(res.purged))],
+        ),
+        Ok(_) => {}
+        Err(purge_trash::Error::Unknown) => logger::log(LogLevel::Error, "trash not purged", &[]),
+    }
+}
+
+fn purge_hourly(repo: Arc<dyn Repository>, retention: u64) {
+    thread::spawn(move || loop {
+        purge(repo.clone(), retention);
+        thread::sleep(Duration::from_secs(60 * 60));
+    });
+}
+
+/// Whatever is done on every change, wherever it is made.
+fn build_subscribers(webhook_repo: &Arc<dyn WebhookRepository>) -> Vec<Arc<dyn Subscriber>> {
+    vec![
+        Arc::new(EventLogger),
+        Arc::new(WebhookDispatcher::new(webhook_repo.clone())),
+    ]
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_api(
+    server: &ServerConfig,
+    repo: Arc<dyn Repository>,
+    store: Arc<dyn Repository>,
+    audit_repo: Arc<dyn AuditRepository>,
+    webhook_repo: Arc<dyn WebhookRepository>,
+    api_key_repo: Arc<dyn ApiKeyRepository>,
+    team_repo: Arc<dyn TeamRepository>,
+    progress_repo: Arc<dyn ProgressRepository>,
+    rules: TeamRules,
+    generations: Generations,
+) {
+    let subscribers = build_subscribers(&webhook_repo);
 
-    api::serve(url, repo);
+    api::serve(
+        server,
+        repo,
+        store,
+        audit_repo,
+        subscribers,
+        team_repo,
+        progress_repo,
+        webhook_repo,
+        api_key_repo,
+        rules,
+        Arc::new(generations),
+    );
 }
 
-fn run_cli(repo: Arc<dyn Repository>) {
-    cli::run(repo);
+fn run_cli(
+    repo: Arc<dyn Repository>,
+    events: Arc<EventBus>,
+    team_repo: Arc<dyn TeamRepository>,
+    progress_repo: Arc<dyn ProgressRepository>,
+    rules: TeamRules,
+    generations: &Generations,
+    languages: Vec<String>,
+) {
+    cli::run(
+        repo,
+        events,
+        team_repo,
+        progress_repo,
+        rules,
+        generations,
+        languages,
+    );
 }
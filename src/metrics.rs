@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Upper bounds of the latency buckets, in seconds, the ones Prometheus
+/// clients use by default.
+const BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Default)]
+struct Histogram {
+    /// Observations at or below each bound, not cumulated.
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        if let Some(i) = BUCKETS.iter().position(|bound| seconds <= *bound) {
+            self.buckets[i] += 1;
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let mut cumulated = 0;
+        for (bound, count) in BUCKETS.iter().zip(self.buckets.iter()) {
+            cumulated += count;
+            writeln!(
+                out,
+                "{}_bucket{{{},le=\"{}\"}} {}",
+                name, labels, bound, cumulated
+            )
+            .ok();
+        }
+        writeln!(
+            out,
+            "{}_bucket{{{},le=\"+Inf\"}} {}",
+            name, labels, self.count
+        )
+        .ok();
+        writeln!(out, "{}_sum{{{}}} {}", name, labels, self.sum).ok();
+        writeln!(out, "{}_count{{{}}} {}", name, labels, self.count).ok();
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// By method, route and status code.
+    requests: BTreeMap<(String, String, u16), Histogram>,
+    /// By repository operation.
+    operations: BTreeMap<&'static str, Histogram>,
+    /// By repository operation and error variant.
+    errors: BTreeMap<(&'static str, &'static str), u64>,
+}
+
+/// A label value escaped as the text format requires.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Measures of the API since it started, rendered in the Prometheus text
+/// format.
+pub struct Metrics {
+    started: Instant,
+    state: Mutex<State>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// `route` is the pattern the path matched, never the path itself, so
+    /// that the number of series stays bounded.
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        if let Ok(mut state) = self.state.lock() {
+            state
+                .requests
+                .entry((method.to_string(), route.to_string(), status))
+                .or_default()
+                .observe(elapsed);
+        }
+    }
+
+    /// `error` is the variant the operation failed with, if it did.
+    pub fn observe_operation(
+        &self,
+        operation: &'static str,
+        elapsed: Duration,
+        error: Option<&'static str>,
+    ) {
+        if let Ok(mut state) = self.state.lock() {
+            state
+                .operations
+                .entry(operation)
+                .or_default()
+                .observe(elapsed);
+            if let Some(error) = error {
+                *state.errors.entry((operation, error)).or_default() += 1;
+            }
+        }
+    }
+
+    /// The number of Pokemons is only reported when it could be counted.
+    pub fn render(&self, pokemons: Option<usize>) -> String {
+        let state = match self.state.lock() {
+            Ok(state) => state,
+            _ => return String::new(),
+        };
+        let mut out = String::new();
+
+        out.push_str("# HELP pokedex_http_requests_total Requests served.\n");
+        out.push_str("# TYPE pokedex_http_requests_total counter\n");
+        for ((method, route, status), histogram) in state.requests.iter() {
+            writeln!(
+                out,
+                "pokedex_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                escape(method),
+                escape(route),
+                status,
+                histogram.count
+            )
+            .ok();
+        }
+
+        out.push_str(
+            "# HELP pokedex_http_request_duration_seconds Time taken to serve a request.\n",
+        );
+        out.push_str("# TYPE pokedex_http_request_duration_seconds histogram\n");
+        for ((method, route, status), histogram) in state.requests.iter() {
+            let labels = format!(
+                "method=\"{}\",route=\"{}\",status=\"{}\"",
+                escape(method),
+                escape(route),
+                status
+            );
+            histogram.render(&mut out, "pokedex_http_request_duration_seconds", &labels);
+        }
+
+        out.push_str("# HELP pokedex_repository_operation_duration_seconds Time taken by a repository operation.\n");
+        out.push_str("# TYPE pokedex_repository_operation_duration_seconds histogram\n");
+        for (operation, histogram) in state.operations.iter() {
+            let labels = format!("operation=\"{}\"", operation);
+            histogram.render(
+                &mut out,
+                "pokedex_repository_operation_duration_seconds",
+                &labels,
+            );
+        }
+
+        out.push_str("# HELP pokedex_repository_errors_total Repository operations that failed.\n");
+        out.push_str("# TYPE pokedex_repository_errors_total counter\n");
+        for ((operation, error), count) in state.errors.iter() {
+            writeln!(
+                out,
+                "pokedex_repository_errors_total{{operation=\"{}\",error=\"{}\"}} {}",
+                operation, error, count
+            )
+            .ok();
+        }
+
+        if let Some(pokemons) = pokemons {
+            out.push_str("# HELP pokedex_pokemons Pokemons in the Pokedex, trash excluded.\n");
+            out.push_str("# TYPE pokedex_pokemons gauge\n");
+            writeln!(out, "pokedex_pokemons {}", pokemons).ok();
+        }
+
+        out.push_str("# HELP process_uptime_seconds Time since the API started.\n");
+        out.push_str("# TYPE process_uptime_seconds gauge\n");
+        writeln!(
+            out,
+            "process_uptime_seconds {}",
+            self.started.elapsed().as_secs_f64()
+        )
+        .ok();
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_count_the_requests_in_cumulated_buckets() {
+        let metrics = Metrics::new();
+
+        metrics.observe_request("GET", "/{number}", 200, Duration::from_millis(3));
+        metrics.observe_request("GET", "/{number}", 200, Duration::from_millis(30));
+        metrics.observe_request("GET", "/{number}", 404, Duration::from_millis(3));
+
+        let out = metrics.render(None);
+        let labels = "method=\"GET\",route=\"/{number}\",status=\"200\"";
+        assert!(out.contains(&format!("pokedex_http_requests_total{{{}}} 2\n", labels)));
+        assert!(out.contains(&format!(
+            "pokedex_http_request_duration_seconds_bucket{{{},le=\"0.005\"}} 1\n",
+            labels
+        )));
+        assert!(out.contains(&format!(
+            "pokedex_http_request_duration_seconds_bucket{{{},le=\"0.05\"}} 2\n",
+            labels
+        )));
+        assert!(out.contains(&format!(
+            "pokedex_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 2\n",
+            labels
+        )));
+        assert!(out.contains("status=\"404\"} 1\n"));
+        assert!(!out.contains("pokedex_pokemons"));
+    }
+
+    #[test]
+    fn it_should_escape_the_label_values() {
+        let metrics = Metrics::new();
+
+        metrics.observe_request("GET", "/a\"b\nc\\d", 404, Duration::from_millis(1));
+
+        let out = metrics.render(None);
+        assert!(out.contains(
+            "pokedex_http_requests_total{method=\"GET\",route=\"/a\\\"b\\nc\\\\d\",status=\"404\"} 1\n"
+        ));
+        assert!(out
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .all(|line| line.starts_with("pokedex_") || line.starts_with("process_")));
+    }
+
+    #[test]
+    fn it_should_count_the_repository_errors_by_variant() {
+        let metrics = Metrics::new();
+
+        metrics.observe_operation("insert", Duration::from_millis(1), None);
+        metrics.observe_operation("insert", Duration::from_millis(1), Some("Conflict"));
+
+        let out = metrics.render(Some(151));
+        assert!(out.contains(
+            "pokedex_repository_operation_duration_seconds_count{operation=\"insert\"} 2\n"
+        ));
+        assert!(out.contains(
+            "pokedex_repository_errors_total{operation=\"insert\",error=\"Conflict\"} 1\n"
+        ));
+        assert!(out.contains("pokedex_pokemons 151\n"));
+        assert!(out.contains("process_uptime_seconds "));
+    }
+}
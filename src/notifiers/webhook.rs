@@ -0,0 +1,609 @@
+use crate::config::LogLevel;
+use crate::domain::clock::now;
+use crate::domain::entities::Pokemon;
+use crate::domain::events::{Event, Subscriber};
+use crate::domain::webhook::{
+    retry_delay, DeadLetter, Delivery, DeliveryStatus, Webhook, WebhookId, MAX_ATTEMPTS,
+};
+use crate::logger;
+use crate::repositories::webhook::{RecordError, WebhookRepository};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Wait before the second attempt at a delivery, doubled before each of the
+/// following ones.
+const RETRY_BASE: Duration = Duration::from_secs(2);
+
+/// How long a receiver has to answer.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize)]
+struct Stats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    speed: u16,
+}
+
+#[derive(Serialize)]
+struct PokemonPayload {
+    number: u16,
+    form: String,
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+impl From<&Pokemon> for PokemonPayload {
+    fn from(pokemon: &Pokemon) -> Self {
+        let stats = pokemon.stats.to_vec_u16();
+        let stat = |i: usize| stats.get(i).copied().unwrap_or_default();
+        Self {
+            number: pokemon.number.to_u16(),
+            form: pokemon.form.to_string(),
+            name: pokemon.name.to_string(),
+            names: pokemon
+                .translations
+                .iter()
+                .map(|(l, n)| (String::from(l.clone()), n.to_string()))
+                .collect(),
+            types: pokemon.types.to_vec_string(),
+            stats: Stats {
+                hp: stat(0),
+                attack: stat(1),
+                defense: stat(2),
+                speed: stat(3),
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DeletedPayload {
+    number: u16,
+    form: String,
+}
+
+#[derive(Serialize)]
+struct Payload<T: Serialize> {
+    event: &'static str,
+    at: u64,
+    pokemon: T,
+}
+
+/// The name of the event and the JSON body sent for it.
+fn payload(event: &Event) -> Result<(&'static str, String), ()> {
+    let at = now();
+    let (name, body) = match event {
+        Event::PokemonCreated(pokemon) => {
+            let event = "pokemon.created";
+            let pokemon = PokemonPayload::from(pokemon);
+            (
+                event,
+                serde_json::to_string(&Payload { event, at, pokemon }),
+            )
+        }
+        Event::PokemonUpdated(pokemon) => {
+            let event = "pokemon.updated";
+            let pokemon = PokemonPayload::from(pokemon);
+            (
+                event,
+                serde_json::to_string(&Payload { event, at, pokemon }),
+            )
+        }
+        Event::PokemonDeleted { number, form } => {
+            let event = "pokemon.deleted";
+            let pokemon = DeletedPayload {
+                number: number.to_u16(),
+                form: form.to_string(),
+            };
+            (
+                event,
+                serde_json::to_string(&Payload { event, at, pokemon }),
+            )
+        }
+    };
+
+    match body {
+        Ok(body) => Ok((name, body)),
+        _ => Err(()),
+    }
+}
+
+/// Deliveries made at once.
+const WORKERS: usize = 4;
+
+/// Deliveries waiting for a worker, beyond which the events are dead-lettered
+/// right away.
+const QUEUE_SIZE: usize = 256;
+
+/// A delivery waiting or running, with what is needed to dead-letter it.
+struct Pending {
+    webhook: WebhookId,
+    event: &'static str,
+    body: String,
+    attempts: u32,
+}
+
+/// The deliveries not over yet, shared by the dispatcher and its workers.
+#[derive(Default)]
+struct Deliveries {
+    pending: Mutex<HashMap<u64, Pending>>,
+    /// Signalled when a delivery is over or the deliveries are abandoned.
+    changed: Condvar,
+    /// Set when the API shuts down before the deliveries are over.
+    abandoned: AtomicBool,
+    next_id: AtomicU64,
+}
+
+impl Deliveries {
+    fn start(&self, pending: Pending) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut lock) = self.pending.lock() {
+            lock.insert(id, pending);
+        }
+        id
+    }
+
+    fn attempted(&self, id: u64, attempt: u32) {
+        if let Some(pending) = self
+            .pending
+            .lock()
+            .ok()
+            .as_mut()
+            .and_then(|lock| lock.get_mut(&id))
+        {
+            pending.attempts = attempt;
+        }
+    }
+
+    /// The delivery, unless it was already dead-lettered on shutdown.
+    fn finish(&self, id: u64) -> Option<Pending> {
+        let pending = self.pending.lock().ok()?.remove(&id);
+        self.changed.notify_all();
+        pending
+    }
+
+    /// Sleeps before the next attempt, and tells whether the deliveries were
+    /// abandoned in the meantime.
+    fn wait(&self, delay: Duration) -> bool {
+        if let Ok(lock) = self.pending.lock() {
+            self.changed
+                .wait_timeout_while(lock, delay, |_| !self.abandoned.load(Ordering::SeqCst))
+                .ok();
+        }
+        self.abandoned.load(Ordering::SeqCst)
+    }
+}
+
+fn dead_letter(repo: &Arc<dyn WebhookRepository>, pending: Pending) {
+    let dead_letter = DeadLetter {
+        webhook: pending.webhook,
+        event: pending.event.to_string(),
+        payload: pending.body,
+        attempts: pending.attempts,
+        failed_at: now(),
+    };
+    if let Err(RecordError::Unknown) = repo.record_dead_letter(dead_letter) {
+        logger::log(LogLevel::Error, "webhook dead letter not recorded", &[]);
+    }
+}
+
+/// Sends every event to the subscribed webhooks as a signed JSON `POST`.
+///
+/// The deliveries are queued for a few workers, each retried with an
+/// exponential backoff and logged attempt by attempt, so a slow or failing
+/// receiver delays the other ones only when every worker waits for one.
+pub struct WebhookDispatcher {
+    repo: Arc<dyn WebhookRepository>,
+    agent: ureq::Agent,
+    retry_base: Duration,
+    queue: SyncSender<Attempts>,
+    deliveries: Arc<Deliveries>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(repo: Arc<dyn WebhookRepository>) -> Self {
+        Self::with_retry_base(repo, RETRY_BASE)
+    }
+
+    fn with_retry_base(repo: Arc<dyn WebhookRepository>, retry_base: Duration) -> Self {
+        let (queue, receiver) = mpsc::sync_channel::<Attempts>(QUEUE_SIZE);
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..WORKERS {
+            let receiver = receiver.clone();
+            // Stops once the dispatcher is dropped.
+            thread::spawn(move || loop {
+                let next = match receiver.lock() {
+                    Ok(receiver) => receiver.recv(),
+                    _ => return,
+                };
+                match next {
+                    Ok(delivery) => delivery.run(),
+                    _ => return,
+                }
+            });
+        }
+
+        Self {
+            repo,
+            agent: ureq::AgentBuilder::new().timeout(TIMEOUT).build(),
+            retry_base,
+            queue,
+            deliveries: Arc::new(Deliveries::default()),
+        }
+    }
+}
+
+impl Subscriber for WebhookDispatcher {
+    fn handle(&self, event: &Event) {
+        let (name, body) = match payload(event) {
+            Ok(payload) => payload,
+            _ => return,
+        };
+
+        let webhooks = match self.repo.fetch_all() {
+            Ok(webhooks) => webhooks,
+            _ => {
+                logger::log(LogLevel::Error, "webhooks not fetched", &[]);
+                return;
+            }
+        };
+
+        for webhook in webhooks {
+            let id = self.deliveries.start(Pending {
+                webhook: webhook.id,
+                event: name,
+                body: body.clone(),
+                attempts: 0,
+            });
+            let delivery = Attempts {
+                id,
+                deliveries: self.deliveries.clone(),
+                repo: self.repo.clone(),
+                agent: self.agent.clone(),
+                retry_base: self.retry_base,
+                webhook,
+                event: name,
+                body: body.clone(),
+            };
+            if self.queue.try_send(delivery).is_err() {
+                logger::log(
+                    LogLevel::Warn,
+                    "too many webhook deliveries waiting, event dead-lettered",
+                    &[("event", Value::from(name))],
+                );
+                if let Some(pending) = self.deliveries.finish(id) {
+                    dead_letter(&self.repo, pending);
+                }
+            }
+        }
+    }
+
+    /// The deliveries still not over at `deadline`, waiting or between two
+    /// attempts, are stopped and dead-lettered.
+    fn flush(&self, deadline: Instant) -> bool {
+        let mut lock = match self.deliveries.pending.lock() {
+            Ok(lock) => lock,
+            _ => return false,
+        };
+        while !lock.is_empty() {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            lock = match self.deliveries.changed.wait_timeout(lock, left) {
+                Ok((lock, _)) => lock,
+                _ => return false,
+            };
+        }
+        if lock.is_empty() {
+            return true;
+        }
+
+        self.deliveries.abandoned.store(true, Ordering::SeqCst);
+        let abandoned = lock
+            .drain()
+            .map(|(_, pending)| pending)
+            .collect::<Vec<Pending>>();
+        drop(lock);
+        self.deliveries.changed.notify_all();
+
+        for pending in abandoned {
+            dead_letter(&self.repo, pending);
+        }
+        false
+    }
+}
+
+/// Delivery of an event to one webhook.
+struct Attempts {
+    id: u64,
+    deliveries: Arc<Deliveries>,
+    repo: Arc<dyn WebhookRepository>,
+    agent: ureq::Agent,
+    retry_base: Duration,
+    webhook: Webhook,
+    event: &'static str,
+    body: String,
+}
+
+impl Attempts {
+    fn run(self) {
+        let signature = format!("sha256={}", self.webhook.secret.sign(self.body.as_bytes()));
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            if self.deliveries.abandoned.load(Ordering::SeqCst) {
+                return;
+            }
+            let status = self.send(&signature);
+            let delivered = status.is_success();
+
+            let logged = self.repo.record_delivery(Delivery {
+                webhook: self.webhook.id,
+                event: self.event.to_string(),
+                attempt,
+                at: now(),
+                status,
+            });
+            if let Err(RecordError::Unknown) = logged {
+                logger::log(LogLevel::Error, "webhook delivery not recorded", &[]);
+            }
+            self.deliveries.attempted(self.id, attempt);
+
+            if delivered {
+                self.deliveries.finish(self.id);
+                return;
+            }
+            if attempt < MAX_ATTEMPTS && self.deliveries.wait(retry_delay(self.retry_base, attempt))
+            {
+                return;
+            }
+        }
+
+        if let Some(pending) = self.deliveries.finish(self.id) {
+            dead_letter(&self.repo, pending);
+        }
+    }
+
+    fn send(&self, signature: &str) -> DeliveryStatus {
+        match self
+            .agent
+            .post(self.webhook.url.as_str())
+            .set("Content-Type", "application/json")
+            .set("X-Pokedex-Event", self.event)
+            .set("X-Pokedex-Signature", signature)
+            .send_string(&self.body)
+        {
+            Ok(res) => DeliveryStatus::Responded(res.status()),
+            Err(ureq::Error::Status(code, _)) => DeliveryStatus::Responded(code),
+            Err(ureq::Error::Transport(transport)) => {
+                DeliveryStatus::Unreachable(transport.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonForm, PokemonNumber};
+    use crate::domain::webhook::{WebhookSecret, WebhookUrl};
+    use crate::repositories::webhook::InMemoryWebhookRepository;
+    use std::convert::TryFrom;
+    use std::io::Read;
+    use std::sync::mpsc::Sender;
+
+    struct Received {
+        event: String,
+        signature: String,
+        body: String,
+    }
+
+    /// A local receiver answering with `statuses` in turn, then with the last
+    /// one, and keeping every request it gets.
+    struct StandIn {
+        url: String,
+        received: Arc<Mutex<Vec<Received>>>,
+        stop: Sender<()>,
+    }
+
+    impl StandIn {
+        fn start(statuses: Vec<u16>) -> Self {
+            let received = Arc::new(Mutex::new(vec![]));
+            let kept = received.clone();
+            let server = rouille::Server::new("localhost:0", move |req| {
+                let mut body = String::new();
+                if let Some(mut data) = req.data() {
+                    data.read_to_string(&mut body).ok();
+                }
+
+                let mut kept = kept.lock().unwrap();
+                kept.push(Received {
+                    event: req
+                        .header("X-Pokedex-Event")
+                        .unwrap_or_default()
+                        .to_string(),
+                    signature: req
+                        .header("X-Pokedex-Signature")
+                        .unwrap_or_default()
+                        .to_string(),
+                    body,
+                });
+                let status = statuses[(kept.len() - 1).min(statuses.len() - 1)];
+                rouille::Response::text("").with_status_code(status)
+            })
+            .unwrap();
+
+            let url = format!("http://{}/hooks", server.server_addr());
+            let (_, stop) = server.stoppable();
+            Self {
+                url,
+                received,
+                stop,
+            }
+        }
+
+        fn subscribe(&self, repo: &InMemoryWebhookRepository) -> Webhook {
+            repo.insert(
+                WebhookUrl::try_from(self.url.clone()).ok().unwrap(),
+                WebhookSecret::test(),
+            )
+            .ok()
+            .unwrap()
+        }
+    }
+
+    impl Drop for StandIn {
+        fn drop(&mut self) {
+            self.stop.send(()).ok();
+        }
+    }
+
+    fn wait_until(done: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    fn deleted() -> Event {
+        Event::PokemonDeleted {
+            number: PokemonNumber::pikachu(),
+            form: PokemonForm::default(),
+        }
+    }
+
+    fn dispatcher(repo: Arc<InMemoryWebhookRepository>) -> WebhookDispatcher {
+        WebhookDispatcher::with_retry_base(repo, Duration::from_millis(1))
+    }
+
+    #[test]
+    fn it_should_post_the_signed_event_to_the_webhooks() {
+        let stand_in = StandIn::start(vec![204]);
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let webhook = stand_in.subscribe(&repo);
+
+        dispatcher(repo.clone()).handle(&deleted());
+        wait_until(|| !repo.fetch_deliveries(webhook.id).ok().unwrap().is_empty());
+
+        let received = stand_in.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].event, "pokemon.deleted");
+        assert!(received[0]
+            .body
+            .contains(r#""pokemon":{"number":25,"form":"default"}"#));
+        assert_eq!(
+            received[0].signature,
+            format!(
+                "sha256={}",
+                WebhookSecret::test().sign(received[0].body.as_bytes())
+            )
+        );
+
+        let deliveries = repo.fetch_deliveries(webhook.id).ok().unwrap();
+        assert_eq!(deliveries[0].status, DeliveryStatus::Responded(204));
+    }
+
+    #[test]
+    fn it_should_retry_until_the_webhook_accepts_the_event() {
+        let stand_in = StandIn::start(vec![500, 503, 200]);
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let webhook = stand_in.subscribe(&repo);
+
+        dispatcher(repo.clone()).handle(&deleted());
+        wait_until(|| repo.fetch_deliveries(webhook.id).ok().unwrap().len() == 3);
+
+        let deliveries = repo.fetch_deliveries(webhook.id).ok().unwrap();
+        assert_eq!(
+            deliveries
+                .iter()
+                .map(|d| (d.attempt, d.status.clone()))
+                .collect::<Vec<(u32, DeliveryStatus)>>(),
+            vec![
+                (1, DeliveryStatus::Responded(500)),
+                (2, DeliveryStatus::Responded(503)),
+                (3, DeliveryStatus::Responded(200)),
+            ]
+        );
+        assert!(repo.fetch_dead_letters().ok().unwrap().is_empty());
+    }
+
+    #[test]
+    fn it_should_keep_the_event_as_a_dead_letter_when_every_attempt_fails() {
+        let stand_in = StandIn::start(vec![500]);
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let webhook = stand_in.subscribe(&repo);
+
+        dispatcher(repo.clone()).handle(&deleted());
+        wait_until(|| !repo.fetch_dead_letters().ok().unwrap().is_empty());
+
+        let dead_letters = repo.fetch_dead_letters().ok().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].webhook, webhook.id);
+        assert_eq!(dead_letters[0].attempts, MAX_ATTEMPTS);
+        assert_eq!(
+            repo.fetch_deliveries(webhook.id).ok().unwrap().len(),
+            MAX_ATTEMPTS as usize
+        );
+    }
+
+    #[test]
+    fn it_should_wait_for_the_deliveries_when_flushed() {
+        let stand_in = StandIn::start(vec![500, 200]);
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let webhook = stand_in.subscribe(&repo);
+        let dispatcher = dispatcher(repo.clone());
+
+        dispatcher.handle(&deleted());
+
+        assert!(dispatcher.flush(Instant::now() + Duration::from_secs(5)));
+        assert_eq!(repo.fetch_deliveries(webhook.id).ok().unwrap().len(), 2);
+        assert!(repo.fetch_dead_letters().ok().unwrap().is_empty());
+    }
+
+    #[test]
+    fn it_should_dead_letter_the_deliveries_still_retried_when_the_flush_times_out() {
+        let stand_in = StandIn::start(vec![500]);
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let webhook = stand_in.subscribe(&repo);
+        let dispatcher = WebhookDispatcher::with_retry_base(repo.clone(), Duration::from_secs(60));
+
+        dispatcher.handle(&deleted());
+        wait_until(|| !repo.fetch_deliveries(webhook.id).ok().unwrap().is_empty());
+
+        assert!(!dispatcher.flush(Instant::now() + Duration::from_millis(50)));
+        let dead_letters = repo.fetch_dead_letters().ok().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].attempts, 1);
+    }
+
+    #[test]
+    fn it_should_log_the_attempts_when_the_webhook_is_unreachable() {
+        let repo = Arc::new(InMemoryWebhookRepository::new());
+        let webhook = repo
+            .insert(
+                WebhookUrl::try_from("http://localhost:1/hooks".to_string())
+                    .ok()
+                    .unwrap(),
+                WebhookSecret::test(),
+            )
+            .ok()
+            .unwrap();
+
+        dispatcher(repo.clone()).handle(&deleted());
+        wait_until(|| !repo.fetch_dead_letters().ok().unwrap().is_empty());
+
+        match &repo.fetch_deliveries(webhook.id).ok().unwrap()[0].status {
+            DeliveryStatus::Unreachable(_) => {}
+            _ => unreachable!(),
+        };
+    }
+}
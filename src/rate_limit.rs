@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Buckets kept before the full ones, which are as good as new, are dropped.
+const MAX_BUCKETS: usize = 10_000;
+
+/// Buckets left once some are dropped, the least recently used ones going
+/// when the full ones are not enough, so that the next drop waits for a
+/// thousand new clients.
+const BUCKETS_KEPT: usize = 9_000;
+
+/// Requests are limited apart whether they read the Pokedex or change it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Class {
+    Read,
+    Write,
+}
+
+/// What a client is left with after a request, as sent in the `RateLimit-*`
+/// headers.
+#[derive(Debug, PartialEq)]
+pub struct Decision {
+    pub allowed: bool,
+    /// Requests allowed per minute.
+    pub limit: u32,
+    pub remaining: u32,
+    /// Seconds until the bucket is full again.
+    pub reset: u64,
+    /// Seconds until the next request is allowed, `0` when it already is.
+    pub retry_after: u64,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// A token bucket per client and class, holding as many tokens as requests
+/// allowed per minute and refilled steadily over the minute, so that short
+/// bursts are allowed but not a sustained excess.
+pub struct RateLimiter {
+    read: u32,
+    write: u32,
+    buckets: Mutex<HashMap<(String, Class), Bucket>>,
+}
+
+impl RateLimiter {
+    /// Limits in requests per minute, `0` for no limit.
+    pub fn new(read: u32, write: u32) -> Self {
+        Self {
+            read,
+            write,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token from the bucket of `client`, `None` when the class is
+    /// not limited.
+    pub fn check(&self, client: &str, class: Class, now: Instant) -> Option<Decision> {
+        let limit = self.limit(class);
+        if limit == 0 {
+            return None;
+        }
+        let capacity = f64::from(limit);
+        let per_second = capacity / 60.0;
+
+        let mut buckets = match self.buckets.lock() {
+            Ok(buckets) => buckets,
+            // Better let a request through than fail it.
+            _ => return None,
+        };
+        if buckets.len() >= MAX_BUCKETS {
+            self.evict(&mut buckets, now);
+        }
+
+        let bucket = buckets
+            .entry((client.to_string(), class))
+            .or_insert(Bucket {
+                tokens: capacity,
+                updated: now,
+            });
+        bucket.tokens = self.refill(class, bucket, now);
+        bucket.updated = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+        let seconds = |tokens: f64| (tokens.max(0.0) / per_second).ceil() as u64;
+
+        Some(Decision {
+            allowed,
+            limit,
+            remaining: bucket.tokens.floor() as u32,
+            reset: seconds(capacity - bucket.tokens),
+            retry_after: if allowed {
+                0
+            } else {
+                seconds(1.0 - bucket.tokens)
+            },
+        })
+    }
+
+    fn limit(&self, class: Class) -> u32 {
+        match class {
+            Class::Read => self.read,
+            Class::Write => self.write,
+        }
+    }
+
+    fn refill(&self, class: Class, bucket: &Bucket, now: Instant) -> f64 {
+        let capacity = f64::from(self.limit(class));
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * capacity / 60.0).min(capacity)
+    }
+
+    /// Drops the full buckets, then the least recently used ones down to
+    /// `BUCKETS_KEPT`, their clients starting afresh.
+    fn evict(&self, buckets: &mut HashMap<(String, Class), Bucket>, now: Instant) {
+        buckets.retain(|(_, class), bucket| {
+            self.refill(*class, bucket, now) < f64::from(self.limit(*class))
+        });
+        if buckets.len() <= BUCKETS_KEPT {
+            return;
+        }
+
+        let mut updated: Vec<Instant> = buckets.values().map(|b| b.updated).collect();
+        updated.sort_unstable();
+        let oldest_kept = updated[updated.len() - BUCKETS_KEPT];
+        buckets.retain(|_, bucket| bucket.updated >= oldest_kept);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn it_should_allow_a_burst_then_refill_steadily() {
+        let limiter = RateLimiter::new(60, 0);
+        let start = Instant::now();
+
+        for remaining in (0..60).rev() {
+            let decision = limiter.check("ash", Class::Read, start).unwrap();
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, remaining);
+        }
+
+        let denied = limiter.check("ash", Class::Read, start).unwrap();
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after, 1);
+        assert_eq!(denied.reset, 60);
+
+        let later = limiter
+            .check("ash", Class::Read, start + Duration::from_secs(1))
+            .unwrap();
+        assert!(later.allowed);
+        assert_eq!(later.remaining, 0);
+    }
+
+    #[test]
+    fn it_should_limit_the_clients_and_the_classes_apart() {
+        let limiter = RateLimiter::new(1, 2);
+        let now = Instant::now();
+
+        assert!(limiter.check("ash", Class::Read, now).unwrap().allowed);
+        assert!(!limiter.check("ash", Class::Read, now).unwrap().allowed);
+        assert!(limiter.check("misty", Class::Read, now).unwrap().allowed);
+
+        let write = limiter.check("ash", Class::Write, now).unwrap();
+        assert!(write.allowed);
+        assert_eq!(write.limit, 2);
+        assert_eq!(write.remaining, 1);
+    }
+
+    #[test]
+    fn it_should_drop_the_least_recently_used_buckets_in_a_batch() {
+        let limiter = RateLimiter::new(1, 0);
+        let start = Instant::now();
+
+        for client in 0..MAX_BUCKETS {
+            let now = start + Duration::from_millis(client as u64);
+            limiter.check(&client.to_string(), Class::Read, now);
+        }
+        let now = start + Duration::from_millis(MAX_BUCKETS as u64);
+        limiter.check("ash", Class::Read, now);
+
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), BUCKETS_KEPT + 1);
+        assert!(!buckets.contains_key(&("0".to_string(), Class::Read)));
+        assert!(buckets.contains_key(&((MAX_BUCKETS - 1).to_string(), Class::Read)));
+    }
+
+    #[test]
+    fn it_should_not_limit_when_the_limit_is_zero() {
+        let limiter = RateLimiter::new(0, 1);
+
+        assert_eq!(limiter.check("ash", Class::Read, Instant::now()), None);
+    }
+}
@@ -0,0 +1,300 @@
+use crate::domain::api_key::{ApiKey, ApiKeyHash, ApiKeyId, ApiKeyName, Role};
+use rusqlite::Error::{QueryReturnedNoRows, SqliteFailure};
+use std::{convert::TryFrom, sync::Mutex};
+
+use crate::repositories::close_connection;
+use rusqlite::{params, Connection, OpenFlags};
+
+pub enum InsertError {
+    Conflict,
+    Unknown,
+}
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum FetchOneError {
+    NotFound,
+    Unknown,
+}
+
+pub enum DeleteError {
+    NotFound,
+    Unknown,
+}
+
+pub enum CloseError {
+    Unknown,
+}
+
+/// Names and hashes are unique. A revoked key is deleted.
+pub trait ApiKeyRepository: Send + Sync {
+    fn insert(
+        &self,
+        name: ApiKeyName,
+        role: Role,
+        hash: ApiKeyHash,
+        created_at: u64,
+    ) -> Result<ApiKey, InsertError>;
+
+    /// Every key, oldest first.
+    fn fetch_all(&self) -> Result<Vec<ApiKey>, FetchAllError>;
+
+    fn fetch_by_hash(&self, hash: ApiKeyHash) -> Result<ApiKey, FetchOneError>;
+
+    fn delete(&self, id: ApiKeyId) -> Result<(), DeleteError>;
+
+    /// Waits for the running operation, if any, then releases the storage.
+    /// The operations made afterwards fail.
+    fn close(&self) -> Result<(), CloseError>;
+}
+
+pub struct InMemoryApiKeyRepository {
+    error: bool,
+    keys: Mutex<Vec<ApiKey>>,
+    next_id: Mutex<u32>,
+}
+
+impl InMemoryApiKeyRepository {
+    pub fn new() -> Self {
+        Self {
+            error: false,
+            keys: Mutex::new(vec![]),
+            next_id: Mutex::new(1),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl ApiKeyRepository for InMemoryApiKeyRepository {
+    fn insert(
+        &self,
+        name: ApiKeyName,
+        role: Role,
+        hash: ApiKeyHash,
+        created_at: u64,
+    ) -> Result<ApiKey, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let (mut lock, mut next_id) = match (self.keys.lock(), self.next_id.lock()) {
+            (Ok(lock), Ok(next_id)) => (lock, next_id),
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if lock.iter().any(|key| key.name == name || key.hash == hash) {
+            return Err(InsertError::Conflict);
+        }
+
+        let key = ApiKey {
+            id: ApiKeyId::from(*next_id),
+            name,
+            role,
+            hash,
+            created_at,
+        };
+        *next_id += 1;
+
+        lock.push(key.clone());
+        Ok(key)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<ApiKey>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        match self.keys.lock() {
+            Ok(lock) => Ok(lock.to_vec()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_by_hash(&self, hash: ApiKeyHash) -> Result<ApiKey, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let lock = match self.keys.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock.iter().find(|key| key.hash == hash) {
+            Some(key) => Ok(key.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn delete(&self, id: ApiKeyId) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        let mut lock = match self.keys.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match lock.iter().position(|key| key.id == id) {
+            Some(index) => {
+                lock.remove(index);
+                Ok(())
+            }
+            None => Err(DeleteError::NotFound),
+        }
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        Ok(())
+    }
+}
+
+pub struct SqliteApiKeyRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteApiKeyRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn key_from_row(row: &rusqlite::Row) -> Result<ApiKey, ()> {
+        match (
+            row.get::<usize, u32>(0),
+            row.get::<usize, String>(1).map(ApiKeyName::try_from),
+            row.get::<usize, String>(2).map(Role::try_from),
+            row.get::<usize, String>(3).map(ApiKeyHash::try_from),
+            row.get::<usize, u64>(4),
+        ) {
+            (Ok(id), Ok(Ok(name)), Ok(Ok(role)), Ok(Ok(hash)), Ok(created_at)) => Ok(ApiKey {
+                id: ApiKeyId::from(id),
+                name,
+                role,
+                hash,
+                created_at,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ApiKeyRepository for SqliteApiKeyRepository {
+    fn insert(
+        &self,
+        name: ApiKeyName,
+        role: Role,
+        hash: ApiKeyHash,
+        created_at: u64,
+    ) -> Result<ApiKey, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        match lock.execute(
+            "insert into api_keys (name, role, hash, created_at) values (?, ?, ?, ?)",
+            params![
+                String::from(name.clone()),
+                role.to_string(),
+                String::from(hash.clone()),
+                created_at
+            ],
+        ) {
+            Ok(_) => Ok(ApiKey {
+                id: ApiKeyId::from(lock.last_insert_rowid() as u32),
+                name,
+                role,
+                hash,
+                created_at,
+            }),
+            Err(SqliteFailure(_, Some(message)))
+                if message.starts_with("UNIQUE constraint failed: api_keys.") =>
+            {
+                Err(InsertError::Conflict)
+            }
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<ApiKey>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut stmt = match lock
+            .prepare("select id, name, role, hash, created_at from api_keys order by id")
+        {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut rows = match stmt.query([]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut keys = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match Self::key_from_row(row) {
+                Ok(key) => keys.push(key),
+                _ => return Err(FetchAllError::Unknown),
+            };
+        }
+
+        Ok(keys)
+    }
+
+    fn fetch_by_hash(&self, hash: ApiKeyHash) -> Result<ApiKey, FetchOneError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock.query_row(
+            "select id, name, role, hash, created_at from api_keys where hash = ?",
+            params![String::from(hash)],
+            |row| Ok(Self::key_from_row(row)),
+        ) {
+            Ok(Ok(key)) => Ok(key),
+            Err(QueryReturnedNoRows) => Err(FetchOneError::NotFound),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn delete(&self, id: ApiKeyId) -> Result<(), DeleteError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match lock.execute("delete from api_keys where id = ?", params![id.to_u32()]) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        close_connection(&self.connection).map_err(|_| CloseError::Unknown)
+    }
+}
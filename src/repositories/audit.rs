@@ -0,0 +1,457 @@
+use crate::config::LogLevel;
+use crate::domain::audit::{AuditAction, AuditEntry, PokemonSnapshot};
+use crate::domain::clock::now;
+use crate::domain::entities::{
+    DeletedPokemon, Language, Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonStats,
+    PokemonTypes,
+};
+use crate::logger;
+use crate::repositories::close_connection;
+use crate::repositories::pokemon::{
+    self, DeleteError, FetchAllError, FetchOneError, InsertError, PurgeError, Repository,
+    RestoreError, SaveNameError,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    sync::{Arc, Mutex},
+};
+
+use rusqlite::{params, Connection, OpenFlags};
+
+pub enum RecordError {
+    Unknown,
+}
+
+pub enum FetchError {
+    Unknown,
+}
+
+pub enum CloseError {
+    Unknown,
+}
+
+/// Append-only: entries are never updated nor removed.
+pub trait AuditRepository: Send + Sync {
+    fn record(&self, entry: AuditEntry) -> Result<(), RecordError>;
+
+    /// Entries about a Pokemon, or about every one, recorded at or after
+    /// `since`, oldest first.
+    fn fetch(
+        &self,
+        number: Option<PokemonNumber>,
+        since: Option<u64>,
+    ) -> Result<Vec<AuditEntry>, FetchError>;
+
+    /// Waits for the running operation, if any, then releases the storage.
+    /// The operations made afterwards fail.
+    fn close(&self) -> Result<(), CloseError>;
+}
+
+pub struct InMemoryAuditRepository {
+    error: bool,
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl InMemoryAuditRepository {
+    pub fn new() -> Self {
+        Self {
+            error: false,
+            entries: Mutex::new(vec![]),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl AuditRepository for InMemoryAuditRepository {
+    fn record(&self, entry: AuditEntry) -> Result<(), RecordError> {
+        if self.error {
+            return Err(RecordError::Unknown);
+        }
+
+        match self.entries.lock() {
+            Ok(mut lock) => {
+                lock.push(entry);
+                Ok(())
+            }
+            _ => Err(RecordError::Unknown),
+        }
+    }
+
+    fn fetch(
+        &self,
+        number: Option<PokemonNumber>,
+        since: Option<u64>,
+    ) -> Result<Vec<AuditEntry>, FetchError> {
+        if self.error {
+            return Err(FetchError::Unknown);
+        }
+
+        let lock = match self.entries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        Ok(lock
+            .iter()
+            .filter(|e| number.as_ref().is_none_or(|n| e.number == *n))
+            .filter(|e| since.is_none_or(|s| e.at >= s))
+            .cloned()
+            .collect())
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        Ok(())
+    }
+}
+
+/// How snapshots are stored in the `before` and `after` columns.
+#[derive(Serialize, Deserialize)]
+struct SnapshotColumn {
+    name: String,
+    names: BTreeMap<String, String>,
+    types: Vec<String>,
+    stats: Vec<u16>,
+}
+
+impl From<PokemonSnapshot> for SnapshotColumn {
+    fn from(snapshot: PokemonSnapshot) -> Self {
+        Self {
+            name: snapshot.name,
+            names: snapshot.names.into_iter().collect(),
+            types: snapshot.types,
+            stats: snapshot.stats,
+        }
+    }
+}
+
+impl From<SnapshotColumn> for PokemonSnapshot {
+    fn from(column: SnapshotColumn) -> Self {
+        Self {
+            name: column.name,
+            names: column.names.into_iter().collect(),
+            types: column.types,
+            stats: column.stats,
+        }
+    }
+}
+
+fn to_column(snapshot: Option<PokemonSnapshot>) -> Result<Option<String>, ()> {
+    match snapshot.map(|s| serde_json::to_string(&SnapshotColumn::from(s))) {
+        Some(Ok(column)) => Ok(Some(column)),
+        Some(Err(_)) => Err(()),
+        None => Ok(None),
+    }
+}
+
+fn from_column(column: Option<String>) -> Result<Option<PokemonSnapshot>, ()> {
+    match column.map(|c| serde_json::from_str::<SnapshotColumn>(&c)) {
+        Some(Ok(column)) => Ok(Some(PokemonSnapshot::from(column))),
+        Some(Err(_)) => Err(()),
+        None => Ok(None),
+    }
+}
+
+pub struct SqliteAuditRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteAuditRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match connection.execute("pragma foreign_keys = 1", []) {
+            Ok(_) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl AuditRepository for SqliteAuditRepository {
+    fn record(&self, entry: AuditEntry) -> Result<(), RecordError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RecordError::Unknown),
+        };
+
+        let (before, after) = match (to_column(entry.before), to_column(entry.after)) {
+            (Ok(before), Ok(after)) => (before, after),
+            _ => return Err(RecordError::Unknown),
+        };
+
+        match lock.execute(
+            "insert into audit_log (at, actor, action, pokemon_number, pokemon_form, before, after) values (?, ?, ?, ?, ?, ?, ?)",
+            params![
+                entry.at,
+                entry.actor,
+                String::from(entry.action),
+                entry.number.to_u16(),
+                entry.form.to_string(),
+                before,
+                after
+            ],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(RecordError::Unknown),
+        }
+    }
+
+    fn fetch(
+        &self,
+        number: Option<PokemonNumber>,
+        since: Option<u64>,
+    ) -> Result<Vec<AuditEntry>, FetchError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare(
+            "select at, actor, action, pokemon_number, pokemon_form, before, after from audit_log where (?1 is null or pokemon_number = ?1) and (?2 is null or at >= ?2) order by id",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let mut rows = match stmt.query(params![number.map(u16::from), since]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let mut entries = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            let entry = match (
+                row.get::<usize, u64>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, String>(2).map(AuditAction::try_from),
+                row.get::<usize, u16>(3).map(PokemonNumber::try_from),
+                row.get::<usize, String>(4).map(PokemonForm::try_from),
+                row.get::<usize, Option<String>>(5).map(from_column),
+                row.get::<usize, Option<String>>(6).map(from_column),
+            ) {
+                (
+                    Ok(at),
+                    Ok(actor),
+                    Ok(Ok(action)),
+                    Ok(Ok(number)),
+                    Ok(Ok(form)),
+                    Ok(Ok(before)),
+                    Ok(Ok(after)),
+                ) => AuditEntry {
+                    at,
+                    actor,
+                    action,
+                    number,
+                    form,
+                    before,
+                    after,
+                },
+                _ => return Err(FetchError::Unknown),
+            };
+
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        close_connection(&self.connection).map_err(|_| CloseError::Unknown)
+    }
+}
+
+/// Decorates a `Repository` to record every successful change, with the
+/// state of the Pokemon before and after it, on behalf of `actor`.
+pub struct AuditedRepository {
+    inner: Arc<dyn Repository>,
+    snapshots: Arc<dyn Repository>,
+    audit: Arc<dyn AuditRepository>,
+    actor: String,
+}
+
+impl AuditedRepository {
+    pub fn new(inner: Arc<dyn Repository>, audit: Arc<dyn AuditRepository>, actor: String) -> Self {
+        Self {
+            snapshots: inner.clone(),
+            inner,
+            audit,
+            actor,
+        }
+    }
+
+    /// Reads the state before a change from `snapshots`, the repository under
+    /// any cache wrapping `inner`, so that it is never stale.
+    pub fn with_snapshots(self, snapshots: Arc<dyn Repository>) -> Self {
+        Self { snapshots, ..self }
+    }
+
+    /// The change already happened, so failing to record it is reported
+    /// without failing the change itself.
+    fn record(
+        &self,
+        action: AuditAction,
+        number: PokemonNumber,
+        form: PokemonForm,
+        before: Option<&Pokemon>,
+        after: Option<&Pokemon>,
+    ) {
+        let entry = AuditEntry {
+            at: now(),
+            actor: self.actor.clone(),
+            action,
+            number,
+            form,
+            before: before.map(PokemonSnapshot::from),
+            after: after.map(PokemonSnapshot::from),
+        };
+
+        if let Err(RecordError::Unknown) = self.audit.record(entry) {
+            logger::log(LogLevel::Error, "audit entry not recorded", &[]);
+        }
+    }
+}
+
+impl Repository for AuditedRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Result<Pokemon, InsertError> {
+        let pokemon = self.inner.insert(number, form, name, types, stats)?;
+        self.record(
+            AuditAction::Created,
+            pokemon.number.clone(),
+            pokemon.form.clone(),
+            None,
+            Some(&pokemon),
+        );
+        Ok(pokemon)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.inner.fetch_all()
+    }
+
+    fn fetch_one(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+    ) -> Result<Pokemon, FetchOneError> {
+        self.inner.fetch_one(number, form)
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchOneError> {
+        self.inner.fetch_by_name(name)
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchOneError> {
+        self.inner.fetch_forms(number)
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.inner.search(query)
+    }
+
+    fn save_name(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        language: Language,
+        name: PokemonName,
+    ) -> Result<Pokemon, SaveNameError> {
+        let before = self.snapshots.fetch_one(number.clone(), form.clone()).ok();
+        let pokemon = self.inner.save_name(number, form, language, name)?;
+        self.record(
+            AuditAction::Updated,
+            pokemon.number.clone(),
+            pokemon.form.clone(),
+            before.as_ref(),
+            Some(&pokemon),
+        );
+        Ok(pokemon)
+    }
+
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        deleted_at: u64,
+    ) -> Result<(), DeleteError> {
+        let before = self.snapshots.fetch_one(number.clone(), form.clone()).ok();
+        self.inner
+            .delete(number.clone(), form.clone(), deleted_at)?;
+        self.record(AuditAction::Deleted, number, form, before.as_ref(), None);
+        Ok(())
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, FetchAllError> {
+        self.inner.fetch_trash()
+    }
+
+    fn restore(&self, number: PokemonNumber, form: PokemonForm) -> Result<Pokemon, RestoreError> {
+        let pokemon = self.inner.restore(number, form)?;
+        self.record(
+            AuditAction::Restored,
+            pokemon.number.clone(),
+            pokemon.form.clone(),
+            None,
+            Some(&pokemon),
+        );
+        Ok(pokemon)
+    }
+
+    fn purge(&self, deleted_until: u64) -> Result<usize, PurgeError> {
+        let purged = self
+            .snapshots
+            .fetch_trash()
+            .map(|trash| {
+                trash
+                    .into_iter()
+                    .filter(|d| d.deleted_at <= deleted_until)
+                    .collect::<Vec<DeletedPokemon>>()
+            })
+            .unwrap_or_default();
+
+        let count = self.inner.purge(deleted_until)?;
+        for deleted in purged {
+            self.record(
+                AuditAction::Purged,
+                deleted.pokemon.number.clone(),
+                deleted.pokemon.form.clone(),
+                Some(&deleted.pokemon),
+                None,
+            );
+        }
+        Ok(count)
+    }
+
+    fn count(&self) -> Result<usize, pokemon::CountError> {
+        self.inner.count()
+    }
+
+    fn ping(&self) -> Result<(), pokemon::PingError> {
+        self.inner.ping()
+    }
+
+    fn close(&self) -> Result<(), pokemon::CloseError> {
+        self.inner.close()
+    }
+}
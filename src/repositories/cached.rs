@@ -0,0 +1,368 @@
+use crate::domain::entities::{
+    DeletedPokemon, Language, Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonStats,
+    PokemonTypes,
+};
+use crate::repositories::pokemon::{
+    CloseError, CountError, DeleteError, FetchAllError, FetchOneError, InsertError, PingError,
+    PurgeError, Repository, RestoreError, SaveNameError,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Key {
+    All,
+    One(u16, String),
+    ByName(String),
+    Forms(u16),
+    Search(String),
+    Trash,
+}
+
+#[derive(Clone)]
+enum Value {
+    Pokemon(Pokemon),
+    Pokemons(Vec<Pokemon>),
+    Trash(Vec<DeletedPokemon>),
+}
+
+struct Entry {
+    value: Value,
+    stored: Instant,
+    /// Position in the insertion order.
+    seq: u64,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<Key, Entry>,
+    /// Keys from the oldest stored to the newest, with the ones stored again
+    /// since, evicted first when the cache is full.
+    order: VecDeque<(u64, Key)>,
+    next_seq: u64,
+    /// Bumped by every write, so that a read started before it is not stored.
+    version: u64,
+}
+
+/// Keeps the reads of the wrapped repository for `ttl`, up to `capacity` of
+/// them, and forgets the ones a write affects once it succeeded. The failed
+/// reads are not kept.
+pub struct CachedRepository {
+    inner: Arc<dyn Repository>,
+    ttl: Duration,
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+impl CachedRepository {
+    pub fn new(inner: Arc<dyn Repository>, ttl: Duration, capacity: usize) -> Self {
+        Self {
+            inner,
+            ttl,
+            capacity,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn read<E>(&self, key: Key, run: impl FnOnce() -> Result<Value, E>) -> Result<Value, E> {
+        let version = match self.state.lock() {
+            Ok(state) => match state.entries.get(&key) {
+                Some(entry) if entry.stored.elapsed() < self.ttl => return Ok(entry.value.clone()),
+                _ => state.version,
+            },
+            _ => return run(),
+        };
+
+        let value = run()?;
+        if let Ok(mut state) = self.state.lock() {
+            if state.version == version {
+                self.store(&mut state, key, value.clone());
+            }
+        }
+        Ok(value)
+    }
+
+    fn store(&self, state: &mut State, key: Key, value: Value) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        while state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+            match state.order.pop_front() {
+                Some((seq, oldest)) => {
+                    if state.entries.get(&oldest).map(|e| e.seq) == Some(seq) {
+                        state.entries.remove(&oldest);
+                    }
+                }
+                None => break,
+            }
+        }
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.order.push_back((seq, key.clone()));
+        state.entries.insert(
+            key,
+            Entry {
+                value,
+                stored: Instant::now(),
+                seq,
+            },
+        );
+
+        // The keys stored again leave stale positions behind.
+        if state.order.len() > 2 * self.capacity {
+            let State { entries, order, .. } = state;
+            order.retain(|(seq, key)| entries.get(key).map(|e| e.seq) == Some(*seq));
+        }
+    }
+
+    fn write<T, E>(
+        &self,
+        affected: impl Fn(&Key, &Value) -> bool,
+        run: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let res = run();
+        if res.is_ok() {
+            self.forget(affected);
+        }
+        res
+    }
+
+    fn forget(&self, affected: impl Fn(&Key, &Value) -> bool) {
+        if let Ok(mut state) = self.state.lock() {
+            state
+                .entries
+                .retain(|key, entry| !affected(key, &entry.value));
+            state.version += 1;
+        }
+    }
+}
+
+/// The reads a change to a Pokemon affects, the trash too when `trash`.
+fn pokemon(
+    number: &PokemonNumber,
+    form: &PokemonForm,
+    trash: bool,
+) -> impl Fn(&Key, &Value) -> bool {
+    let (number, form) = (number.to_u16(), form.to_string());
+    move |key, value| match (key, value) {
+        (Key::All, _) | (Key::Search(_), _) => true,
+        (Key::One(n, f), _) => *n == number && *f == form,
+        (Key::Forms(n), _) => *n == number,
+        (Key::ByName(_), Value::Pokemon(p)) => {
+            p.number.to_u16() == number && p.form.to_string() == form
+        }
+        (Key::ByName(_), _) => true,
+        (Key::Trash, _) => trash,
+    }
+}
+
+impl Repository for CachedRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Result<Pokemon, InsertError> {
+        self.write(pokemon(&number, &form, false), || {
+            self.inner
+                .insert(number.clone(), form.clone(), name, types, stats)
+        })
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+        match self.read(Key::All, || self.inner.fetch_all().map(Value::Pokemons))? {
+            Value::Pokemons(pokemons) => Ok(pokemons),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_one(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+    ) -> Result<Pokemon, FetchOneError> {
+        let key = Key::One(number.to_u16(), form.to_string());
+        match self.read(key, || {
+            self.inner.fetch_one(number, form).map(Value::Pokemon)
+        })? {
+            Value::Pokemon(pokemon) => Ok(pokemon),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchOneError> {
+        let key = Key::ByName(name.to_string().to_lowercase());
+        match self.read(key, || self.inner.fetch_by_name(name).map(Value::Pokemon))? {
+            Value::Pokemon(pokemon) => Ok(pokemon),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchOneError> {
+        let key = Key::Forms(number.to_u16());
+        match self.read(key, || self.inner.fetch_forms(number).map(Value::Pokemons))? {
+            Value::Pokemons(pokemons) => Ok(pokemons),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Pokemon>, FetchAllError> {
+        let key = Key::Search(query.to_string());
+        match self.read(key, || self.inner.search(query).map(Value::Pokemons))? {
+            Value::Pokemons(pokemons) => Ok(pokemons),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn save_name(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        language: Language,
+        name: PokemonName,
+    ) -> Result<Pokemon, SaveNameError> {
+        self.write(pokemon(&number, &form, false), || {
+            self.inner
+                .save_name(number.clone(), form.clone(), language, name)
+        })
+    }
+
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        deleted_at: u64,
+    ) -> Result<(), DeleteError> {
+        self.write(pokemon(&number, &form, true), || {
+            self.inner.delete(number.clone(), form.clone(), deleted_at)
+        })
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, FetchAllError> {
+        match self.read(Key::Trash, || self.inner.fetch_trash().map(Value::Trash))? {
+            Value::Trash(trash) => Ok(trash),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn restore(&self, number: PokemonNumber, form: PokemonForm) -> Result<Pokemon, RestoreError> {
+        self.write(pokemon(&number, &form, true), || {
+            self.inner.restore(number.clone(), form.clone())
+        })
+    }
+
+    fn purge(&self, deleted_until: u64) -> Result<usize, PurgeError> {
+        // Only the Pokemons already in the trash are purged.
+        self.write(
+            |key, _| *key == Key::Trash,
+            || self.inner.purge(deleted_until),
+        )
+    }
+
+    fn count(&self) -> Result<usize, CountError> {
+        self.inner.count()
+    }
+
+    fn ping(&self) -> Result<(), PingError> {
+        self.inner.ping()
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        self.forget(|_, _| true);
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn insert_pikachu(repo: &dyn Repository) {
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+    }
+
+    #[test]
+    fn it_should_keep_the_reads_until_a_write() {
+        let inner = Arc::new(InMemoryRepository::new());
+        let repo = CachedRepository::new(inner.clone(), Duration::from_secs(60), 10);
+
+        assert_eq!(repo.fetch_all().ok().unwrap().len(), 0);
+        insert_pikachu(inner.as_ref());
+        assert_eq!(repo.fetch_all().ok().unwrap().len(), 0);
+
+        repo.delete(PokemonNumber::charmander(), PokemonForm::default(), 0)
+            .ok();
+        assert_eq!(repo.fetch_all().ok().unwrap().len(), 0);
+
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            PokemonStats::charmander(),
+        )
+        .ok();
+        assert_eq!(repo.fetch_all().ok().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn it_should_forget_only_the_reads_a_write_affects() {
+        let inner = Arc::new(InMemoryRepository::new());
+        let repo = CachedRepository::new(inner.clone(), Duration::from_secs(60), 10);
+        insert_pikachu(inner.as_ref());
+        inner
+            .insert(
+                PokemonNumber::charmander(),
+                PokemonForm::default(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+                PokemonStats::charmander(),
+            )
+            .ok();
+
+        repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default())
+            .ok();
+        repo.fetch_one(PokemonNumber::charmander(), PokemonForm::default())
+            .ok();
+        inner
+            .delete(PokemonNumber::charmander(), PokemonForm::default(), 0)
+            .ok();
+        repo.delete(PokemonNumber::pikachu(), PokemonForm::default(), 0)
+            .ok();
+
+        assert!(repo
+            .fetch_one(PokemonNumber::pikachu(), PokemonForm::default())
+            .is_err());
+        assert!(repo
+            .fetch_one(PokemonNumber::charmander(), PokemonForm::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn it_should_read_again_once_stale_or_evicted() {
+        let inner = Arc::new(InMemoryRepository::new());
+        let stale = CachedRepository::new(inner.clone(), Duration::ZERO, 10);
+        let small = CachedRepository::new(inner.clone(), Duration::from_secs(60), 1);
+
+        small.fetch_all().ok();
+        stale.fetch_all().ok();
+        insert_pikachu(inner.as_ref());
+
+        assert_eq!(stale.fetch_all().ok().unwrap().len(), 1);
+        small.fetch_trash().ok();
+        assert_eq!(small.fetch_all().ok().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::config::LogLevel;
+use crate::domain::entities::{
+    DeletedPokemon, Language, Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonStats,
+    PokemonTypes,
+};
+use crate::logger;
+use crate::metrics::Metrics;
+use crate::repositories::pokemon::{
+    CloseError, CountError, DeleteError, FetchAllError, FetchOneError, InsertError, PingError,
+    PurgeError, Repository, RestoreError, SaveNameError,
+};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Name of an error variant, as reported in the metrics and the logs.
+trait Variant {
+    fn variant(&self) -> &'static str;
+}
+
+impl Variant for FetchAllError {
+    fn variant(&self) -> &'static str {
+        match self {
+            FetchAllError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for FetchOneError {
+    fn variant(&self) -> &'static str {
+        match self {
+            FetchOneError::NotFound => "NotFound",
+            FetchOneError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for InsertError {
+    fn variant(&self) -> &'static str {
+        match self {
+            InsertError::Conflict => "Conflict",
+            InsertError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for SaveNameError {
+    fn variant(&self) -> &'static str {
+        match self {
+            SaveNameError::NotFound => "NotFound",
+            SaveNameError::Conflict => "Conflict",
+            SaveNameError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for DeleteError {
+    fn variant(&self) -> &'static str {
+        match self {
+            DeleteError::NotFound => "NotFound",
+            DeleteError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for RestoreError {
+    fn variant(&self) -> &'static str {
+        match self {
+            RestoreError::NotFound => "NotFound",
+            RestoreError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for PurgeError {
+    fn variant(&self) -> &'static str {
+        match self {
+            PurgeError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for CountError {
+    fn variant(&self) -> &'static str {
+        match self {
+            CountError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for PingError {
+    fn variant(&self) -> &'static str {
+        match self {
+            PingError::Unknown => "Unknown",
+        }
+    }
+}
+
+impl Variant for CloseError {
+    fn variant(&self) -> &'static str {
+        match self {
+            CloseError::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Times every operation of the wrapped repository, counts its errors and logs
+/// it at the debug level, with the request it serves.
+pub struct MeteredRepository {
+    inner: Arc<dyn Repository>,
+    metrics: Arc<Metrics>,
+}
+
+impl MeteredRepository {
+    pub fn new(inner: Arc<dyn Repository>, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    fn measure<T, E: Variant>(
+        &self,
+        operation: &'static str,
+        run: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let started = Instant::now();
+        let res = run();
+        let error = res.as_ref().err().map(Variant::variant);
+        self.metrics
+            .observe_operation(operation, started.elapsed(), error);
+
+        let mut fields = vec![
+            ("operation", operation.into()),
+            ("latency_ms", logger::latency_ms(started)),
+        ];
+        if let Some(error) = error {
+            fields.push(("error", error.into()));
+        }
+        logger::log(LogLevel::Debug, "repository", &fields);
+        res
+    }
+}
+
+impl Repository for MeteredRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Result<Pokemon, InsertError> {
+        self.measure("insert", || {
+            self.inner.insert(number, form, name, types, stats)
+        })
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.measure("fetch_all", || self.inner.fetch_all())
+    }
+
+    fn fetch_one(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+    ) -> Result<Pokemon, FetchOneError> {
+        self.measure("fetch_one", || self.inner.fetch_one(number, form))
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchOneError> {
+        self.measure("fetch_by_name", || self.inner.fetch_by_name(name))
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchOneError> {
+        self.measure("fetch_forms", || self.inner.fetch_forms(number))
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.measure("search", || self.inner.search(query))
+    }
+
+    fn save_name(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        language: Language,
+        name: PokemonName,
+    ) -> Result<Pokemon, SaveNameError> {
+        self.measure("save_name", || {
+            self.inner.save_name(number, form, language, name)
+        })
+    }
+
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        deleted_at: u64,
+    ) -> Result<(), DeleteError> {
+        self.measure("delete", || self.inner.delete(number, form, deleted_at))
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, FetchAllError> {
+        self.measure("fetch_trash", || self.inner.fetch_trash())
+    }
+
+    fn restore(&self, number: PokemonNumber, form: PokemonForm) -> Result<Pokemon, RestoreError> {
+        self.measure("restore", || self.inner.restore(number, form))
+    }
+
+    fn purge(&self, deleted_until: u64) -> Result<usize, PurgeError> {
+        self.measure("purge", || self.inner.purge(deleted_until))
+    }
+
+    fn count(&self) -> Result<usize, CountError> {
+        self.measure("count", || self.inner.count())
+    }
+
+    fn ping(&self) -> Result<(), PingError> {
+        self.measure("ping", || self.inner.ping())
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        self.inner.close()
+    }
+}
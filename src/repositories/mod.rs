@@ -1 +1,30 @@
+use rusqlite::Connection;
+use std::sync::Mutex;
+
+pub mod api_key;
+pub mod audit;
+pub mod cached;
+pub mod metered;
 pub mod pokemon;
+pub mod progress;
+pub mod team;
+pub mod webhook;
+
+/// Closes the connection once the operation running on it, if any, is over,
+/// leaving an empty in-memory database in its place.
+fn close_connection(connection: &Mutex<Connection>) -> Result<(), ()> {
+    let mut lock = match connection.lock() {
+        Ok(lock) => lock,
+        _ => return Err(()),
+    };
+
+    let closed = match Connection::open_in_memory() {
+        Ok(empty) => std::mem::replace(&mut *lock, empty),
+        _ => return Err(()),
+    };
+
+    match closed.close() {
+        Ok(()) => Ok(()),
+        _ => Err(()),
+    }
+}
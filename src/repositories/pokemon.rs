@@ -1,15 +1,18 @@
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
-use rusqlite::Error::SqliteFailure;
+use crate::domain::entities::{
+    DeletedPokemon, Language, Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonStats,
+    PokemonTypes,
+};
+use crate::domain::fuzzy::trigrams;
+use rusqlite::Error::{QueryReturnedNoRows, SqliteFailure};
 use std::{
+    collections::{BTreeSet, HashMap},
     convert::TryFrom,
+    path::Path,
     sync::{Mutex, MutexGuard},
 };
 
-use rusqlite::{params, params_from_iter, Connection, OpenFlags};
-
-pub enum Insert {
-    Ok(PokemonNumber),
-}
+use crate::repositories::close_connection;
+use rusqlite::{params, Connection, OpenFlags};
 
 pub enum FetchAllError {
     Unknown,
@@ -25,32 +28,114 @@ pub enum InsertError {
     Unknown,
 }
 
+pub enum SaveNameError {
+    NotFound,
+    Conflict,
+    Unknown,
+}
+
 pub enum DeleteError {
     NotFound,
     Unknown,
 }
 
+pub enum RestoreError {
+    NotFound,
+    Unknown,
+}
+
+pub enum PurgeError {
+    Unknown,
+}
+
+pub enum CountError {
+    Unknown,
+}
+
+pub enum PingError {
+    Unknown,
+}
+
+pub enum CloseError {
+    Unknown,
+}
+
+/// Names are unique regardless of case: English names across every Pokemon,
+/// translations within their language. Deleted Pokemons stay in the trash,
+/// hidden from every fetch but still holding their number, form and names,
+/// until they are restored or purged.
 pub trait Repository: Send + Sync {
     fn insert(
         &self,
         number: PokemonNumber,
+        form: PokemonForm,
         name: PokemonName,
         types: PokemonTypes,
+        stats: PokemonStats,
     ) -> Result<Pokemon, InsertError>;
 
+    /// Every form of every species, ordered by number then form.
     fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError>;
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError>;
+    fn fetch_one(&self, number: PokemonNumber, form: PokemonForm)
+        -> Result<Pokemon, FetchOneError>;
+
+    /// The Pokemon whose English name is `name`, regardless of case.
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchOneError>;
+
+    /// Every form of a species, the default one first.
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchOneError>;
+
+    /// Pokemons with a name, in any language, sharing at least a trigram with
+    /// `query`. They are candidates for the search use case to rank.
+    fn search(&self, query: &str) -> Result<Vec<Pokemon>, FetchAllError>;
 
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError>;
+    /// Sets the name of a form in a language; an English name replaces `Pokemon::name`.
+    fn save_name(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        language: Language,
+        name: PokemonName,
+    ) -> Result<Pokemon, SaveNameError>;
+
+    /// Moves the form to the trash.
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        deleted_at: u64,
+    ) -> Result<(), DeleteError>;
+
+    /// Deleted Pokemons, the most recently deleted first.
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, FetchAllError>;
+
+    /// Takes the form out of the trash.
+    fn restore(&self, number: PokemonNumber, form: PokemonForm) -> Result<Pokemon, RestoreError>;
+
+    /// Permanently removes the Pokemons deleted at or before `deleted_until`
+    /// and returns how many there were.
+    fn purge(&self, deleted_until: u64) -> Result<usize, PurgeError>;
+
+    /// How many forms are outside the trash, without loading them.
+    fn count(&self) -> Result<usize, CountError>;
+
+    /// Cheaply checks that the storage can be read.
+    fn ping(&self) -> Result<(), PingError>;
+
+    /// Waits for the running operation, if any, then releases the storage.
+    /// The operations made afterwards fail.
+    fn close(&self) -> Result<(), CloseError>;
 }
 
 impl Repository for InMemoryRepository {
     fn insert(
         &self,
         number: PokemonNumber,
+        form: PokemonForm,
         name: PokemonName,
         types: PokemonTypes,
+        stats: PokemonStats,
     ) -> Result<Pokemon, InsertError> {
         if self.error {
             return Err(InsertError::Unknown);
@@ -61,14 +146,30 @@ impl Repository for InMemoryRepository {
             _ => return Err(InsertError::Unknown),
         };
 
-        if lock.iter().any(|pokemon| pokemon.number == number) {
+        let trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if lock
+            .iter()
+            .chain(trash.iter().map(|d| &d.pokemon))
+            .any(|pokemon| {
+                (pokemon.number == number && pokemon.form == form)
+                    || pokemon.name.key() == name.key()
+            })
+        {
             return Err(InsertError::Conflict);
         }
 
-        let number_clone = number.clone();
+        let mut name_index = match self.name_index.lock() {
+            Ok(name_index) => name_index,
+            _ => return Err(InsertError::Unknown),
+        };
 
-        let pokemon = Pokemon::new(number_clone, name, types);
+        let pokemon = Pokemon::new(number, form, name, types, stats);
 
+        name_index.add(&pokemon);
         lock.push(pokemon.clone());
         Ok(pokemon)
     }
@@ -84,11 +185,15 @@ impl Repository for InMemoryRepository {
         };
 
         let mut pokemons = lock.to_vec();
-        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
+        pokemons.sort_by(form_order);
         Ok(pokemons)
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+    fn fetch_one(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+    ) -> Result<Pokemon, FetchOneError> {
         if self.error {
             return Err(FetchOneError::Unknown);
         }
@@ -98,13 +203,58 @@ impl Repository for InMemoryRepository {
             _ => return Err(FetchOneError::Unknown),
         };
 
-        match lock.iter().find(|p| p.number == number) {
+        match lock.iter().find(|p| p.number == number && p.form == form) {
             Some(pokemon) => Ok(pokemon.clone()),
             None => Err(FetchOneError::NotFound),
         }
     }
 
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock.iter().find(|p| p.name.key() == name.key()) {
+            Some(pokemon) => Ok(pokemon.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut pokemons = lock
+            .iter()
+            .filter(|p| p.number == number)
+            .cloned()
+            .collect::<Vec<Pokemon>>();
+
+        if pokemons.is_empty() {
+            return Err(FetchOneError::NotFound);
+        }
+
+        pokemons.sort_by(form_order);
+        Ok(pokemons)
+    }
+
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        deleted_at: u64,
+    ) -> Result<(), DeleteError> {
         if self.error {
             return Err(DeleteError::Unknown);
         }
@@ -114,19 +264,282 @@ impl Repository for InMemoryRepository {
             _ => return Err(DeleteError::Unknown),
         };
 
-        let index = match lock.iter().position(|p| p.number == number) {
+        let index = match lock
+            .iter()
+            .position(|p| p.number == number && p.form == form)
+        {
             Some(index) => index,
             None => return Err(DeleteError::NotFound),
         };
 
-        lock.remove(index);
+        let mut trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let mut name_index = match self.name_index.lock() {
+            Ok(name_index) => name_index,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let pokemon = lock.remove(index);
+        name_index.remove(&pokemon);
+        trash.push(DeletedPokemon {
+            pokemon,
+            deleted_at,
+        });
+        Ok(())
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        let trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut deleted = trash.to_vec();
+        deleted.sort_by(trash_order);
+        Ok(deleted)
+    }
+
+    fn restore(&self, number: PokemonNumber, form: PokemonForm) -> Result<Pokemon, RestoreError> {
+        if self.error {
+            return Err(RestoreError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RestoreError::Unknown),
+        };
+
+        let mut trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            _ => return Err(RestoreError::Unknown),
+        };
+
+        let index = match trash
+            .iter()
+            .position(|d| d.pokemon.number == number && d.pokemon.form == form)
+        {
+            Some(index) => index,
+            None => return Err(RestoreError::NotFound),
+        };
+
+        let mut name_index = match self.name_index.lock() {
+            Ok(name_index) => name_index,
+            _ => return Err(RestoreError::Unknown),
+        };
+
+        let pokemon = trash.remove(index).pokemon;
+        name_index.add(&pokemon);
+        lock.push(pokemon.clone());
+        Ok(pokemon)
+    }
+
+    fn purge(&self, deleted_until: u64) -> Result<usize, PurgeError> {
+        if self.error {
+            return Err(PurgeError::Unknown);
+        }
+
+        let mut trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            _ => return Err(PurgeError::Unknown),
+        };
+
+        let before = trash.len();
+        trash.retain(|d| d.deleted_at > deleted_until);
+        Ok(before - trash.len())
+    }
+
+    fn save_name(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        language: Language,
+        name: PokemonName,
+    ) -> Result<Pokemon, SaveNameError> {
+        if self.error {
+            return Err(SaveNameError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(SaveNameError::Unknown),
+        };
+
+        let index = match lock
+            .iter()
+            .position(|p| p.number == number && p.form == form)
+        {
+            Some(index) => index,
+            None => return Err(SaveNameError::NotFound),
+        };
+
+        let trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            _ => return Err(SaveNameError::Unknown),
+        };
+
+        let taken = lock
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(_, p)| p)
+            .chain(trash.iter().map(|d| &d.pokemon))
+            .any(|p| {
+                if language.is_english() {
+                    p.name.key() == name.key()
+                } else {
+                    p.translations
+                        .iter()
+                        .any(|(l, n)| *l == language && n.key() == name.key())
+                }
+            });
+        if taken {
+            return Err(SaveNameError::Conflict);
+        }
+
+        let mut name_index = match self.name_index.lock() {
+            Ok(name_index) => name_index,
+            _ => return Err(SaveNameError::Unknown),
+        };
+
+        let pokemon = &mut lock[index];
+        name_index.remove(pokemon);
+
+        if language.is_english() {
+            pokemon.name = name;
+        } else {
+            pokemon.translations.retain(|(l, _)| *l != language);
+            pokemon.translations.push((language, name));
+            pokemon.translations.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+
+        name_index.add(pokemon);
+        Ok(pokemon.clone())
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Pokemon>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        let lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let name_index = match self.name_index.lock() {
+            Ok(name_index) => name_index,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let candidates = name_index.candidates(query);
+        let mut pokemons = lock
+            .iter()
+            .filter(|p| candidates.contains(&(p.number.clone(), p.form.clone())))
+            .cloned()
+            .collect::<Vec<Pokemon>>();
+        pokemons.sort_by(form_order);
+        Ok(pokemons)
+    }
+
+    fn count(&self) -> Result<usize, CountError> {
+        if self.error {
+            return Err(CountError::Unknown);
+        }
+
+        match self.pokemons.lock() {
+            Ok(lock) => Ok(lock.len()),
+            _ => Err(CountError::Unknown),
+        }
+    }
+
+    fn ping(&self) -> Result<(), PingError> {
+        if self.error {
+            return Err(PingError::Unknown);
+        }
+
+        match self.pokemons.lock() {
+            Ok(_) => Ok(()),
+            _ => Err(PingError::Unknown),
+        }
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
         Ok(())
     }
 }
 
+/// Orders by number, then puts the default form before the other ones.
+fn form_order(a: &Pokemon, b: &Pokemon) -> std::cmp::Ordering {
+    a.number
+        .cmp(&b.number)
+        .then_with(|| b.form.is_default().cmp(&a.form.is_default()))
+        .then_with(|| a.form.cmp(&b.form))
+}
+
+/// Orders the trash by deletion time, the most recent first.
+fn trash_order(a: &DeletedPokemon, b: &DeletedPokemon) -> std::cmp::Ordering {
+    b.deleted_at
+        .cmp(&a.deleted_at)
+        .then_with(|| form_order(&a.pokemon, &b.pokemon))
+}
+
+/// Maps every trigram of every name to the forms carrying that name.
+#[derive(Default)]
+struct NameIndex {
+    postings: HashMap<String, BTreeSet<(PokemonNumber, PokemonForm)>>,
+}
+
+impl NameIndex {
+    fn names(pokemon: &Pokemon) -> impl Iterator<Item = String> + '_ {
+        std::iter::once(pokemon.name.to_string()).chain(
+            pokemon
+                .translations
+                .iter()
+                .map(|(_, name)| name.to_string()),
+        )
+    }
+
+    fn add(&mut self, pokemon: &Pokemon) {
+        for name in Self::names(pokemon) {
+            for trigram in trigrams(&name) {
+                self.postings
+                    .entry(trigram)
+                    .or_default()
+                    .insert((pokemon.number.clone(), pokemon.form.clone()));
+            }
+        }
+    }
+
+    fn remove(&mut self, pokemon: &Pokemon) {
+        let key = (pokemon.number.clone(), pokemon.form.clone());
+        self.postings.retain(|_, keys| {
+            keys.remove(&key);
+            !keys.is_empty()
+        });
+    }
+
+    fn candidates(&self, query: &str) -> BTreeSet<(PokemonNumber, PokemonForm)> {
+        trigrams(query)
+            .iter()
+            .filter_map(|trigram| self.postings.get(trigram))
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
 pub struct InMemoryRepository {
     error: bool,
     pokemons: Mutex<Vec<Pokemon>>,
+    trash: Mutex<Vec<DeletedPokemon>>,
+    name_index: Mutex<NameIndex>,
 }
 
 impl InMemoryRepository {
@@ -135,6 +548,8 @@ impl InMemoryRepository {
         Self {
             error: false,
             pokemons,
+            trash: Mutex::new(vec![]),
+            name_index: Mutex::new(NameIndex::default()),
         }
     }
 
@@ -147,8 +562,18 @@ impl InMemoryRepository {
     }
 }
 
+/// Number, form, name, stats and deletion time of a stored Pokemon.
+type PokemonRow = (u16, String, String, Vec<u16>, Option<u64>);
+
+/// Told of every row left out of a read because it no longer holds valid
+/// values, with the kind of row, `pokemon` or `translation`, and the number
+/// and form of the Pokemon.
+type InvalidRowHandler = Box<dyn Fn(&'static str, u16, &str) + Send + Sync>;
+
 pub struct SqliteRepository {
+    path: String,
     connection: Mutex<Connection>,
+    on_invalid_row: InvalidRowHandler,
 }
 
 impl SqliteRepository {
@@ -161,30 +586,39 @@ impl SqliteRepository {
 
         match connection.execute("pragma foreign_keys = 1", []) {
             Ok(_) => Ok(Self {
+                path: path.to_string(),
                 connection: Mutex::new(connection),
+                on_invalid_row: Box::new(|_, _, _| {}),
             }),
             _ => Err(()),
         }
     }
 
+    pub fn on_invalid_row(
+        self,
+        handler: impl Fn(&'static str, u16, &str) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            on_invalid_row: Box::new(handler),
+            ..self
+        }
+    }
+
+    /// Rows of the live Pokemons, or of the deleted ones when `trashed`.
     fn fetch_pokemon_rows(
         lock: &MutexGuard<'_, Connection>,
         number: Option<u16>,
-    ) -> Result<Vec<(u16, String)>, ()> {
-        let (query, params) = match number {
-            Some(number) => (
-                "select number, name from pokemons where number = ?",
-                vec![number],
-            ),
-            _ => ("select number, name from pokemons", vec![]),
-        };
-
-        let mut stmt = match lock.prepare(query) {
+        form: Option<String>,
+        trashed: bool,
+    ) -> Result<Vec<PokemonRow>, ()> {
+        let mut stmt = match lock.prepare(
+            "select number, form, name, hp, attack, defense, speed, deleted_at from pokemons where (?1 is null or number = ?1) and (?2 is null or form = ?2) and (deleted_at is not null) = ?3 order by deleted_at desc, number, form <> 'default', form",
+        ) {
             Ok(stmt) => stmt,
             _ => return Err(()),
         };
 
-        let mut rows = match stmt.query(params_from_iter(params)) {
+        let mut rows = match stmt.query(params![number, form, trashed]) {
             Ok(rows) => rows,
             _ => return Err(()),
         };
@@ -192,8 +626,23 @@ impl SqliteRepository {
         let mut pokemon_rows = vec![];
 
         while let Ok(Some(row)) = rows.next() {
-            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
-                (Ok(number), Ok(name)) => pokemon_rows.push((number, name)),
+            let stats = match (3..7)
+                .map(|i| row.get::<usize, u16>(i))
+                .collect::<Result<Vec<u16>, _>>()
+            {
+                Ok(stats) => stats,
+                _ => return Err(()),
+            };
+
+            match (
+                row.get::<usize, u16>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, String>(2),
+                row.get::<usize, Option<u64>>(7),
+            ) {
+                (Ok(number), Ok(form), Ok(name), Ok(deleted_at)) => {
+                    pokemon_rows.push((number, form, name, stats, deleted_at))
+                }
                 _ => return Err(()),
             };
         }
@@ -201,13 +650,19 @@ impl SqliteRepository {
         Ok(pokemon_rows)
     }
 
-    fn fetch_type_rows(lock: &MutexGuard<'_, Connection>, number: u16) -> Result<Vec<String>, ()> {
-        let mut stmt = match lock.prepare("select name from types where pokemon_number = ?") {
+    fn fetch_type_rows(
+        lock: &MutexGuard<'_, Connection>,
+        number: u16,
+        form: &str,
+    ) -> Result<Vec<String>, ()> {
+        let mut stmt = match lock
+            .prepare("select name from types where pokemon_number = ? and pokemon_form = ?")
+        {
             Ok(stmt) => stmt,
             _ => return Err(()),
         };
 
-        let mut rows = match stmt.query([number]) {
+        let mut rows = match stmt.query(params![number, form]) {
             Ok(rows) => rows,
             _ => return Err(()),
         };
@@ -223,14 +678,156 @@ impl SqliteRepository {
 
         Ok(type_rows)
     }
+
+    fn fetch_name_rows(
+        lock: &MutexGuard<'_, Connection>,
+        number: u16,
+        form: &str,
+    ) -> Result<Vec<(String, String)>, ()> {
+        let mut stmt = match lock.prepare(
+            "select language, name from pokemon_names where pokemon_number = ? and pokemon_form = ? order by language",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query(params![number, form]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut name_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (row.get::<usize, String>(0), row.get::<usize, String>(1)) {
+                (Ok(language), Ok(name)) => name_rows.push((language, name)),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(name_rows)
+    }
+
+    /// Forms with a name matching the query in `pokemon_search`. Queries too
+    /// short to hold a trigram match the start of the names instead.
+    fn search_rows(
+        lock: &MutexGuard<'_, Connection>,
+        query: &str,
+    ) -> Result<Vec<(u16, String)>, ()> {
+        let query = query.trim().to_lowercase();
+        let chars = query.chars().collect::<Vec<char>>();
+
+        let (sql, param) = if chars.len() >= 3 {
+            (
+                "select distinct number, form from pokemon_search where pokemon_search match ?",
+                chars
+                    .windows(3)
+                    .map(|w| format!("\"{}\"", w.iter().collect::<String>().replace('"', "\"\"")))
+                    .collect::<Vec<String>>()
+                    .join(" OR "),
+            )
+        } else {
+            (
+                "select number, form from pokemons where name_key like ?1 escape '\\' union select pokemon_number, pokemon_form from pokemon_names where name_key like ?1 escape '\\'",
+                format!(
+                    "{}%",
+                    query
+                        .replace('\\', "\\\\")
+                        .replace('%', "\\%")
+                        .replace('_', "\\_")
+                ),
+            )
+        };
+
+        let mut stmt = match lock.prepare(sql) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query(params![param]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut search_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
+                (Ok(number), Ok(form)) => search_rows.push((number, form)),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(search_rows)
+    }
+
+    fn fetch_pokemons(
+        &self,
+        lock: &MutexGuard<'_, Connection>,
+        number: Option<u16>,
+        form: Option<String>,
+    ) -> Result<Vec<Pokemon>, ()> {
+        Ok(self
+            .fetch_pokemons_in(lock, number, form, false)?
+            .into_iter()
+            .map(|(pokemon, _)| pokemon)
+            .collect())
+    }
+
+    /// Live Pokemons, or deleted ones along with their deletion time when `trashed`.
+    fn fetch_pokemons_in(
+        &self,
+        lock: &MutexGuard<'_, Connection>,
+        number: Option<u16>,
+        form: Option<String>,
+        trashed: bool,
+    ) -> Result<Vec<(Pokemon, Option<u64>)>, ()> {
+        let pokemon_rows = Self::fetch_pokemon_rows(lock, number, form, trashed)?;
+
+        let mut pokemons = vec![];
+
+        for (number, form, name, stats, deleted_at) in pokemon_rows {
+            let type_rows = Self::fetch_type_rows(lock, number, &form)?;
+
+            // A row no longer valid is skipped rather than failing the whole read.
+            let mut translations = vec![];
+            for (language, translation) in Self::fetch_name_rows(lock, number, &form)? {
+                match (
+                    Language::try_from(language),
+                    PokemonName::try_from(translation),
+                ) {
+                    (Ok(language), Ok(translation)) => translations.push((language, translation)),
+                    _ => (self.on_invalid_row)("translation", number, &form),
+                }
+            }
+
+            match (
+                PokemonNumber::try_from(number),
+                PokemonForm::try_from(form.clone()),
+                PokemonName::try_from(name),
+                PokemonTypes::try_from(type_rows),
+                PokemonStats::try_from(stats),
+            ) {
+                (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => pokemons.push((
+                    Pokemon::new(number, form, name, types, stats).with_translations(translations),
+                    deleted_at,
+                )),
+                _ => (self.on_invalid_row)("pokemon", number, &form),
+            };
+        }
+
+        Ok(pokemons)
+    }
 }
 
 impl Repository for SqliteRepository {
     fn insert(
         &self,
         number: PokemonNumber,
+        form: PokemonForm,
         name: PokemonName,
         types: PokemonTypes,
+        stats: PokemonStats,
     ) -> Result<Pokemon, InsertError> {
         let mut lock = match self.connection.lock() {
             Ok(lock) => lock,
@@ -242,33 +839,53 @@ impl Repository for SqliteRepository {
             _ => return Err(InsertError::Unknown),
         };
 
+        if transaction
+            .execute(
+                "insert or ignore into species (number) values (?)",
+                params![number.to_u16()],
+            )
+            .is_err()
+        {
+            return Err(InsertError::Unknown);
+        }
+
         match transaction.execute(
-            "insert into pokemons (number, name) values (?, ?)",
-            params![u16::from(number.clone()), name.clone().to_string()],
+            "insert into pokemons (number, form, name, name_key, hp, attack, defense, speed) values (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                number.to_u16(),
+                form.to_string(),
+                name.to_string(),
+                name.key(),
+                stats.hp(),
+                stats.attack(),
+                stats.defense(),
+                stats.speed()
+            ],
         ) {
             Ok(_) => {}
             Err(SqliteFailure(_, Some(message)))
-                if message == "UNIQUE constraint failed: pokemons.number" =>
+                if message == "UNIQUE constraint failed: pokemons.number, pokemons.form"
+                    || message == "UNIQUE constraint failed: pokemons.name_key" =>
             {
                 return Err(InsertError::Conflict);
             }
-            Err(SqliteFailure(_, Some(message))) => {
-                return Err(InsertError::Unknown);
-            }
             _ => return Err(InsertError::Unknown),
         };
 
-        for _type in types.clone().to_vec_string() {
-            if let Err(_) = transaction.execute(
-                "insert into types (pokemon_number, name) values (?, ?)",
-                params![u16::from(number.clone()), _type],
-            ) {
+        for _type in types.to_vec_string() {
+            if transaction
+                .execute(
+                    "insert into types (pokemon_number, pokemon_form, name) values (?, ?, ?)",
+                    params![number.to_u16(), form.to_string(), _type],
+                )
+                .is_err()
+            {
                 return Err(InsertError::Unknown);
             }
         }
 
         match transaction.commit() {
-            Ok(_) => Ok(Pokemon::new(number, name, types)),
+            Ok(_) => Ok(Pokemon::new(number, form, name, types, stats)),
             _ => Err(InsertError::Unknown),
         }
     }
@@ -279,80 +896,362 @@ impl Repository for SqliteRepository {
             _ => return Err(FetchAllError::Unknown),
         };
 
-        let pokemon_rows = match Self::fetch_pokemon_rows(&lock, None) {
-            Ok(pokemon_rows) => pokemon_rows,
-            _ => return Err(FetchAllError::Unknown),
-        };
+        match self.fetch_pokemons(&lock, None, None) {
+            Ok(pokemons) => Ok(pokemons),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
 
-        let mut pokemons = vec![];
+    fn fetch_one(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+    ) -> Result<Pokemon, FetchOneError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
 
-        for pokemon_row in pokemon_rows {
-            let type_rows = match Self::fetch_type_rows(&lock, pokemon_row.0) {
-                Ok(type_rows) => type_rows,
-                _ => return Err(FetchAllError::Unknown),
+        let mut pokemons =
+            match self.fetch_pokemons(&lock, Some(number.to_u16()), Some(form.to_string())) {
+                Ok(pokemons) => pokemons,
+                _ => return Err(FetchOneError::Unknown),
             };
 
-            let pokemon = match (
-                PokemonNumber::try_from(pokemon_row.0),
-                PokemonName::try_from(pokemon_row.1),
-                PokemonTypes::try_from(type_rows),
-            ) {
-                (Ok(number), Ok(name), Ok(types)) => Pokemon::new(number, name, types),
-                _ => return Err(FetchAllError::Unknown),
-            };
+        if pokemons.is_empty() {
+            return Err(FetchOneError::NotFound);
+        }
 
-            pokemons.push(pokemon);
+        Ok(pokemons.remove(0))
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Pokemon>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let search_rows = match Self::search_rows(&lock, query) {
+            Ok(search_rows) => search_rows,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut pokemons = vec![];
+        for (number, form) in search_rows {
+            match self.fetch_pokemons(&lock, Some(number), Some(form)) {
+                Ok(found) => pokemons.extend(found),
+                _ => return Err(FetchAllError::Unknown),
+            }
         }
 
+        pokemons.sort_by(form_order);
         Ok(pokemons)
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchOneError> {
         let lock = match self.connection.lock() {
             Ok(lock) => lock,
             _ => return Err(FetchOneError::Unknown),
         };
 
-        let mut pokemon_rows =
-            match Self::fetch_pokemon_rows(&lock, Some(u16::from(number.clone()))) {
-                Ok(pokemon_rows) => pokemon_rows,
-                _ => return Err(FetchOneError::Unknown),
-            };
+        let (number, form) = match lock.query_row(
+            "select number, form from pokemons where name_key = ? and deleted_at is null",
+            params![name.key()],
+            |row| Ok((row.get::<usize, u16>(0)?, row.get::<usize, String>(1)?)),
+        ) {
+            Ok(key) => key,
+            Err(QueryReturnedNoRows) => return Err(FetchOneError::NotFound),
+            _ => return Err(FetchOneError::Unknown),
+        };
 
-        if pokemon_rows.is_empty() {
-            return Err(FetchOneError::NotFound);
+        // An invalid row is skipped when read, as if there were none.
+        match self.fetch_pokemons(&lock, Some(number), Some(form)) {
+            Ok(pokemons) if pokemons.is_empty() => Err(FetchOneError::NotFound),
+            Ok(mut pokemons) => Ok(pokemons.remove(0)),
+            _ => Err(FetchOneError::Unknown),
         }
+    }
 
-        let pokemon_row = pokemon_rows.remove(0);
-
-        let type_rows = match Self::fetch_type_rows(&lock, pokemon_row.0) {
-            Ok(type_rows) => type_rows,
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchOneError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
             _ => return Err(FetchOneError::Unknown),
         };
 
-        match (
-            PokemonNumber::try_from(pokemon_row.0),
-            PokemonName::try_from(pokemon_row.1),
-            PokemonTypes::try_from(type_rows),
-        ) {
-            (Ok(number), Ok(name), Ok(types)) => Ok(Pokemon::new(number, name, types)),
+        match self.fetch_pokemons(&lock, Some(number.to_u16()), None) {
+            Ok(pokemons) if pokemons.is_empty() => Err(FetchOneError::NotFound),
+            Ok(pokemons) => Ok(pokemons),
             _ => Err(FetchOneError::Unknown),
         }
     }
 
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        deleted_at: u64,
+    ) -> Result<(), DeleteError> {
         let lock = match self.connection.lock() {
             Ok(lock) => lock,
             _ => return Err(DeleteError::Unknown),
         };
 
         match lock.execute(
-            "delete from pokemons where number = ?",
-            params![u16::from(number)],
+            "update pokemons set deleted_at = ? where number = ? and form = ? and deleted_at is null",
+            params![deleted_at, number.to_u16(), form.to_string()],
         ) {
             Ok(0) => Err(DeleteError::NotFound),
             Ok(_) => Ok(()),
             _ => Err(DeleteError::Unknown),
         }
     }
+
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        match self.fetch_pokemons_in(&lock, None, None, true) {
+            Ok(pokemons) => Ok(pokemons
+                .into_iter()
+                .map(|(pokemon, deleted_at)| DeletedPokemon {
+                    pokemon,
+                    deleted_at: deleted_at.unwrap_or_default(),
+                })
+                .collect()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn restore(&self, number: PokemonNumber, form: PokemonForm) -> Result<Pokemon, RestoreError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RestoreError::Unknown),
+        };
+
+        match lock.execute(
+            "update pokemons set deleted_at = null where number = ? and form = ? and deleted_at is not null",
+            params![number.to_u16(), form.to_string()],
+        ) {
+            Ok(0) => return Err(RestoreError::NotFound),
+            Ok(_) => {}
+            _ => return Err(RestoreError::Unknown),
+        };
+
+        match self.fetch_pokemons(&lock, Some(number.to_u16()), Some(form.to_string())) {
+            Ok(mut pokemons) if !pokemons.is_empty() => Ok(pokemons.remove(0)),
+            _ => Err(RestoreError::Unknown),
+        }
+    }
+
+    fn purge(&self, deleted_until: u64) -> Result<usize, PurgeError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PurgeError::Unknown),
+        };
+
+        // The species stay, the teams and the Pokedex progress referencing them.
+        match lock.execute(
+            "delete from pokemons where deleted_at <= ?",
+            params![deleted_until],
+        ) {
+            Ok(purged) => Ok(purged),
+            _ => Err(PurgeError::Unknown),
+        }
+    }
+
+    fn save_name(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        language: Language,
+        name: PokemonName,
+    ) -> Result<Pokemon, SaveNameError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(SaveNameError::Unknown),
+        };
+
+        match lock.query_row(
+            "select 1 from pokemons where number = ? and form = ? and deleted_at is null",
+            params![number.to_u16(), form.to_string()],
+            |_| Ok(()),
+        ) {
+            Ok(()) => {}
+            Err(QueryReturnedNoRows) => return Err(SaveNameError::NotFound),
+            _ => return Err(SaveNameError::Unknown),
+        };
+
+        let saved = if language.is_english() {
+            lock.execute(
+                "update pokemons set name = ?, name_key = ? where number = ? and form = ?",
+                params![
+                    name.to_string(),
+                    name.key(),
+                    number.to_u16(),
+                    form.to_string()
+                ],
+            )
+        } else {
+            lock.execute(
+                "insert into pokemon_names (pokemon_number, pokemon_form, language, name, name_key) values (?, ?, ?, ?, ?) on conflict (pokemon_number, pokemon_form, language) do update set name = excluded.name, name_key = excluded.name_key",
+                params![
+                    number.to_u16(),
+                    form.to_string(),
+                    language.to_string(),
+                    name.to_string(),
+                    name.key()
+                ],
+            )
+        };
+
+        match saved {
+            Ok(0) => return Err(SaveNameError::NotFound),
+            Ok(_) => {}
+            Err(SqliteFailure(_, Some(message))) if message == "FOREIGN KEY constraint failed" => {
+                return Err(SaveNameError::NotFound);
+            }
+            Err(SqliteFailure(_, Some(message)))
+                if message.starts_with("UNIQUE constraint failed") =>
+            {
+                return Err(SaveNameError::Conflict);
+            }
+            _ => return Err(SaveNameError::Unknown),
+        };
+
+        match self.fetch_pokemons(&lock, Some(number.to_u16()), Some(form.to_string())) {
+            Ok(mut pokemons) if !pokemons.is_empty() => Ok(pokemons.remove(0)),
+            _ => Err(SaveNameError::Unknown),
+        }
+    }
+
+    fn count(&self) -> Result<usize, CountError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(CountError::Unknown),
+        };
+
+        match lock.query_row(
+            "select count(*) from pokemons where deleted_at is null",
+            [],
+            |row| row.get::<usize, usize>(0),
+        ) {
+            Ok(count) => Ok(count),
+            _ => Err(CountError::Unknown),
+        }
+    }
+
+    fn ping(&self) -> Result<(), PingError> {
+        // SQLite keeps reading a deleted database through its open file.
+        if !Path::new(&self.path).exists() {
+            return Err(PingError::Unknown);
+        }
+
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PingError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select 1 from pokemons limit 1") {
+            Ok(stmt) => stmt,
+            _ => return Err(PingError::Unknown),
+        };
+        match stmt.exists([]) {
+            Ok(_) => Ok(()),
+            _ => Err(PingError::Unknown),
+        }
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        close_connection(&self.connection).map_err(|_| CloseError::Unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn legacy_database() -> String {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path =
+            std::env::temp_dir().join(format!("pokedex-{}-{}.sqlite", std::process::id(), nanos));
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "create table species (number integer primary key);
+                create table pokemons (number integer not null, form text not null default 'default', name text not null, name_key text not null unique, hp integer not null, attack integer not null, defense integer not null, speed integer not null, deleted_at integer, primary key (number, form));
+                create table types (pokemon_number integer, pokemon_form text, name text, primary key (pokemon_number, pokemon_form, name));
+                create table pokemon_names (pokemon_number integer, pokemon_form text, language text, name text not null, name_key text not null, primary key (pokemon_number, pokemon_form, language));
+                insert into species values (4), (25);
+                insert into pokemons values (25, 'default', 'Pikachu', 'pikachu', 35, 55, 40, 90, null);
+                insert into types values (25, 'default', 'Electric');
+                insert into pokemon_names values (25, 'default', 'fr', 'Pika<chu>', 'pika<chu>');
+                insert into pokemons values (4, 'default', 'Charmander!', 'charmander', 39, 52, 43, 65, null);
+                insert into types values (4, 'default', 'Fire');",
+            )
+            .unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn it_should_skip_the_legacy_rows_when_reading_the_pokedex() {
+        let path = legacy_database();
+        let skipped = Arc::new(Mutex::new(vec![]));
+        let reported = skipped.clone();
+        let repo = SqliteRepository::try_new(&path)
+            .ok()
+            .unwrap()
+            .on_invalid_row(move |row, number, _| reported.lock().unwrap().push((row, number)));
+
+        let pokemons = repo.fetch_all().ok().unwrap();
+
+        std::fs::remove_file(&path).ok();
+        assert_eq!(pokemons.len(), 1);
+        assert_eq!(u16::from(pokemons[0].number.clone()), 25);
+        assert_eq!(pokemons[0].translations.len(), 0);
+        assert_eq!(
+            *skipped.lock().unwrap(),
+            vec![("pokemon", 4), ("translation", 25)]
+        );
+    }
+
+    #[test]
+    fn it_should_keep_the_species_when_purging_the_trash() {
+        let path = legacy_database();
+        let repo = SqliteRepository::try_new(&path).ok().unwrap();
+
+        repo.delete(PokemonNumber::pikachu(), PokemonForm::default(), 1)
+            .ok();
+        let purged = repo.purge(1);
+        let species =
+            Connection::open(&path)
+                .unwrap()
+                .query_row("select count(*) from species", [], |row| {
+                    row.get::<usize, u32>(0)
+                });
+
+        std::fs::remove_file(&path).ok();
+        assert_eq!(purged.ok(), Some(1));
+        assert_eq!(species.ok(), Some(2));
+    }
+
+    #[test]
+    fn it_should_not_find_a_legacy_row_by_its_name() {
+        let path = legacy_database();
+        let repo = SqliteRepository::try_new(&path).ok().unwrap();
+
+        let res = repo.fetch_by_name(PokemonName::charmander());
+
+        std::fs::remove_file(&path).ok();
+        match res {
+            Err(FetchOneError::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
 }
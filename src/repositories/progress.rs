@@ -0,0 +1,270 @@
+use crate::domain::entities::PokemonNumber;
+use crate::domain::progress::{DexEntry, EntryStatus, ProfileName};
+use std::{
+    convert::TryFrom,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::repositories::close_connection;
+use rusqlite::{params, Connection, OpenFlags};
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum FetchOneError {
+    NotFound,
+    Unknown,
+}
+
+pub enum SaveError {
+    Unknown,
+}
+
+pub enum CloseError {
+    Unknown,
+}
+
+pub trait ProgressRepository: Send + Sync {
+    /// Inserts the entry or replaces the one already recorded for this profile.
+    fn save(&self, profile: ProfileName, entry: DexEntry) -> Result<DexEntry, SaveError>;
+
+    fn fetch_entries(&self, profile: ProfileName) -> Result<Vec<DexEntry>, FetchAllError>;
+
+    fn fetch_entry(
+        &self,
+        profile: ProfileName,
+        number: PokemonNumber,
+    ) -> Result<DexEntry, FetchOneError>;
+
+    /// Waits for the running operation, if any, then releases the storage.
+    /// The operations made afterwards fail.
+    fn close(&self) -> Result<(), CloseError>;
+}
+
+pub struct InMemoryProgressRepository {
+    error: bool,
+    entries: Mutex<Vec<(ProfileName, DexEntry)>>,
+}
+
+impl InMemoryProgressRepository {
+    pub fn new() -> Self {
+        Self {
+            error: false,
+            entries: Mutex::new(vec![]),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl ProgressRepository for InMemoryProgressRepository {
+    fn save(&self, profile: ProfileName, entry: DexEntry) -> Result<DexEntry, SaveError> {
+        if self.error {
+            return Err(SaveError::Unknown);
+        }
+
+        let mut lock = match self.entries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(SaveError::Unknown),
+        };
+
+        lock.retain(|(p, e)| !(*p == profile && e.number == entry.number));
+        lock.push((profile, entry.clone()));
+        Ok(entry)
+    }
+
+    fn fetch_entries(&self, profile: ProfileName) -> Result<Vec<DexEntry>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        let lock = match self.entries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut entries = lock
+            .iter()
+            .filter(|(p, _)| *p == profile)
+            .map(|(_, e)| e.clone())
+            .collect::<Vec<DexEntry>>();
+        entries.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(entries)
+    }
+
+    fn fetch_entry(
+        &self,
+        profile: ProfileName,
+        number: PokemonNumber,
+    ) -> Result<DexEntry, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let lock = match self.entries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock
+            .iter()
+            .find(|(p, e)| *p == profile && e.number == number)
+        {
+            Some((_, entry)) => Ok(entry.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        Ok(())
+    }
+}
+
+pub struct SqliteProgressRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteProgressRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match connection.execute("pragma foreign_keys = 1", []) {
+            Ok(_) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_entry_rows(
+        lock: &MutexGuard<'_, Connection>,
+        profile: String,
+        number: Option<u16>,
+    ) -> Result<Vec<DexEntry>, ()> {
+        let query = match number {
+            Some(_) => "select pokemon_number, caught, shiny, form, seen_at, caught_at from dex_entries where profile = ?1 and pokemon_number = ?2",
+            _ => "select pokemon_number, caught, shiny, form, seen_at, caught_at from dex_entries where profile = ?1 and ?2 is null order by pokemon_number",
+        };
+
+        let mut stmt = match lock.prepare(query) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query(params![profile, number]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut entries = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            let entry = match (
+                row.get::<usize, u16>(0),
+                row.get::<usize, bool>(1),
+                row.get::<usize, bool>(2),
+                row.get::<usize, Option<String>>(3),
+                row.get::<usize, u64>(4),
+                row.get::<usize, Option<u64>>(5),
+            ) {
+                (Ok(number), Ok(caught), Ok(shiny), Ok(form), Ok(seen_at), Ok(caught_at)) => {
+                    match PokemonNumber::try_from(number) {
+                        Ok(number) => DexEntry {
+                            number,
+                            status: if caught {
+                                EntryStatus::Caught
+                            } else {
+                                EntryStatus::Seen
+                            },
+                            shiny,
+                            form,
+                            seen_at,
+                            caught_at,
+                        },
+                        _ => return Err(()),
+                    }
+                }
+                _ => return Err(()),
+            };
+
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+}
+
+impl ProgressRepository for SqliteProgressRepository {
+    fn save(&self, profile: ProfileName, entry: DexEntry) -> Result<DexEntry, SaveError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(SaveError::Unknown),
+        };
+
+        match lock.execute(
+            "insert or replace into dex_entries (profile, pokemon_number, caught, shiny, form, seen_at, caught_at) values (?, ?, ?, ?, ?, ?, ?)",
+            params![
+                String::from(profile),
+                entry.number.to_u16(),
+                entry.status == EntryStatus::Caught,
+                entry.shiny,
+                entry.form,
+                entry.seen_at,
+                entry.caught_at
+            ],
+        ) {
+            Ok(_) => Ok(entry),
+            _ => Err(SaveError::Unknown),
+        }
+    }
+
+    fn fetch_entries(&self, profile: ProfileName) -> Result<Vec<DexEntry>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        match Self::fetch_entry_rows(&lock, String::from(profile), None) {
+            Ok(entries) => Ok(entries),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_entry(
+        &self,
+        profile: ProfileName,
+        number: PokemonNumber,
+    ) -> Result<DexEntry, FetchOneError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut entries =
+            match Self::fetch_entry_rows(&lock, String::from(profile), Some(number.to_u16())) {
+                Ok(entries) => entries,
+                _ => return Err(FetchOneError::Unknown),
+            };
+
+        if entries.is_empty() {
+            return Err(FetchOneError::NotFound);
+        }
+
+        Ok(entries.remove(0))
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        close_connection(&self.connection).map_err(|_| CloseError::Unknown)
+    }
+}
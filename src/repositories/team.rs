@@ -0,0 +1,501 @@
+use crate::domain::entities::PokemonNumber;
+use crate::domain::team::{Team, TeamId, TeamMember, TeamName};
+use rusqlite::Error::SqliteFailure;
+use std::{
+    convert::TryFrom,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::repositories::close_connection;
+use rusqlite::{params, Connection, OpenFlags, Transaction};
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum FetchOneError {
+    NotFound,
+    Unknown,
+}
+
+pub enum InsertError {
+    Conflict,
+    Unknown,
+}
+
+pub enum UpdateError {
+    NotFound,
+    Conflict,
+    Unknown,
+}
+
+pub enum DeleteError {
+    NotFound,
+    Unknown,
+}
+
+pub enum CloseError {
+    Unknown,
+}
+
+pub trait TeamRepository: Send + Sync {
+    fn insert(&self, name: TeamName, members: Vec<TeamMember>) -> Result<Team, InsertError>;
+
+    fn fetch_all(&self) -> Result<Vec<Team>, FetchAllError>;
+
+    fn fetch_one(&self, id: TeamId) -> Result<Team, FetchOneError>;
+
+    fn update(&self, team: Team) -> Result<Team, UpdateError>;
+
+    fn delete(&self, id: TeamId) -> Result<(), DeleteError>;
+
+    /// Waits for the running operation, if any, then releases the storage.
+    /// The operations made afterwards fail.
+    fn close(&self) -> Result<(), CloseError>;
+}
+
+pub struct InMemoryTeamRepository {
+    error: bool,
+    teams: Mutex<Vec<Team>>,
+    next_id: Mutex<u32>,
+}
+
+impl InMemoryTeamRepository {
+    pub fn new() -> Self {
+        Self {
+            error: false,
+            teams: Mutex::new(vec![]),
+            next_id: Mutex::new(1),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl TeamRepository for InMemoryTeamRepository {
+    fn insert(&self, name: TeamName, members: Vec<TeamMember>) -> Result<Team, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let (mut lock, mut next_id) = match (self.teams.lock(), self.next_id.lock()) {
+            (Ok(lock), Ok(next_id)) => (lock, next_id),
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if lock.iter().any(|team| team.name == name) {
+            return Err(InsertError::Conflict);
+        }
+
+        let team = Team::new(TeamId::from(*next_id), name, members);
+        *next_id += 1;
+
+        lock.push(team.clone());
+        Ok(team)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Team>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        let lock = match self.teams.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut teams = lock.to_vec();
+        teams.sort_by_key(|team| team.id);
+        Ok(teams)
+    }
+
+    fn fetch_one(&self, id: TeamId) -> Result<Team, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let lock = match self.teams.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock.iter().find(|t| t.id == id) {
+            Some(team) => Ok(team.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn update(&self, team: Team) -> Result<Team, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        let mut lock = match self.teams.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if lock.iter().any(|t| t.name == team.name && t.id != team.id) {
+            return Err(UpdateError::Conflict);
+        }
+
+        match lock.iter_mut().find(|t| t.id == team.id) {
+            Some(stored) => {
+                *stored = team.clone();
+                Ok(team)
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn delete(&self, id: TeamId) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        let mut lock = match self.teams.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let index = match lock.iter().position(|t| t.id == id) {
+            Some(index) => index,
+            None => return Err(DeleteError::NotFound),
+        };
+
+        lock.remove(index);
+        Ok(())
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        Ok(())
+    }
+}
+
+pub struct SqliteTeamRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteTeamRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match connection.execute("pragma foreign_keys = 1", []) {
+            Ok(_) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_team_rows(
+        lock: &MutexGuard<'_, Connection>,
+        id: Option<u32>,
+    ) -> Result<Vec<(u32, String)>, ()> {
+        let (query, params) = match id {
+            Some(id) => ("select id, name from teams where id = ?", vec![id]),
+            _ => ("select id, name from teams order by id", vec![]),
+        };
+
+        let mut stmt = match lock.prepare(query) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query(rusqlite::params_from_iter(params)) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut team_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (row.get::<usize, u32>(0), row.get::<usize, String>(1)) {
+                (Ok(id), Ok(name)) => team_rows.push((id, name)),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(team_rows)
+    }
+
+    fn fetch_member_rows(
+        lock: &MutexGuard<'_, Connection>,
+        team_id: u32,
+    ) -> Result<Vec<TeamMember>, ()> {
+        let mut stmt = match lock.prepare(
+            "select slot, pokemon_number, nickname, level, held_item from team_members where team_id = ? order by slot",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query([team_id]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut member_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, u8>(0),
+                row.get::<usize, u16>(1),
+                row.get::<usize, Option<String>>(2),
+                row.get::<usize, u8>(3),
+                row.get::<usize, Option<String>>(4),
+            ) {
+                (Ok(slot), Ok(number), Ok(nickname), Ok(level), Ok(held_item)) => {
+                    member_rows.push((slot, number, nickname, level, held_item))
+                }
+                _ => return Err(()),
+            };
+        }
+
+        let mut members = vec![];
+
+        for (slot, number, nickname, level, held_item) in member_rows {
+            let moves = Self::fetch_move_rows(lock, team_id, slot)?;
+
+            match PokemonNumber::try_from(number) {
+                Ok(number) => members.push(TeamMember::try_new(
+                    number, nickname, level, moves, held_item,
+                )?),
+                _ => return Err(()),
+            }
+        }
+
+        Ok(members)
+    }
+
+    fn fetch_move_rows(
+        lock: &MutexGuard<'_, Connection>,
+        team_id: u32,
+        slot: u8,
+    ) -> Result<Vec<String>, ()> {
+        let mut stmt = match lock.prepare(
+            "select name from team_member_moves where team_id = ? and slot = ? order by position",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query(params![team_id, slot]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut move_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match row.get::<usize, String>(0) {
+                Ok(name) => move_rows.push(name),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(move_rows)
+    }
+
+    fn insert_members(
+        transaction: &Transaction<'_>,
+        team_id: u32,
+        members: &[TeamMember],
+    ) -> Result<(), ()> {
+        for (slot, member) in members.iter().enumerate() {
+            if transaction
+                .execute(
+                    "insert into team_members (team_id, slot, pokemon_number, nickname, level, held_item) values (?, ?, ?, ?, ?, ?)",
+                    params![
+                        team_id,
+                        slot,
+                        member.number.to_u16(),
+                        member.nickname,
+                        member.level,
+                        member.held_item
+                    ],
+                )
+                .is_err()
+            {
+                return Err(());
+            }
+
+            for (position, name) in member.moves.iter().enumerate() {
+                if transaction
+                    .execute(
+                        "insert into team_member_moves (team_id, slot, position, name) values (?, ?, ?, ?)",
+                        params![team_id, slot, position, name],
+                    )
+                    .is_err()
+                {
+                    return Err(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fetch_team(lock: &MutexGuard<'_, Connection>, row: (u32, String)) -> Result<Team, ()> {
+        let members = Self::fetch_member_rows(lock, row.0)?;
+
+        match TeamName::try_from(row.1) {
+            Ok(name) => Ok(Team::new(TeamId::from(row.0), name, members)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TeamRepository for SqliteTeamRepository {
+    fn insert(&self, name: TeamName, members: Vec<TeamMember>) -> Result<Team, InsertError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        match transaction.execute(
+            "insert into teams (name) values (?)",
+            params![String::from(name.clone())],
+        ) {
+            Ok(_) => {}
+            Err(SqliteFailure(_, Some(message)))
+                if message == "UNIQUE constraint failed: teams.name" =>
+            {
+                return Err(InsertError::Conflict);
+            }
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let id = transaction.last_insert_rowid() as u32;
+
+        if Self::insert_members(&transaction, id, &members).is_err() {
+            return Err(InsertError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(Team::new(TeamId::from(id), name, members)),
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Team>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let team_rows = match Self::fetch_team_rows(&lock, None) {
+            Ok(team_rows) => team_rows,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut teams = vec![];
+
+        for team_row in team_rows {
+            match Self::fetch_team(&lock, team_row) {
+                Ok(team) => teams.push(team),
+                _ => return Err(FetchAllError::Unknown),
+            }
+        }
+
+        Ok(teams)
+    }
+
+    fn fetch_one(&self, id: TeamId) -> Result<Team, FetchOneError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut team_rows = match Self::fetch_team_rows(&lock, Some(id.to_u32())) {
+            Ok(team_rows) => team_rows,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        if team_rows.is_empty() {
+            return Err(FetchOneError::NotFound);
+        }
+
+        match Self::fetch_team(&lock, team_rows.remove(0)) {
+            Ok(team) => Ok(team),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn update(&self, team: Team) -> Result<Team, UpdateError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match transaction.execute(
+            "update teams set name = ? where id = ?",
+            params![String::from(team.name.clone()), team.id.to_u32()],
+        ) {
+            Ok(0) => return Err(UpdateError::NotFound),
+            Ok(_) => {}
+            Err(SqliteFailure(_, Some(message)))
+                if message == "UNIQUE constraint failed: teams.name" =>
+            {
+                return Err(UpdateError::Conflict);
+            }
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "delete from team_members where team_id = ?",
+                params![team.id.to_u32()],
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        if Self::insert_members(&transaction, team.id.to_u32(), &team.members).is_err() {
+            return Err(UpdateError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(team),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn delete(&self, id: TeamId) -> Result<(), DeleteError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match lock.execute("delete from teams where id = ?", params![id.to_u32()]) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        close_connection(&self.connection).map_err(|_| CloseError::Unknown)
+    }
+}
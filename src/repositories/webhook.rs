@@ -0,0 +1,463 @@
+use crate::domain::webhook::{
+    DeadLetter, Delivery, DeliveryStatus, Webhook, WebhookId, WebhookSecret, WebhookUrl,
+};
+use rusqlite::Error::SqliteFailure;
+use std::{convert::TryFrom, sync::Mutex};
+
+use crate::repositories::close_connection;
+use rusqlite::{params, Connection, OpenFlags};
+
+pub enum InsertError {
+    Conflict,
+    Unknown,
+}
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum FetchOneError {
+    NotFound,
+    Unknown,
+}
+
+pub enum DeleteError {
+    NotFound,
+    Unknown,
+}
+
+pub enum RecordError {
+    Unknown,
+}
+
+pub enum CloseError {
+    Unknown,
+}
+
+/// Deleting a webhook also deletes its deliveries and dead letters.
+pub trait WebhookRepository: Send + Sync {
+    fn insert(&self, url: WebhookUrl, secret: WebhookSecret) -> Result<Webhook, InsertError>;
+
+    fn fetch_all(&self) -> Result<Vec<Webhook>, FetchAllError>;
+
+    fn delete(&self, id: WebhookId) -> Result<(), DeleteError>;
+
+    fn record_delivery(&self, delivery: Delivery) -> Result<(), RecordError>;
+
+    /// The delivery log of a webhook, oldest first.
+    fn fetch_deliveries(&self, id: WebhookId) -> Result<Vec<Delivery>, FetchOneError>;
+
+    fn record_dead_letter(&self, dead_letter: DeadLetter) -> Result<(), RecordError>;
+
+    /// The events no webhook could receive, oldest first.
+    fn fetch_dead_letters(&self) -> Result<Vec<DeadLetter>, FetchAllError>;
+
+    /// Waits for the running operation, if any, then releases the storage.
+    /// The operations made afterwards fail.
+    fn close(&self) -> Result<(), CloseError>;
+}
+
+pub struct InMemoryWebhookRepository {
+    error: bool,
+    webhooks: Mutex<Vec<Webhook>>,
+    deliveries: Mutex<Vec<Delivery>>,
+    dead_letters: Mutex<Vec<DeadLetter>>,
+    next_id: Mutex<u32>,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new() -> Self {
+        Self {
+            error: false,
+            webhooks: Mutex::new(vec![]),
+            deliveries: Mutex::new(vec![]),
+            dead_letters: Mutex::new(vec![]),
+            next_id: Mutex::new(1),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl WebhookRepository for InMemoryWebhookRepository {
+    fn insert(&self, url: WebhookUrl, secret: WebhookSecret) -> Result<Webhook, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let (mut lock, mut next_id) = match (self.webhooks.lock(), self.next_id.lock()) {
+            (Ok(lock), Ok(next_id)) => (lock, next_id),
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if lock.iter().any(|webhook| webhook.url == url) {
+            return Err(InsertError::Conflict);
+        }
+
+        let webhook = Webhook {
+            id: WebhookId::from(*next_id),
+            url,
+            secret,
+        };
+        *next_id += 1;
+
+        lock.push(webhook.clone());
+        Ok(webhook)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Webhook>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        match self.webhooks.lock() {
+            Ok(lock) => Ok(lock.to_vec()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn delete(&self, id: WebhookId) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        let (mut lock, mut deliveries, mut dead_letters) = match (
+            self.webhooks.lock(),
+            self.deliveries.lock(),
+            self.dead_letters.lock(),
+        ) {
+            (Ok(lock), Ok(deliveries), Ok(dead_letters)) => (lock, deliveries, dead_letters),
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let index = match lock.iter().position(|w| w.id == id) {
+            Some(index) => index,
+            None => return Err(DeleteError::NotFound),
+        };
+
+        lock.remove(index);
+        deliveries.retain(|d| d.webhook != id);
+        dead_letters.retain(|d| d.webhook != id);
+        Ok(())
+    }
+
+    fn record_delivery(&self, delivery: Delivery) -> Result<(), RecordError> {
+        if self.error {
+            return Err(RecordError::Unknown);
+        }
+
+        match self.deliveries.lock() {
+            Ok(mut lock) => {
+                lock.push(delivery);
+                Ok(())
+            }
+            _ => Err(RecordError::Unknown),
+        }
+    }
+
+    fn fetch_deliveries(&self, id: WebhookId) -> Result<Vec<Delivery>, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let (webhooks, deliveries) = match (self.webhooks.lock(), self.deliveries.lock()) {
+            (Ok(webhooks), Ok(deliveries)) => (webhooks, deliveries),
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        if !webhooks.iter().any(|w| w.id == id) {
+            return Err(FetchOneError::NotFound);
+        }
+
+        Ok(deliveries
+            .iter()
+            .filter(|d| d.webhook == id)
+            .cloned()
+            .collect())
+    }
+
+    fn record_dead_letter(&self, dead_letter: DeadLetter) -> Result<(), RecordError> {
+        if self.error {
+            return Err(RecordError::Unknown);
+        }
+
+        match self.dead_letters.lock() {
+            Ok(mut lock) => {
+                lock.push(dead_letter);
+                Ok(())
+            }
+            _ => Err(RecordError::Unknown),
+        }
+    }
+
+    fn fetch_dead_letters(&self) -> Result<Vec<DeadLetter>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        match self.dead_letters.lock() {
+            Ok(lock) => Ok(lock.to_vec()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        Ok(())
+    }
+}
+
+pub struct SqliteWebhookRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteWebhookRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match connection.execute("pragma foreign_keys = 1", []) {
+            Ok(_) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl WebhookRepository for SqliteWebhookRepository {
+    fn insert(&self, url: WebhookUrl, secret: WebhookSecret) -> Result<Webhook, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        match lock.execute(
+            "insert into webhooks (url, secret) values (?, ?)",
+            params![String::from(url.clone()), String::from(secret.clone())],
+        ) {
+            Ok(_) => Ok(Webhook {
+                id: WebhookId::from(lock.last_insert_rowid() as u32),
+                url,
+                secret,
+            }),
+            Err(SqliteFailure(_, Some(message)))
+                if message == "UNIQUE constraint failed: webhooks.url" =>
+            {
+                Err(InsertError::Conflict)
+            }
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Webhook>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select id, url, secret from webhooks order by id") {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut rows = match stmt.query([]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut webhooks = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, u32>(0),
+                row.get::<usize, String>(1).map(WebhookUrl::try_from),
+                row.get::<usize, String>(2).map(WebhookSecret::try_from),
+            ) {
+                (Ok(id), Ok(Ok(url)), Ok(Ok(secret))) => webhooks.push(Webhook {
+                    id: WebhookId::from(id),
+                    url,
+                    secret,
+                }),
+                _ => return Err(FetchAllError::Unknown),
+            };
+        }
+
+        Ok(webhooks)
+    }
+
+    fn delete(&self, id: WebhookId) -> Result<(), DeleteError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match lock.execute("delete from webhooks where id = ?", params![id.to_u32()]) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+
+    fn record_delivery(&self, delivery: Delivery) -> Result<(), RecordError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RecordError::Unknown),
+        };
+
+        let (status_code, error) = match delivery.status {
+            DeliveryStatus::Responded(code) => (Some(code), None),
+            DeliveryStatus::Unreachable(error) => (None, Some(error)),
+        };
+
+        match lock.execute(
+            "insert into webhook_deliveries (webhook_id, event, attempt, at, status_code, error) values (?, ?, ?, ?, ?, ?)",
+            params![
+                delivery.webhook.to_u32(),
+                delivery.event,
+                delivery.attempt,
+                delivery.at,
+                status_code,
+                error
+            ],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(RecordError::Unknown),
+        }
+    }
+
+    fn fetch_deliveries(&self, id: WebhookId) -> Result<Vec<Delivery>, FetchOneError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock.query_row(
+            "select count(*) from webhooks where id = ?",
+            params![id.to_u32()],
+            |row| row.get::<usize, u32>(0),
+        ) {
+            Ok(0) => return Err(FetchOneError::NotFound),
+            Ok(_) => {}
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare(
+            "select event, attempt, at, status_code, error from webhook_deliveries where webhook_id = ? order by id",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut rows = match stmt.query(params![id.to_u32()]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut deliveries = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            let status = match (
+                row.get::<usize, Option<u16>>(3),
+                row.get::<usize, Option<String>>(4),
+            ) {
+                (Ok(Some(code)), _) => DeliveryStatus::Responded(code),
+                (Ok(None), Ok(Some(error))) => DeliveryStatus::Unreachable(error),
+                _ => return Err(FetchOneError::Unknown),
+            };
+
+            match (
+                row.get::<usize, String>(0),
+                row.get::<usize, u32>(1),
+                row.get::<usize, u64>(2),
+            ) {
+                (Ok(event), Ok(attempt), Ok(at)) => deliveries.push(Delivery {
+                    webhook: id,
+                    event,
+                    attempt,
+                    at,
+                    status,
+                }),
+                _ => return Err(FetchOneError::Unknown),
+            };
+        }
+
+        Ok(deliveries)
+    }
+
+    fn record_dead_letter(&self, dead_letter: DeadLetter) -> Result<(), RecordError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RecordError::Unknown),
+        };
+
+        match lock.execute(
+            "insert into webhook_dead_letters (webhook_id, event, payload, attempts, failed_at) values (?, ?, ?, ?, ?)",
+            params![
+                dead_letter.webhook.to_u32(),
+                dead_letter.event,
+                dead_letter.payload,
+                dead_letter.attempts,
+                dead_letter.failed_at
+            ],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(RecordError::Unknown),
+        }
+    }
+
+    fn fetch_dead_letters(&self) -> Result<Vec<DeadLetter>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare(
+            "select webhook_id, event, payload, attempts, failed_at from webhook_dead_letters order by id",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut rows = match stmt.query([]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut dead_letters = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, u32>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, String>(2),
+                row.get::<usize, u32>(3),
+                row.get::<usize, u64>(4),
+            ) {
+                (Ok(webhook), Ok(event), Ok(payload), Ok(attempts), Ok(failed_at)) => dead_letters
+                    .push(DeadLetter {
+                        webhook: WebhookId::from(webhook),
+                        event,
+                        payload,
+                        attempts,
+                        failed_at,
+                    }),
+                _ => return Err(FetchAllError::Unknown),
+            };
+        }
+
+        Ok(dead_letters)
+    }
+
+    fn close(&self) -> Result<(), CloseError> {
+        close_connection(&self.connection).map_err(|_| CloseError::Unknown)
+    }
+}